@@ -8,7 +8,7 @@ use tokio::sync::broadcast;
 use tokio::time::{interval, Duration};
 
 use crate::{AppState, ApiSignal, config};
-use soma_core::StemProcessor;
+use soma_core::{StemEvent, StemProcessor};
 use soma_conscious::ConsciousState;
 use soma_conscious::{ReflectionAnalyzer, FeedbackController, CausalTrace};
 
@@ -48,6 +48,24 @@ pub async fn background_update(
     }
 }
 
+/// Фоновая задача периодического сохранения снапшота организма
+pub async fn snapshot_persist(stem: Arc<Mutex<StemProcessor>>, path: String) {
+    let mut tick = interval(Duration::from_secs(config::api::SNAPSHOT_INTERVAL_SEC));
+
+    loop {
+        tick.tick().await;
+
+        let snapshot = match stem.lock() {
+            Ok(s) => s.snapshot(),
+            Err(_) => continue,
+        };
+
+        if let Err(e) = snapshot.save(&path) {
+            println!("⚠️  Failed to save snapshot to {}: {}", path, e);
+        }
+    }
+}
+
 /// Фоновая задача синхронизации состояния mesh
 pub async fn mesh_state_sync(
     stem: Arc<Mutex<StemProcessor>>,
@@ -100,6 +118,70 @@ pub async fn mesh_resonance_sync(
     }
 }
 
+/// Запись событий морфогенеза в причинные цепи сознательного слоя
+pub async fn morphogenesis_traces(
+    conscious: Arc<Mutex<ConsciousState>>,
+    mut events: broadcast::Receiver<StemEvent>,
+) {
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+
+        let (cause, effect, delta) = match event {
+            StemEvent::ThresholdCrossed { load, threshold, rising } => (
+                "load_threshold".to_string(),
+                if rising { "threshold_exceeded" } else { "threshold_released" }.to_string(),
+                load - threshold,
+            ),
+            StemEvent::DimensionThresholdCrossed { dimension, load, threshold, rising } => (
+                format!("{:?}_load_threshold", dimension).to_lowercase(),
+                if rising { "threshold_exceeded" } else { "threshold_released" }.to_string(),
+                load - threshold,
+            ),
+            StemEvent::CellDivided { role, parent, .. } => (
+                parent.unwrap_or_else(|| "stem".to_string()),
+                format!("divided_{:?}", role),
+                1.0,
+            ),
+            StemEvent::DivisionRefused { role, reserve, cost } => (
+                "energy_deficit".to_string(),
+                format!("division_refused_{:?}", role),
+                reserve - cost,
+            ),
+            StemEvent::CellRemoved { id, cause, .. } => (
+                cause.map_or("removal".to_string(), |c| format!("{:?}", c).to_lowercase()),
+                format!("{}_removed", id),
+                -1.0,
+            ),
+            StemEvent::Transdifferentiated { id, from, to } => (
+                format!("{}_signal_history", id),
+                format!("transdifferentiated_{:?}_to_{:?}", from, to),
+                0.0,
+            ),
+            StemEvent::Budded { child, cells } => (
+                "cell_count".to_string(),
+                format!("budded_{}", child),
+                -(cells.len() as f64),
+            ),
+            StemEvent::LoadChanged { previous, load } => (
+                "load".to_string(),
+                format!("load_{:.2}", load),
+                load - previous,
+            ),
+        };
+
+        let mut state = match conscious.lock() {
+            Ok(s) => s,
+            Err(_) => continue,
+        };
+        let trace = CausalTrace::at(cause, effect, delta, state.clock().as_ref());
+        state.record_trace(trace);
+    }
+}
+
 /// Conscious Cycle - observe → record → analyze → generate → apply
 pub async fn conscious_cycle(
     conscious: Arc<Mutex<ConsciousState>>,
@@ -126,10 +208,11 @@ pub async fn conscious_cycle(
             // Для каждого изменения веса создаём trace
             for (peer_id, weight, _quality) in &link_weights {
                 if *weight != 0.3 { // Изменён от дефолта
-                    let trace = CausalTrace::new(
+                    let trace = CausalTrace::at(
                         format!("network_activity"),
                         format!("{}_weight_{:.3}", peer_id, weight),
                         weight - 0.3,
+                        state.clock().as_ref(),
                     );
                     state.record_trace(trace);
                 }
@@ -76,6 +76,9 @@ pub mod api {
     /// Размер broadcast канала для сигналов
     pub const SIGNAL_CHANNEL_SIZE: usize = 100;
 
+    /// Размер broadcast канала для событий стволового процессора
+    pub const EVENT_CHANNEL_SIZE: usize = 256;
+
     /// Интервал обновления фонового процесса (мс)
     pub const BACKGROUND_UPDATE_INTERVAL_MS: u64 = 100;
 
@@ -105,5 +108,11 @@ pub mod api {
 
     /// Количество топ связей для topology endpoint
     pub const DEFAULT_TOP_LINKS_COUNT: usize = 10;
+
+    /// Путь к снапшоту организма (по умолчанию)
+    pub const DEFAULT_SNAPSHOT_PATH: &str = "soma_snapshot.json";
+
+    /// Интервал сохранения снапшота организма
+    pub const SNAPSHOT_INTERVAL_SEC: u64 = 30;
 }
 
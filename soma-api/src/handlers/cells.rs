@@ -1,7 +1,10 @@
 //! Обработчики для работы с клетками
 
-use axum::extract::State;
+use axum::extract::{Path, State};
+use axum::http::header;
+use axum::response::IntoResponse;
 use axum::Json;
+use soma_core::LineageNode;
 
 use crate::{AppState, errors::ApiError, errors::lock_arc_mutex};
 
@@ -15,7 +18,7 @@ pub async fn get_cells(State(state): State<AppState>) -> Result<Json<Vec<crate::
             id: cell.id.clone(),
             role: format!("{:?}", cell.role),
             generation: cell.generation,
-            age_ms: cell.age_millis(),
+            age_ms: cell.age_at(stem.clock().now_millis()),
             activity: cell.activity,
         })
         .collect();
@@ -33,7 +36,48 @@ pub async fn get_distribution(State(state): State<AppState>) -> Result<Json<crat
         sensor: *dist.get(&CellRole::Sensor).unwrap_or(&0),
         logic: *dist.get(&CellRole::Logic).unwrap_or(&0),
         motor: *dist.get(&CellRole::Motor).unwrap_or(&0),
+        memory: *dist.get(&CellRole::Memory).unwrap_or(&0),
+        hub: *dist.get(&CellRole::Hub).unwrap_or(&0),
+        inhibitor: *dist.get(&CellRole::Inhibitor).unwrap_or(&0),
+        explorer: *dist.get(&CellRole::Explorer).unwrap_or(&0),
         total: stem.cell_count(),
     }))
 }
 
+
+/// Получить родословную всех клеток (включая умершие)
+pub async fn get_lineage(State(state): State<AppState>) -> Result<Json<Vec<LineageNode>>, ApiError> {
+    let stem = lock_arc_mutex(&state.stem)?;
+    Ok(Json(stem.lineage().nodes().cloned().collect()))
+}
+
+/// Получить родословную в формате GraphViz DOT
+pub async fn get_lineage_dot(State(state): State<AppState>) -> Result<impl IntoResponse, ApiError> {
+    let stem = lock_arc_mutex(&state.stem)?;
+    let dot = stem.lineage().to_dot();
+    Ok(([(header::CONTENT_TYPE, "text/vnd.graphviz")], dot))
+}
+
+/// Получить предков, потомков и статистику поддерева клетки
+pub async fn get_cell_lineage(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<crate::CellLineageResponse>, ApiError> {
+    let stem = lock_arc_mutex(&state.stem)?;
+    let lineage = stem.lineage();
+    let node = lineage
+        .get(&id)
+        .ok_or_else(|| ApiError::NotFound(format!("Cell {} not found in lineage", id)))?;
+    let stats = lineage.subtree_stats(&id).unwrap_or_default();
+
+    Ok(Json(crate::CellLineageResponse {
+        id: node.id.clone(),
+        role: format!("{:?}", node.role),
+        alive: node.is_alive(),
+        ancestors: lineage.ancestors(&id).iter().map(|n| n.id.clone()).collect(),
+        descendants: lineage.descendants(&id).iter().map(|n| n.id.clone()).collect(),
+        subtree_alive: stats.alive,
+        subtree_dead: stats.dead,
+        subtree_depth: stats.depth,
+    }))
+}
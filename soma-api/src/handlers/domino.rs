@@ -56,7 +56,7 @@ pub async fn domino_evaluate(
     Json(req): Json<DominoEvaluateRequest>,
 ) -> Json<DominoEvaluateResponse> {
     // Генерируем уникальный ID решения
-    let timestamp = chrono::Utc::now().timestamp_millis();
+    let timestamp = state.mesh.clock.now_millis() as i64;
     let decision_id = format!(
         "domino_{}_{}",
         state.mesh.id,
@@ -87,12 +87,12 @@ pub async fn domino_evaluate(
     let input = DominoInput::new(intent_kind.clone(), candidates.clone(), req.context_tags.clone());
 
     // Выполняем оценку
-    let decision = DominoEngine::evaluate(input);
+    let decision = DominoEngine::evaluate_on(input, state.mesh.clock.as_ref());
 
     // Создаём trace для Conscious Layer
     let trace = DominoDecisionTrace::new(
         decision_id.clone(),
-        chrono::Utc::now().timestamp_millis() as u64,
+        timestamp as u64,
         format!("{:?}", intent_kind),
         req.context_tags,
         req.candidates.iter().map(|c| c.peer_id.clone()).collect(),
@@ -226,7 +226,7 @@ pub async fn get_domino_insights(State(state): State<AppState>) -> Result<Json<s
 
     Ok(Json(serde_json::json!({
         "node_id": state.mesh.id,
-        "timestamp": chrono::Utc::now().timestamp_millis(),
+        "timestamp": state.mesh.clock.now_millis(),
         "total_decisions": decisions_count,
         "stats": stats,
         "insights": insights,
@@ -4,6 +4,7 @@
 
 pub mod system;
 pub mod cells;
+pub mod tissues;
 pub mod mesh;
 pub mod domino;
 pub mod conscious;
@@ -2,6 +2,7 @@
 
 use axum::{extract::State, Json};
 use serde_json::json;
+use soma_core::LoadVector;
 
 use crate::{AppState, responses::SuccessResponse};
 
@@ -30,11 +31,14 @@ pub async fn root(State(state): State<AppState>) -> Json<serde_json::Value> {
             "/state": "GET - System state",
             "/cells": "GET - List all cells",
             "/distribution": "GET - Role distribution",
+            "/tissues": "GET - Tissues with aggregate metrics",
+            "/organs": "GET - Organs with aggregate metrics",
             "/peers": "GET - Connected peers with health metrics",
             "/peers/register": "POST - Register peer for auto-reconnect {peer_id, url}",
             "/resonance": "GET - Network resonance stats with adaptive strength",
             "/signal": "POST - Send signal {id, value}",
             "/stimulate": "POST - Stimulate system {activity}",
+            "/stimulate/vector": "POST - Stimulate system {input, compute, output}",
             "/ws": "GET - WebSocket real-time stream",
             "/mesh": "GET - Mesh peer connection (WebSocket)"
         }
@@ -51,6 +55,7 @@ pub async fn get_state(State(state): State<AppState>) -> Result<Json<crate::Stat
         generation: stem.generation,
         load: stem.load,
         threshold: stem.threshold,
+        energy: stem.energy().map(|e| e.reserve()),
     }))
 }
 
@@ -77,6 +82,25 @@ pub async fn stimulate(
     })))
 }
 
+/// Стимулировать систему многомерной нагрузкой (деление в перегруженную роль)
+pub async fn stimulate_vector(
+    State(state): State<AppState>,
+    Json(req): Json<LoadVector>,
+) -> Result<Json<serde_json::Value>, crate::errors::ApiError> {
+    use crate::errors::lock_arc_mutex;
+
+    let mut stem = lock_arc_mutex(&state.stem)?;
+    stem.sense_vector(req);
+
+    Ok(Json(json!({
+        "status": "ok",
+        "load": stem.load,
+        "load_vector": stem.load_vector(),
+        "cells": stem.cell_count(),
+        "generation": stem.generation
+    })))
+}
+
 /// Отправить сигнал в систему
 pub async fn post_signal(
     State(state): State<AppState>,
@@ -0,0 +1,83 @@
+//! Обработчики для тканей и органов
+
+use axum::extract::{Path, State};
+use axum::Json;
+use serde_json::json;
+use soma_core::{StemProcessor, TissueId};
+
+use crate::{AppState, errors::ApiError, errors::lock_arc_mutex};
+
+/// Найти ткань по имени
+fn find_tissue(stem: &StemProcessor, name: &str) -> Result<TissueId, ApiError> {
+    stem.anatomy()
+        .find_tissue(name)
+        .ok_or_else(|| ApiError::NotFound(format!("Tissue {} not found", name)))
+}
+
+/// Получить все ткани со сводными метриками
+pub async fn get_tissues(State(state): State<AppState>) -> Result<Json<Vec<crate::GroupResponse>>, ApiError> {
+    let stem = lock_arc_mutex(&state.stem)?;
+    let anatomy = stem.anatomy();
+    let tissues = anatomy
+        .tissues()
+        .filter_map(|(id, tissue)| {
+            let organ = tissue.organ.and_then(|o| anatomy.organ(o)).map(|o| o.name.clone());
+            Some(crate::GroupResponse {
+                name: tissue.name.clone(),
+                parent: organ,
+                metrics: stem.tissue_metrics(id)?,
+            })
+        })
+        .collect();
+    Ok(Json(tissues))
+}
+
+/// Получить все органы со сводными метриками
+pub async fn get_organs(State(state): State<AppState>) -> Result<Json<Vec<crate::GroupResponse>>, ApiError> {
+    let stem = lock_arc_mutex(&state.stem)?;
+    let organs = stem
+        .anatomy()
+        .organs()
+        .filter_map(|(id, organ)| {
+            Some(crate::GroupResponse {
+                name: organ.name.clone(),
+                parent: None,
+                metrics: stem.organ_metrics(id)?,
+            })
+        })
+        .collect();
+    Ok(Json(organs))
+}
+
+/// Получить клетки ткани
+pub async fn get_tissue_cells(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<Vec<String>>, ApiError> {
+    let stem = lock_arc_mutex(&state.stem)?;
+    let tissue = find_tissue(&stem, &name)?;
+    let cells = stem
+        .tissue_cells(tissue)
+        .into_iter()
+        .filter_map(|id| stem.cells().name(id))
+        .collect();
+    Ok(Json(cells))
+}
+
+/// Стимулировать ткань целиком
+pub async fn stimulate_tissue(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(req): Json<super::system::StimulateRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let mut stem = lock_arc_mutex(&state.stem)?;
+    let tissue = find_tissue(&stem, &name)?;
+    stem.stimulate_tissue(tissue, req.activity);
+
+    Ok(Json(json!({
+        "status": "ok",
+        "tissue": name,
+        "metrics": stem.tissue_metrics(tissue),
+        "generation": stem.generation
+    })))
+}
@@ -22,6 +22,7 @@ pub async fn websocket_handler(
 /// Задача WebSocket - отправка сигналов клиенту
 async fn websocket_task(mut socket: WebSocket, state: AppState) {
     let mut rx = state.signal_tx.subscribe();
+    let mut events = state.event_tx.subscribe();
 
     // Отправляем приветственное сообщение
     let welcome = json!({
@@ -60,6 +61,22 @@ async fn websocket_task(mut socket: WebSocket, state: AppState) {
                 }
             }
 
+            // Событие морфогенеза от стволового процессора
+            Ok(event) = events.recv() => {
+                let msg = json!({
+                    "type": "stem_event",
+                    "data": event
+                });
+
+                if socket
+                    .send(Message::Text(serde_json::to_string(&msg).unwrap_or_default()))
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+
             // Периодическое обновление состояния
             _ = interval.tick() => {
                 let state_msg = {
@@ -16,7 +16,7 @@ pub use responses::{SuccessResponse, DataResponse, ListResponse, MetaResponse};
 // Типы состояния и данных
 use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
-use soma_core::StemProcessor;
+use soma_core::{StemEvent, StemProcessor};
 use soma_conscious::ConsciousState;
 
 /// Состояние приложения, разделяемое между обработчиками
@@ -24,6 +24,7 @@ use soma_conscious::ConsciousState;
 pub struct AppState {
     pub stem: Arc<Mutex<StemProcessor>>,
     pub signal_tx: broadcast::Sender<ApiSignal>,
+    pub event_tx: broadcast::Sender<StemEvent>,
     pub mesh: Arc<mesh::MeshNode>,
     pub conscious: Arc<Mutex<ConsciousState>>,
 }
@@ -53,6 +54,8 @@ pub struct StateResponse {
     pub generation: u32,
     pub load: f64,
     pub threshold: f64,
+    /// Запас энергии организма (None - бюджет не задан)
+    pub energy: Option<f64>,
 }
 
 /// Информация о клетке для API
@@ -71,5 +74,31 @@ pub struct DistributionResponse {
     pub sensor: usize,
     pub logic: usize,
     pub motor: usize,
+    pub memory: usize,
+    pub hub: usize,
+    pub inhibitor: usize,
+    pub explorer: usize,
     pub total: usize,
 }
+
+/// Ткань или орган со сводными метриками
+#[derive(serde::Serialize)]
+pub struct GroupResponse {
+    pub name: String,
+    /// Орган, в который входит ткань
+    pub parent: Option<String>,
+    pub metrics: soma_core::GroupMetrics,
+}
+
+/// Родословная клетки
+#[derive(serde::Serialize)]
+pub struct CellLineageResponse {
+    pub id: String,
+    pub role: String,
+    pub alive: bool,
+    pub ancestors: Vec<String>,
+    pub descendants: Vec<String>,
+    pub subtree_alive: usize,
+    pub subtree_dead: usize,
+    pub subtree_depth: usize,
+}
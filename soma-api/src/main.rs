@@ -6,26 +6,29 @@ use axum::{
     routing::{get, post},
     Router,
 };
-use std::{env, net::SocketAddr};
+use std::{env, net::SocketAddr, path::Path};
 use tokio::sync::broadcast;
 use tower_http::cors::CorsLayer;
 
 use soma_api::{
     AppState, ApiSignal, config,
     handlers::{
-        system, cells, mesh, domino, conscious, websocket,
+        system, cells, tissues, mesh, domino, conscious, websocket,
     },
     background,
 };
-use soma_core::StemProcessor;
+use soma_core::{system_clock, EnergyBudget, LifecyclePolicy, StemEvent, StemProcessor};
 use soma_conscious::ConsciousState;
 use std::sync::{Arc, Mutex};
 
 #[tokio::main]
 async fn main() {
+    // Часы узла - общий источник времени для mesh и осознанности
+    let clock = system_clock();
+
     // Получить ID узла из переменной окружения или сгенерировать
     let node_id = env::var("NODE_ID").unwrap_or_else(|_| {
-        format!("node_{}", chrono::Utc::now().timestamp_millis() % 10000)
+        format!("node_{}", clock.now_millis() % 10000)
     });
 
     // Получить порт из переменной окружения или использовать дефолтный
@@ -34,15 +37,28 @@ async fn main() {
         .and_then(|p| p.parse().ok())
         .unwrap_or(config::api::DEFAULT_PORT);
 
-    // Инициализация состояния
-    let stem = Arc::new(Mutex::new(StemProcessor::new()));
+    // Путь к снапшоту организма
+    let snapshot_path = env::var("SNAPSHOT_PATH")
+        .unwrap_or_else(|_| config::api::DEFAULT_SNAPSHOT_PATH.to_string());
+
+    // Инициализация состояния (восстановление из снапшота, если он есть)
+    let mut organism = load_organism(&snapshot_path);
     let (signal_tx, _) = broadcast::channel::<ApiSignal>(config::api::SIGNAL_CHANNEL_SIZE);
-    let mesh = Arc::new(soma_api::mesh::MeshNode::new(&node_id));
-    let conscious = Arc::new(Mutex::new(ConsciousState::new()));
+
+    // События морфогенеза транслируются в broadcast канал
+    let (event_tx, _) = broadcast::channel::<StemEvent>(config::api::EVENT_CHANNEL_SIZE);
+    let stem_events = event_tx.clone();
+    organism.subscribe(move |event| {
+        let _ = stem_events.send(event.clone());
+    });
+    let stem = Arc::new(Mutex::new(organism));
+    let mesh = Arc::new(soma_api::mesh::MeshNode::with_clock(&node_id, clock.clone()));
+    let conscious = Arc::new(Mutex::new(ConsciousState::with_clock(clock)));
 
     let state = AppState {
         stem: stem.clone(),
         signal_tx: signal_tx.clone(),
+        event_tx: event_tx.clone(),
         mesh: mesh.clone(),
         conscious: conscious.clone(),
     };
@@ -54,10 +70,18 @@ async fn main() {
         .route("/state", get(system::get_state))
         .route("/signal", post(system::post_signal))
         .route("/stimulate", post(system::stimulate))
+        .route("/stimulate/vector", post(system::stimulate_vector))
         
         // Cell endpoints
         .route("/cells", get(cells::get_cells))
         .route("/distribution", get(cells::get_distribution))
+        .route("/lineage", get(cells::get_lineage))
+        .route("/lineage/dot", get(cells::get_lineage_dot))
+        .route("/lineage/:id", get(cells::get_cell_lineage))
+        .route("/tissues", get(tissues::get_tissues))
+        .route("/tissues/:name/cells", get(tissues::get_tissue_cells))
+        .route("/tissues/:name/stimulate", post(tissues::stimulate_tissue))
+        .route("/organs", get(tissues::get_organs))
         
         // Mesh endpoints
         .route("/mesh", get(websocket::mesh_handler))
@@ -91,7 +115,7 @@ async fn main() {
         .with_state(state);
 
     // Запуск фоновых процессов
-    start_background_tasks(stem, signal_tx, mesh.clone(), conscious);
+    start_background_tasks(stem, signal_tx, event_tx, mesh.clone(), conscious, snapshot_path);
 
     // Запуск сервера
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
@@ -110,12 +134,17 @@ async fn main() {
 fn start_background_tasks(
     stem: Arc<Mutex<StemProcessor>>,
     signal_tx: broadcast::Sender<ApiSignal>,
+    event_tx: broadcast::Sender<StemEvent>,
     mesh: Arc<soma_api::mesh::MeshNode>,
     conscious: Arc<Mutex<ConsciousState>>,
+    snapshot_path: String,
 ) {
     // Фоновое обновление системы
     tokio::spawn(background::background_update(stem.clone(), signal_tx));
 
+    // Периодическое сохранение снапшота организма
+    tokio::spawn(background::snapshot_persist(stem.clone(), snapshot_path));
+
     // Mesh фоновые процессы
     let mesh_heartbeat = mesh.clone();
     tokio::spawn(async move {
@@ -136,10 +165,45 @@ fn start_background_tasks(
     tokio::spawn(background::mesh_state_sync(stem.clone(), mesh.clone()));
     tokio::spawn(background::mesh_resonance_sync(stem.clone(), mesh.clone()));
 
+    // Причинные цепи из событий морфогенеза
+    tokio::spawn(background::morphogenesis_traces(conscious.clone(), event_tx.subscribe()));
+
     // Conscious Cycle
     tokio::spawn(background::conscious_cycle(conscious, mesh, stem));
 }
 
+/// Восстановить организм из снапшота или создать новый
+///
+/// Организм узла всегда получает политику жизненного цикла и
+/// энергетический бюджет, ограничивающий рост популяции
+fn load_organism(snapshot_path: &str) -> StemProcessor {
+    let mut stem = if !Path::new(snapshot_path).exists() {
+        StemProcessor::new()
+    } else {
+        match StemProcessor::load_snapshot(snapshot_path) {
+            Ok(stem) => {
+                println!(
+                    "♻️  Restored organism {} from {} (generation {}, {} cells)",
+                    stem.id, snapshot_path, stem.generation, stem.cell_count()
+                );
+                stem
+            }
+            Err(e) => {
+                println!("⚠️  Failed to restore snapshot {}: {}", snapshot_path, e);
+                StemProcessor::new()
+            }
+        }
+    };
+
+    if stem.lifecycle().is_none() {
+        stem.set_lifecycle(Some(LifecyclePolicy::default()));
+    }
+    if stem.energy().is_none() {
+        stem.set_energy(Some(EnergyBudget::default()));
+    }
+    stem
+}
+
 /// Вывод информации о запуске сервера
 fn print_startup_info(node_id: &str, addr: &SocketAddr) {
     println!("\n╔═══════════════════════════════════════╗");
@@ -152,6 +216,13 @@ fn print_startup_info(node_id: &str, addr: &SocketAddr) {
     println!("  GET  /state         - System state");
     println!("  GET  /cells         - List all cells");
     println!("  GET  /distribution  - Role distribution");
+    println!("  GET  /lineage       - Cell lineage (JSON)");
+    println!("  GET  /lineage/dot   - Cell lineage (GraphViz DOT)");
+    println!("  GET  /lineage/:id   - Ancestors and descendants of a cell");
+    println!("  GET  /tissues       - Tissues with aggregate metrics");
+    println!("  GET  /tissues/:name/cells - Cells of a tissue");
+    println!("  POST /tissues/:name/stimulate - Stimulate a whole tissue");
+    println!("  GET  /organs        - Organs with aggregate metrics");
     println!("  GET  /peers         - Connected peers (with health)");
     println!("  POST /peers/register - Register peer for auto-reconnect");
     println!("  GET  /resonance     - Network resonance stats");
@@ -172,6 +243,7 @@ fn print_startup_info(node_id: &str, addr: &SocketAddr) {
     println!("  GET  /conscious/health - Consciousness metrics");
     println!("  POST /signal        - Send signal");
     println!("  POST /stimulate     - Stimulate system");
+    println!("  POST /stimulate/vector - Stimulate with input/compute/output load");
     println!("  GET  /ws            - WebSocket stream");
     println!("  GET  /mesh          - Mesh peer connection");
     println!("\nPress Ctrl+C to stop.\n");
@@ -1,12 +1,11 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
 use axum::extract::ws::{WebSocket, Message};
 use serde::{Serialize, Deserialize};
 use tokio::sync::mpsc;
 use tokio::time::{interval, Duration};
-use chrono::Utc;
 use futures::{StreamExt, SinkExt};
+use soma_core::{system_clock, SharedClock};
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message as TungsteniteMessage};
 
 mod config;
@@ -15,36 +14,43 @@ use config::{
 };
 
 /// Здоровье соединения с peer
+///
+/// Время успехов и сбоев берётся от часов узла и хранится в миллисекундах
+/// (Unix timestamp). Раньше `last_success` и `last_failure` имели тип
+/// `Instant` - код, читавший их как `Instant`, нужно перевести на миллисекунды.
 #[derive(Debug, Clone)]
 pub struct ConnectionHealth {
     pub failures: u32,
     pub successes: u32,
-    pub last_success: Instant,
-    pub last_failure: Option<Instant>,
+    pub last_success: i64,         // Время последнего успеха (ms)
+    pub last_failure: Option<i64>, // Время последнего сбоя (ms)
     pub quality: f64, // 0.0-1.0
+
+    clock: SharedClock,            // Источник времени (last_success, last_failure)
 }
 
 impl ConnectionHealth {
-    fn new() -> Self {
+    fn new(clock: SharedClock) -> Self {
         Self {
             failures: 0,
             successes: 0,
-            last_success: Instant::now(),
+            last_success: clock.now_millis() as i64,
             last_failure: None,
             quality: 1.0,
+            clock,
         }
     }
 
     fn record_success(&mut self) {
         self.successes += 1;
-        self.last_success = Instant::now();
+        self.last_success = self.clock.now_millis() as i64;
         // Плавное восстановление quality
         self.quality = (self.quality + health::QUALITY_RECOVERY_STEP).min(1.0);
     }
 
     fn record_failure(&mut self) {
         self.failures += 1;
-        self.last_failure = Some(Instant::now());
+        self.last_failure = Some(self.clock.now_millis() as i64);
         // Быстрая деградация quality
         self.quality = (self.quality - health::QUALITY_DEGRADATION_STEP).max(0.0);
     }
@@ -121,17 +127,19 @@ pub struct PeerInfo {
     pub decay: f64,           // Скорость забывания (сек^-1)
     pub last_fire_local: i64,  // Время последней вспышки локального узла (ms)
     pub last_fire_remote: i64, // Время последней вспышки удаленного peer (ms)
+
+    clock: SharedClock,        // Источник времени (last_seen, is_alive)
 }
 
 impl PeerInfo {
-    fn new(id: String) -> Self {
+    fn new(id: String, clock: SharedClock) -> Self {
         Self {
             id,
-            last_seen: Utc::now().timestamp_millis(),
+            last_seen: clock.now_millis() as i64,
             cells: 0,
             generation: 0,
             load: 0.0,
-            health: ConnectionHealth::new(),
+            health: ConnectionHealth::new(clock.clone()),
             url: None,
             connected: true,
             // Hebbian defaults
@@ -143,17 +151,18 @@ impl PeerInfo {
             decay: hebbian::DECAY_RATE,
             last_fire_local: 0,
             last_fire_remote: 0,
+            clock,
         }
     }
 
-    fn with_url(id: String, url: String) -> Self {
+    fn with_url(id: String, url: String, clock: SharedClock) -> Self {
         Self {
             id,
-            last_seen: Utc::now().timestamp_millis(),
+            last_seen: clock.now_millis() as i64,
             cells: 0,
             generation: 0,
             load: 0.0,
-            health: ConnectionHealth::new(),
+            health: ConnectionHealth::new(clock.clone()),
             url: Some(url),
             connected: false,
             // Hebbian defaults
@@ -165,11 +174,12 @@ impl PeerInfo {
             decay: hebbian::DECAY_RATE,
             last_fire_local: 0,
             last_fire_remote: 0,
+            clock,
         }
     }
 
     fn update_heartbeat(&mut self) {
-        self.last_seen = Utc::now().timestamp_millis();
+        self.last_seen = self.clock.now_millis() as i64;
         self.health.record_success();
     }
 
@@ -177,7 +187,7 @@ impl PeerInfo {
         self.cells = cells;
         self.generation = generation;
         self.load = load;
-        self.last_seen = Utc::now().timestamp_millis();
+        self.last_seen = self.clock.now_millis() as i64;
         self.health.record_success();
     }
 
@@ -186,7 +196,7 @@ impl PeerInfo {
     }
 
     pub fn is_alive(&self, timeout_ms: i64) -> bool {
-        let now = Utc::now().timestamp_millis();
+        let now = self.clock.now_millis() as i64;
         (now - self.last_seen) < timeout_ms
     }
 
@@ -247,35 +257,48 @@ pub struct MeshNode {
     pub id: String,
     pub peers: Arc<Mutex<HashMap<String, PeerInfo>>>,
     pub message_tx: Arc<Mutex<Option<mpsc::UnboundedSender<MeshMessage>>>>,
+    pub clock: SharedClock,
 }
 
 impl MeshNode {
     pub fn new(id: &str) -> Self {
+        Self::with_clock(id, system_clock())
+    }
+
+    /// Создать узел с заданным источником времени (heartbeat, окна Хебба, таймауты)
+    pub fn with_clock(id: &str, clock: SharedClock) -> Self {
         Self {
             id: id.to_string(),
             peers: Arc::new(Mutex::new(HashMap::new())),
             message_tx: Arc::new(Mutex::new(None)),
+            clock,
         }
     }
 
+    /// Текущее время по часам узла (ms)
+    fn now_millis(&self) -> i64 {
+        self.clock.now_millis() as i64
+    }
+
     /// Обработать входящее сообщение от peer
     fn handle_mesh_message(
         msg: &MeshMessage,
         node_id: &str,
         peers: &Arc<Mutex<HashMap<String, PeerInfo>>>,
         msg_tx: &mpsc::UnboundedSender<MeshMessage>,
+        clock: &SharedClock,
     ) {
         match msg {
             MeshMessage::Handshake { node_id: peer_id, .. } => {
                 let mut peers_map = peers.lock().unwrap();
-                peers_map.insert(peer_id.clone(), PeerInfo::new(peer_id.clone()));
+                peers_map.insert(peer_id.clone(), PeerInfo::new(peer_id.clone(), clock.clone()));
                 println!("🤝 Handshake from peer: {}", peer_id);
 
                 // Отправляем Ack
                 let ack = MeshMessage::Ack {
                     node_id: node_id.to_string(),
                     ack_to: peer_id.clone(),
-                    timestamp: Utc::now().timestamp_millis(),
+                    timestamp: clock.now_millis() as i64,
                 };
                 msg_tx.send(ack).ok();
             }
@@ -311,6 +334,7 @@ impl MeshNode {
     pub async fn handle_peer_connection(&self, socket: WebSocket) {
         let node_id = self.id.clone();
         let peers = self.peers.clone();
+        let clock = self.clock.clone();
 
         let (mut ws_sender, mut ws_receiver) = socket.split();
         let (msg_tx, mut msg_rx) = mpsc::unbounded_channel::<MeshMessage>();
@@ -324,7 +348,7 @@ impl MeshNode {
         // Отправляем handshake при подключении
         let handshake = MeshMessage::Handshake {
             node_id: node_id.clone(),
-            timestamp: Utc::now().timestamp_millis(),
+            timestamp: self.now_millis(),
         };
 
         if let Ok(json) = serde_json::to_string(&handshake) {
@@ -353,7 +377,7 @@ impl MeshNode {
             while let Some(Ok(msg)) = ws_receiver.next().await {
                 if let Message::Text(txt) = msg {
                     if let Ok(parsed) = serde_json::from_str::<MeshMessage>(&txt) {
-                        Self::handle_mesh_message(&parsed, &node_id, &peers, &msg_tx);
+                        Self::handle_mesh_message(&parsed, &node_id, &peers, &msg_tx, &clock);
                     }
                 }
             }
@@ -380,7 +404,7 @@ impl MeshNode {
     pub fn broadcast_heartbeat(&self) {
         let msg = MeshMessage::Heartbeat {
             node_id: self.id.clone(),
-            timestamp: Utc::now().timestamp_millis(),
+            timestamp: self.now_millis(),
         };
         self.send_message(msg);
     }
@@ -391,7 +415,7 @@ impl MeshNode {
             cells,
             generation,
             load,
-            timestamp: Utc::now().timestamp_millis(),
+            timestamp: self.now_millis(),
         };
         self.send_message(msg);
     }
@@ -541,7 +565,7 @@ impl MeshNode {
         loop {
             tick.tick().await;
             let mut peers = self.peers.lock().unwrap();
-            let now = Utc::now().timestamp_millis();
+            let now = self.now_millis();
 
             // Отмечаем мертвые peers как disconnected, но сохраняем их для переподключения
             for (id, peer) in peers.iter_mut() {
@@ -558,7 +582,7 @@ impl MeshNode {
     /// Зарегистрировать peer URL для автоматического переподключения
     pub fn register_peer(&self, peer_id: String, url: String) {
         let mut peers = self.peers.lock().unwrap();
-        peers.insert(peer_id.clone(), PeerInfo::with_url(peer_id, url));
+        peers.insert(peer_id.clone(), PeerInfo::with_url(peer_id, url, self.clock.clone()));
     }
 
     /// Попытаться подключиться к peer как клиент
@@ -587,7 +611,7 @@ impl MeshNode {
                 // Отправляем handshake
                 let handshake = MeshMessage::Handshake {
                     node_id: node_id.clone(),
-                    timestamp: Utc::now().timestamp_millis(),
+                    timestamp: self.now_millis(),
                 };
 
                 if let Ok(json) = serde_json::to_string(&handshake) {
@@ -655,7 +679,7 @@ impl MeshNode {
 
     /// Отправить Fire событие всем peers
     pub fn send_fire(&self) {
-        let now = Utc::now().timestamp_millis();
+        let now = self.now_millis();
         let msg = MeshMessage::Fire {
             node_id: self.id.clone(),
             timestamp: now,
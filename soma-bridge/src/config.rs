@@ -0,0 +1,13 @@
+//! Конфигурационные константы для SOMA Bridge
+
+/// Параметры сигнализации между клетками
+pub mod signalling {
+    /// Доля среднего сигнала соседей, доставляемая клетке
+    pub const DEFAULT_COUPLING: f64 = 0.5;
+
+    /// Ёмкость буфера канала между клетками
+    pub const DEFAULT_LINK_CAPACITY: usize = 8;
+
+    /// Размер ячейки пространственного индекса клеток
+    pub const SPATIAL_BUCKET_SIZE: f64 = 1.0;
+}
@@ -10,6 +10,7 @@
 //! - **MessageType**: Типы передаваемых сообщений
 //! - **Signal**: Легковесная структура для передачи значений
 //! - **Link**: Канал связи между нейронами/узлами
+//! - **CellSignalling**: Сигнализация между клетками организма через Link
 //!
 //! ## Поддерживаемые транспорты
 //!
@@ -38,12 +39,15 @@
 //! }
 //! ```
 
+pub mod config;
 pub mod link;
 pub mod signal;
+pub mod signalling;
 pub mod transport;
 
 pub use link::Link;
 pub use signal::Signal;
+pub use signalling::{CellSignalling, Synapse, WiringRule};
 pub use transport::{
     LocalTransport, Message, MessageType, Transport, TransportError,
 };
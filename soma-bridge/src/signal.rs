@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
+use soma_core::{Clock, SystemClock};
 
 /// Сигнал - базовая единица передачи данных в SOMA
 ///
@@ -17,11 +17,12 @@ pub struct Signal {
 impl Signal {
     /// Создать новый сигнал
     pub fn new(id: &str, value: f64) -> Self {
-        Self {
-            id: id.to_string(),
-            value,
-            timestamp: current_timestamp_millis(),
-        }
+        Self::now(id, value, &SystemClock)
+    }
+
+    /// Создать сигнал с временной меткой от заданных часов
+    pub fn now(id: &str, value: f64, clock: &dyn Clock) -> Self {
+        Self::with_timestamp(id, value, clock.now_millis() as i64)
     }
 
     /// Создать сигнал с заданной временной меткой
@@ -35,28 +36,29 @@ impl Signal {
 
     /// Проверить, старше ли сигнал заданного времени (в миллисекундах)
     pub fn is_older_than(&self, millis: i64) -> bool {
-        let now = current_timestamp_millis();
-        now - self.timestamp > millis
+        self.is_older_than_on(millis, &SystemClock)
+    }
+
+    /// Проверить возраст сигнала по заданным часам
+    pub fn is_older_than_on(&self, millis: i64, clock: &dyn Clock) -> bool {
+        self.age_millis_on(clock) > millis
     }
 
     /// Получить возраст сигнала в миллисекундах
     pub fn age_millis(&self) -> i64 {
-        let now = current_timestamp_millis();
-        now - self.timestamp
+        self.age_millis_on(&SystemClock)
     }
-}
 
-/// Получить текущую временную метку в миллисекундах
-fn current_timestamp_millis() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as i64
+    /// Получить возраст сигнала по заданным часам
+    pub fn age_millis_on(&self, clock: &dyn Clock) -> i64 {
+        clock.now_millis() as i64 - self.timestamp
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use soma_core::ManualClock;
 
     #[test]
     fn test_signal_creation() {
@@ -69,11 +71,23 @@ mod tests {
 
     #[test]
     fn test_signal_age() {
-        let old_timestamp = current_timestamp_millis() - 1000;
+        let old_timestamp = SystemClock.now_millis() as i64 - 1000;
         let signal = Signal::with_timestamp("test", 0.5, old_timestamp);
 
         assert!(signal.is_older_than(500));
         assert!(!signal.is_older_than(2000));
         assert!(signal.age_millis() >= 1000);
     }
+
+    #[test]
+    fn test_signal_age_on_manual_clock() {
+        let clock = ManualClock::new(10_000);
+        let signal = Signal::now("test", 0.5, &clock);
+        assert_eq!(signal.timestamp, 10_000);
+
+        clock.advance(750);
+        assert_eq!(signal.age_millis_on(&clock), 750);
+        assert!(signal.is_older_than_on(500, &clock));
+        assert!(!signal.is_older_than_on(750, &clock));
+    }
 }
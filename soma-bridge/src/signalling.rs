@@ -0,0 +1,488 @@
+//! Сигнализация между клетками стволового процессора
+//!
+//! Клетки соединяются каналами [`Link`]: выходной поток клетки уходит
+//! соседям как [`Signal`] и попадает в их следующий `sense`. Связи
+//! создаются автоматически при делении - по родству (родитель и потомок)
+//! или по близости клеток в пространстве.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::mpsc;
+
+use soma_core::{Position, StemEvent, StemProcessor};
+
+use crate::config::signalling;
+use crate::{Link, Signal};
+
+/// Правило автоматического соединения клеток при делении
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WiringRule {
+    /// Потомок соединяется с родителем
+    Lineage,
+    /// Клетка соединяется со всеми клетками в радиусе
+    Proximity { radius: f64 },
+}
+
+/// Направленная связь между двумя клетками
+#[derive(Clone)]
+pub struct Synapse {
+    /// Клетка-источник
+    pub from: String,
+    /// Клетка-получатель
+    pub to: String,
+    link: Link,
+}
+
+impl Synapse {
+    /// Канал связи
+    pub fn link(&self) -> &Link {
+        &self.link
+    }
+}
+
+/// Паракринная сеть клеток организма
+///
+/// Связи двунаправленные: соединение двух клеток создаёт по каналу в
+/// каждую сторону. Клетка получает средний сигнал соседей, умноженный
+/// на коэффициент связи.
+pub struct CellSignalling {
+    rules: Vec<WiringRule>,
+    coupling: f64,
+    capacity: usize,
+    synapses: Vec<Synapse>,
+    /// Индекс направленных связей: источник → получатели
+    adjacency: HashMap<String, BTreeSet<String>>,
+    /// Индекс связей клетки: позиции входящих и исходящих связей в `synapses`
+    incident: HashMap<String, HashSet<usize>>,
+    /// Пространственный индекс соединённых по правилам клеток
+    grid: HashMap<Bucket, Vec<(String, Position)>>,
+    /// Ячейка индекса каждой клетки
+    buckets: HashMap<String, Bucket>,
+    events: Option<mpsc::Receiver<StemEvent>>,
+}
+
+/// Ячейка пространственного индекса
+type Bucket = (i64, i64, i64);
+
+/// Ячейка, в которую попадает точка
+fn bucket_of(position: &Position) -> Bucket {
+    let size = signalling::SPATIAL_BUCKET_SIZE;
+    (
+        (position.x / size).floor() as i64,
+        (position.y / size).floor() as i64,
+        (position.z / size).floor() as i64,
+    )
+}
+
+impl CellSignalling {
+    /// Создать сеть без правил соединения
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            coupling: signalling::DEFAULT_COUPLING,
+            capacity: signalling::DEFAULT_LINK_CAPACITY,
+            synapses: Vec::new(),
+            adjacency: HashMap::new(),
+            incident: HashMap::new(),
+            grid: HashMap::new(),
+            buckets: HashMap::new(),
+            events: None,
+        }
+    }
+
+    /// Добавить правило соединения при делении
+    pub fn with_rule(mut self, rule: WiringRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Установить коэффициент связи (0.0 - 1.0)
+    pub fn with_coupling(mut self, coupling: f64) -> Self {
+        self.coupling = coupling.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Установить ёмкость буфера новых каналов (0 = без ограничений)
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Коэффициент связи
+    pub fn coupling(&self) -> f64 {
+        self.coupling
+    }
+
+    /// Подключиться к организму
+    ///
+    /// Существующие клетки соединяются по правилам, дальнейшие деления
+    /// и гибели отслеживаются через канал событий процессора. Родители
+    /// существующих клеток известны, только если организм ведёт родословную
+    pub fn attach(&mut self, stem: &mut StemProcessor) {
+        self.events = Some(stem.event_channel());
+
+        let mut cells: Vec<(u32, String)> = stem.cells().values().map(|c| (c.generation, c.id)).collect();
+        cells.sort();
+        for (_, id) in cells {
+            let parent = stem.lineage().get(&id).and_then(|node| node.parent.clone());
+            self.wire(stem, &id, parent.as_deref());
+        }
+    }
+
+    /// Соединить две клетки каналами в обе стороны
+    ///
+    /// Возвращает false, если клетки совпадают или уже соединены
+    pub fn connect(&mut self, a: &str, b: &str) -> bool {
+        if a == b || self.is_connected(a, b) {
+            return false;
+        }
+        for (from, to) in [(a, b), (b, a)] {
+            self.adjacency.entry(from.to_string()).or_default().insert(to.to_string());
+            let index = self.synapses.len();
+            self.incident.entry(from.to_string()).or_default().insert(index);
+            self.incident.entry(to.to_string()).or_default().insert(index);
+            self.synapses.push(Synapse {
+                from: from.to_string(),
+                to: to.to_string(),
+                link: Link::with_capacity(self.capacity),
+            });
+        }
+        true
+    }
+
+    /// Удалить все связи клетки
+    ///
+    /// Работает за время, пропорциональное числу связей клетки: на место
+    /// удалённой связи переносится последняя
+    pub fn disconnect(&mut self, id: &str) {
+        self.unplace(id);
+        if let Some(neighbours) = self.adjacency.remove(id) {
+            for neighbour in neighbours {
+                if let Some(targets) = self.adjacency.get_mut(&neighbour) {
+                    targets.remove(id);
+                }
+            }
+        }
+
+        let Some(indices) = self.incident.remove(id) else { return };
+        let mut indices: Vec<usize> = indices.into_iter().collect();
+        // С конца: связи клетки правее текущей уже удалены, последней
+        // оказывается чужая связь
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        for index in indices {
+            let removed = self.synapses.swap_remove(index);
+            let other = if removed.from == id { &removed.to } else { &removed.from };
+            if let Some(own) = self.incident.get_mut(other) {
+                own.remove(&index);
+            }
+
+            let last = self.synapses.len();
+            let Some(moved) = self.synapses.get(index) else { continue };
+            for end in [&moved.from, &moved.to] {
+                if let Some(own) = self.incident.get_mut(end) {
+                    own.remove(&last);
+                    own.insert(index);
+                }
+            }
+        }
+    }
+
+    /// Есть ли канал от одной клетки к другой
+    pub fn is_connected(&self, from: &str, to: &str) -> bool {
+        self.adjacency.get(from).is_some_and(|targets| targets.contains(to))
+    }
+
+    /// Соседи клетки (по возрастанию имени)
+    pub fn neighbours(&self, id: &str) -> Vec<&str> {
+        self.adjacency
+            .get(id)
+            .map(|targets| targets.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Все направленные связи
+    pub fn synapses(&self) -> &[Synapse] {
+        &self.synapses
+    }
+
+    /// Количество направленных связей
+    pub fn len(&self) -> usize {
+        self.synapses.len()
+    }
+
+    /// Проверить, нет ли связей
+    pub fn is_empty(&self) -> bool {
+        self.synapses.is_empty()
+    }
+
+    /// Соединить клетку по правилам
+    fn wire(&mut self, stem: &StemProcessor, id: &str, parent: Option<&str>) {
+        for rule in self.rules.clone() {
+            match rule {
+                WiringRule::Lineage => {
+                    if let Some(parent) = parent {
+                        self.connect(parent, id);
+                    }
+                }
+                WiringRule::Proximity { radius } => {
+                    let Some(position) = stem.cell_position(id) else { continue };
+                    self.place(id, position);
+                    for other in self.within(&position, radius) {
+                        self.connect(id, &other);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Добавить клетку в пространственный индекс
+    fn place(&mut self, id: &str, position: Position) {
+        if self.buckets.contains_key(id) {
+            return;
+        }
+        let bucket = bucket_of(&position);
+        self.buckets.insert(id.to_string(), bucket);
+        self.grid.entry(bucket).or_default().push((id.to_string(), position));
+    }
+
+    /// Убрать клетку из пространственного индекса
+    fn unplace(&mut self, id: &str) {
+        let Some(bucket) = self.buckets.remove(id) else { return };
+        if let Some(cells) = self.grid.get_mut(&bucket) {
+            cells.retain(|(other, _)| other != id);
+            if cells.is_empty() {
+                self.grid.remove(&bucket);
+            }
+        }
+    }
+
+    /// Клетки индекса в радиусе от точки
+    ///
+    /// Просматриваются только ячейки, задетые радиусом. Если таких ячеек
+    /// больше, чем занятых, перебираются занятые
+    fn within(&self, position: &Position, radius: f64) -> Vec<String> {
+        let reach = (radius / signalling::SPATIAL_BUCKET_SIZE).ceil() as i64;
+        let (x, y, z) = bucket_of(position);
+        let span = (2 * reach + 1) as usize;
+        let near = |bucket: &Bucket| {
+            (bucket.0 - x).abs() <= reach && (bucket.1 - y).abs() <= reach && (bucket.2 - z).abs() <= reach
+        };
+
+        let mut buckets: Vec<Bucket> = if span.saturating_pow(3) > self.grid.len() {
+            self.grid.keys().copied().filter(near).collect()
+        } else {
+            let offsets = -reach..=reach;
+            offsets
+                .clone()
+                .flat_map(|dx| offsets.clone().map(move |dy| (dx, dy)))
+                .flat_map(|(dx, dy)| offsets.clone().map(move |dz| (x + dx, y + dy, z + dz)))
+                .collect()
+        };
+        // Детерминированный порядок соединения
+        buckets.sort_unstable();
+
+        buckets
+            .iter()
+            .filter_map(|bucket| self.grid.get(bucket))
+            .flatten()
+            .filter(|(_, other)| other.distance(position) <= radius)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Обработать деления и гибели клеток с момента прошлого вызова
+    pub fn sync(&mut self, stem: &StemProcessor) {
+        let Some(events) = self.events.take() else { return };
+        for event in events.try_iter() {
+            match event {
+                StemEvent::CellDivided { id, parent, .. } => self.wire(stem, &id, parent.as_deref()),
+                StemEvent::CellRemoved { id, .. } => self.disconnect(&id),
+                StemEvent::Budded { cells, .. } => cells.iter().for_each(|id| self.disconnect(id)),
+                _ => {}
+            }
+        }
+        self.events = Some(events);
+    }
+
+    /// Обменяться сигналами перед тиком организма
+    ///
+    /// Каждая клетка отправляет свой выходной поток соседям, затем
+    /// получатели забирают сигналы из каналов и получают их средний
+    /// сигнал к следующему `sense`. Возвращает число клеток, получивших сигнал
+    pub fn exchange(&mut self, stem: &mut StemProcessor) -> usize {
+        self.sync(stem);
+
+        let clock = stem.clock().clone();
+        for synapse in &self.synapses {
+            if let Some(flow) = stem.cell_flow(&synapse.from) {
+                synapse.link.send(Signal::now(&synapse.from, flow, clock.as_ref()));
+            }
+        }
+
+        let mut received: BTreeMap<&str, (f64, usize)> = BTreeMap::new();
+        for synapse in &self.synapses {
+            for signal in synapse.link.drain() {
+                let entry = received.entry(synapse.to.as_str()).or_insert((0.0, 0));
+                entry.0 += signal.value;
+                entry.1 += 1;
+            }
+        }
+
+        let mut delivered = 0;
+        for (id, (total, count)) in received {
+            if stem.deliver_signal(id, total / count as f64 * self.coupling) {
+                delivered += 1;
+            }
+        }
+        delivered
+    }
+}
+
+impl Default for CellSignalling {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soma_core::{CellRole, FixedRatioSelector, MorphogenField};
+
+    #[test]
+    fn test_division_wires_child_to_parent() {
+        let mut stem = StemProcessor::with_params(0.3, 0.0).with_seed(7);
+        let mut network = CellSignalling::new().with_rule(WiringRule::Lineage);
+        network.attach(&mut stem);
+
+        stem.sense(0.9);
+        stem.sense(0.9);
+        network.sync(&stem);
+
+        let cells: Vec<_> = stem.lineage().nodes().cloned().collect();
+        assert_eq!(cells.len(), 2);
+        let child = cells.iter().find(|c| c.parent.is_some()).unwrap();
+        let parent = child.parent.as_deref().unwrap();
+        assert!(network.is_connected(parent, &child.id));
+        assert!(network.is_connected(&child.id, parent));
+        assert_eq!(network.len(), 2);
+
+        stem.remove_cell(parent);
+        network.sync(&stem);
+        assert!(network.is_empty());
+    }
+
+    #[test]
+    fn test_proximity_wiring_on_attach() {
+        let mut stem = StemProcessor::with_params(0.3, 0.0)
+            .with_seed(3)
+            .with_morphogen_field(MorphogenField::planar(8, 8, 1.0));
+        for _ in 0..4 {
+            stem.sense(0.9);
+        }
+
+        let mut network = CellSignalling::new().with_rule(WiringRule::Proximity { radius: 1.5 });
+        network.attach(&mut stem);
+
+        for synapse in network.synapses() {
+            let from = stem.cell_position(&synapse.from).unwrap();
+            let to = stem.cell_position(&synapse.to).unwrap();
+            assert!(from.distance(&to) <= 1.5);
+        }
+        assert!(!network.is_empty());
+    }
+
+    #[test]
+    fn test_proximity_index_matches_full_scan() {
+        let mut stem = StemProcessor::with_params(0.3, 0.0)
+            .with_seed(11)
+            .with_morphogen_field(MorphogenField::planar(6, 6, 1.0));
+        let mut network = CellSignalling::new().with_rule(WiringRule::Proximity { radius: 0.5 });
+        network.attach(&mut stem);
+        for _ in 0..12 {
+            stem.sense(0.9);
+        }
+        network.sync(&stem);
+
+        let ids: Vec<String> = stem.cells().keys().collect();
+        let removed = ids[0].clone();
+        stem.remove_cell(&removed);
+        network.sync(&stem);
+
+        // Индекс даёт те же связи, что и полный перебор пар
+        let mut pairs = 0;
+        for a in &ids[1..] {
+            for b in &ids[1..] {
+                let pa = stem.cell_position(a).unwrap();
+                let pb = stem.cell_position(b).unwrap();
+                let near = a != b && pa.distance(&pb) <= 0.5;
+                assert_eq!(network.is_connected(a, b), near);
+                pairs += near as usize;
+            }
+        }
+        assert!(pairs > 0);
+        assert_eq!(network.len(), pairs);
+        assert!(network.neighbours(&removed).is_empty());
+        for (index, synapse) in network.synapses().iter().enumerate() {
+            assert!(synapse.from != removed && synapse.to != removed);
+            assert!(network.incident[&synapse.from].contains(&index));
+            assert!(network.incident[&synapse.to].contains(&index));
+        }
+    }
+
+    #[test]
+    fn test_spatial_index_query() {
+        let mut network = CellSignalling::new();
+        let mut points = Vec::new();
+        for i in 0..10 {
+            for j in 0..10 {
+                let position = Position::planar(i as f64 * 0.7, j as f64 * 0.7);
+                network.place(&format!("c{}_{}", i, j), position);
+                points.push((format!("c{}_{}", i, j), position));
+            }
+        }
+
+        // Малый радиус перебирает ячейки вокруг точки, большой - занятые ячейки
+        let center = Position::planar(3.1, 2.9);
+        for radius in [0.9, 3.0] {
+            let mut found = network.within(&center, radius);
+            found.sort();
+            let mut expected: Vec<String> = points
+                .iter()
+                .filter(|(_, p)| p.distance(&center) <= radius)
+                .map(|(id, _)| id.clone())
+                .collect();
+            expected.sort();
+            assert!(!expected.is_empty());
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn test_exchange_delivers_neighbour_flow() {
+        let mut stem = StemProcessor::with_params(0.3, 0.0)
+            .with_seed(7)
+            .with_role_selector(FixedRatioSelector::new(&[(CellRole::Memory, 1.0)]));
+        let mut network = CellSignalling::new().with_coupling(0.5);
+
+        // Клетки без живых экземпляров: сигнал прибавляется к активности
+        stem.sense(0.9);
+        stem.sense(0.9);
+        let mut cells: Vec<(u32, String)> = stem.population().values().map(|c| (c.generation, c.id)).collect();
+        cells.sort();
+        let ids: Vec<String> = cells.into_iter().map(|(_, id)| id).collect();
+        assert_eq!(ids.len(), 2);
+        stem.deliver_signal(&ids[0], 0.8);
+        stem.tick();
+        let source = stem.cell_flow(&ids[0]).unwrap();
+        assert!(source > 0.0);
+
+        assert!(network.connect(&ids[0], &ids[1]));
+        assert!(!network.connect(&ids[1], &ids[0]));
+        assert_eq!(network.neighbours(&ids[1]), vec![ids[0].as_str()]);
+        assert_eq!(network.exchange(&mut stem), 2);
+        stem.tick();
+
+        let flow = stem.cell_flow(&ids[1]).unwrap();
+        assert!((flow - 0.5 * source * CellRole::Memory.activity_decay()).abs() < 1e-9);
+    }
+}
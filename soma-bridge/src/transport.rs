@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use soma_core::{Clock, SystemClock};
 use std::collections::HashMap;
 
 /// Сообщение, передаваемое через bridge
@@ -42,6 +43,17 @@ impl Message {
         source: String,
         destination: String,
         msg_type: MessageType,
+    ) -> Self {
+        Self::now(id, source, destination, msg_type, &SystemClock)
+    }
+
+    /// Создать сообщение с временной меткой от заданных часов
+    pub fn now(
+        id: String,
+        source: String,
+        destination: String,
+        msg_type: MessageType,
+        clock: &dyn Clock,
     ) -> Self {
         Self {
             id,
@@ -49,7 +61,7 @@ impl Message {
             destination,
             msg_type,
             payload: HashMap::new(),
-            timestamp: current_timestamp(),
+            timestamp: clock.now_millis(),
         }
     }
 
@@ -110,15 +122,6 @@ impl std::fmt::Display for TransportError {
 
 impl std::error::Error for TransportError {}
 
-/// Получить текущую временную метку в миллисекундах
-fn current_timestamp() -> u64 {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
-}
-
 /// Локальный транспорт для тестирования (в памяти)
 pub struct LocalTransport {
     messages: std::sync::Arc<tokio::sync::Mutex<Vec<Message>>>,
@@ -186,14 +189,17 @@ mod tests {
 
     #[test]
     fn test_message_payload() {
-        let msg = Message::new(
+        let clock = soma_core::ManualClock::new(42_000);
+        let msg = Message::now(
             "test-2".to_string(),
             "sender".to_string(),
             "receiver".to_string(),
             MessageType::Command,
+            &clock,
         )
         .with_payload("key".to_string(), serde_json::json!("value"));
 
+        assert_eq!(msg.timestamp, 42_000);
         assert_eq!(
             msg.get_payload("key"),
             Some(&serde_json::json!("value"))
@@ -4,6 +4,7 @@
 //! один генерирует гипотезу, второй проверяет, третий сводит результат.
 
 use serde::{Deserialize, Serialize};
+use soma_core::{Clock, SystemClock};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
@@ -118,10 +119,18 @@ impl BraidResult {
         result: String,
         participants: Vec<String>,
     ) -> Self {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        Self::success_at(task_id, confidence, result, participants, &SystemClock)
+    }
+
+    /// Создать успешный результат с временем от заданных часов
+    pub fn success_at(
+        task_id: String,
+        confidence: f64,
+        result: String,
+        participants: Vec<String>,
+        clock: &dyn Clock,
+    ) -> Self {
+        let now = clock.now_millis() / 1000;
 
         Self {
             task_id,
@@ -136,10 +145,12 @@ impl BraidResult {
 
     /// Создать неудачный результат
     pub fn failure(task_id: String, error: String, participants: Vec<String>) -> Self {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        Self::failure_at(task_id, error, participants, &SystemClock)
+    }
+
+    /// Создать неудачный результат с временем от заданных часов
+    pub fn failure_at(task_id: String, error: String, participants: Vec<String>, clock: &dyn Clock) -> Self {
+        let now = clock.now_millis() / 1000;
 
         Self {
             task_id,
@@ -323,6 +334,17 @@ mod tests {
         assert!(task.participants.contains(&"node_b".to_string()));
     }
 
+    #[test]
+    fn test_result_uses_clock() {
+        let clock = soma_core::ManualClock::new(7_000);
+        let ok = BraidResult::success_at("task_001".to_string(), 0.9, "ok".to_string(), vec![], &clock);
+        let failed = BraidResult::failure_at("task_002".to_string(), "err".to_string(), vec![], &clock);
+
+        assert_eq!((ok.started_at, ok.completed_at), (7, 7));
+        assert_eq!(failed.completed_at, 7);
+        assert_eq!(ok.duration_secs(), 0);
+    }
+
     #[tokio::test]
     async fn test_inference_braid() {
         let braid = InferenceBraid::new();
@@ -7,6 +7,7 @@
 //! v1.3: Voting-based consensus с Byzantine fault tolerance
 
 use serde::{Deserialize, Serialize};
+use soma_core::{Clock, SystemClock};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -44,15 +45,17 @@ pub struct NodeVote {
 impl NodeVote {
     /// Создать новый голос
     pub fn new(node_id: String, vote: Vote, confidence: f64) -> Self {
+        Self::at(node_id, vote, confidence, &SystemClock)
+    }
+
+    /// Создать голос с временной меткой от заданных часов
+    pub fn at(node_id: String, vote: Vote, confidence: f64, clock: &dyn Clock) -> Self {
         Self {
             node_id,
             vote,
             confidence,
             reasoning: None,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: clock.now_millis() / 1000,
         }
     }
 
@@ -4,6 +4,7 @@
 //! но и лога когнитивных событий.
 
 use serde::{Deserialize, Serialize};
+use soma_core::{Clock, SystemClock};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -74,14 +75,23 @@ impl CognitiveEvent {
         participants: Vec<String>,
         result: EventResult,
         confidence: f64,
+    ) -> Self {
+        Self::at(id, event_type, participants, result, confidence, &SystemClock)
+    }
+
+    /// Создать событие с временной меткой от заданных часов
+    pub fn at(
+        id: String,
+        event_type: EventType,
+        participants: Vec<String>,
+        result: EventResult,
+        confidence: f64,
+        clock: &dyn Clock,
     ) -> Self {
         Self {
             id,
             event_type,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: clock.now_millis() / 1000,
             task: None,
             participants,
             result,
@@ -7,6 +7,7 @@
 //! - self_reflection_latency_ms - время отклика на самоанализ
 
 use serde::{Deserialize, Serialize};
+use soma_core::{Clock, SystemClock};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -42,11 +43,13 @@ pub struct MetricSnapshot {
 impl MetricSnapshot {
     /// Создать новый снимок метрик
     pub fn new() -> Self {
+        Self::at(&SystemClock)
+    }
+
+    /// Создать снимок с временной меткой от заданных часов
+    pub fn at(clock: &dyn Clock) -> Self {
         Self {
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: clock.now_millis() / 1000,
             cognitive_overlap_avg: 0.0,
             clusters_active_total: 0,
             braid_success_rate: 0.0,
@@ -307,6 +310,9 @@ mod tests {
 
         assert_eq!(snapshot.cognitive_overlap_avg, 0.85);
         assert_eq!(snapshot.clusters_active_total, 5);
+
+        let clock = soma_core::ManualClock::new(42_500);
+        assert_eq!(MetricSnapshot::at(&clock).timestamp, 42);
     }
 
     #[tokio::test]
@@ -7,6 +7,7 @@
 
 use crate::embeddings::{cosine_similarity, IntentEmbeddings};
 use serde::{Deserialize, Serialize};
+use soma_core::{Clock, SystemClock};
 use std::collections::HashMap;
 use tokio::time::{interval, Duration};
 
@@ -106,16 +107,18 @@ pub struct CognitivePulse {
 impl CognitivePulse {
     /// Создать новый когнитивный пульс
     pub fn new(node_id: String, intent: Intent, confidence: f64) -> Self {
+        Self::at(node_id, intent, confidence, &SystemClock)
+    }
+
+    /// Создать пульс с временной меткой от заданных часов
+    pub fn at(node_id: String, intent: Intent, confidence: f64, clock: &dyn Clock) -> Self {
         let context = intent.context_tags();
         Self {
             node_id,
             intent,
             confidence,
             context,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: clock.now_millis() / 1000,
             metadata: HashMap::new(),
         }
     }
@@ -44,7 +44,7 @@ pub use decision_tracker::{
 
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
-use chrono::Utc;
+use soma_core::{system_clock, Clock, SharedClock, SystemClock};
 
 /// Причинно-следственная цепь (cause → effect)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,11 +64,16 @@ pub struct CausalTrace {
 
 impl CausalTrace {
     pub fn new(cause: String, effect: String, delta: f64) -> Self {
+        Self::at(cause, effect, delta, &SystemClock)
+    }
+
+    /// Создать trace с временной меткой от заданных часов
+    pub fn at(cause: String, effect: String, delta: f64, clock: &dyn Clock) -> Self {
         Self {
             cause,
             effect,
             delta,
-            timestamp: Utc::now().timestamp_millis(),
+            timestamp: clock.now_millis() as i64,
         }
     }
 }
@@ -91,11 +96,16 @@ pub struct Insight {
 
 impl Insight {
     pub fn new(insight: String, category: String, importance: f64) -> Self {
+        Self::at(insight, category, importance, &SystemClock)
+    }
+
+    /// Создать инсайт с временной меткой от заданных часов
+    pub fn at(insight: String, category: String, importance: f64, clock: &dyn Clock) -> Self {
         Self {
             insight,
             category,
             importance,
-            timestamp: Utc::now().timestamp_millis(),
+            timestamp: clock.now_millis() as i64,
         }
     }
 }
@@ -128,15 +138,25 @@ pub struct AttentionMap {
 
 impl AttentionMap {
     pub fn new() -> Self {
+        Self::with_clock(&SystemClock)
+    }
+
+    /// Создать пустую карту с временем обновления от заданных часов
+    pub fn with_clock(clock: &dyn Clock) -> Self {
         Self {
             top_nodes: Vec::new(),
-            updated_at: Utc::now().timestamp_millis(),
+            updated_at: clock.now_millis() as i64,
         }
     }
 
     pub fn update(&mut self, nodes: Vec<AttentionNode>) {
+        self.update_at(nodes, &SystemClock);
+    }
+
+    /// Заменить узлы карты, время обновления берётся от заданных часов
+    pub fn update_at(&mut self, nodes: Vec<AttentionNode>, clock: &dyn Clock) {
         self.top_nodes = nodes;
-        self.updated_at = Utc::now().timestamp_millis();
+        self.updated_at = clock.now_millis() as i64;
     }
 }
 
@@ -172,23 +192,37 @@ pub struct ConsciousState {
 
     /// Время последнего цикла
     pub last_cycle: i64,
+
+    /// Источник времени (окна traces, время циклов)
+    clock: SharedClock,
 }
 
 impl ConsciousState {
     /// Создать новое состояние осознанности
     pub fn new() -> Self {
+        Self::with_clock(system_clock())
+    }
+
+    /// Создать состояние осознанности с заданным источником времени
+    pub fn with_clock(clock: SharedClock) -> Self {
         Self {
             traces: VecDeque::new(),
             max_traces: 1000,
             insights: VecDeque::new(),
             max_insights: 100,
-            attention_map: AttentionMap::new(),
+            attention_map: AttentionMap::with_clock(clock.as_ref()),
             decision_history: DecisionHistory::new(500), // 500 последних решений
             cycle_count: 0,
-            last_cycle: Utc::now().timestamp_millis(),
+            last_cycle: clock.now_millis() as i64,
+            clock,
         }
     }
 
+    /// Получить источник времени
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     /// Записать причинную цепь
     pub fn record_trace(&mut self, trace: CausalTrace) {
         if self.traces.len() >= self.max_traces {
@@ -209,7 +243,7 @@ impl ConsciousState {
 
     /// Получить traces за окно времени (в миллисекундах)
     pub fn get_traces_window(&self, window_ms: i64) -> Vec<CausalTrace> {
-        let now = Utc::now().timestamp_millis();
+        let now = self.clock.now_millis() as i64;
         let cutoff = now - window_ms;
 
         self.traces
@@ -250,7 +284,7 @@ impl ConsciousState {
     /// Завершить цикл осознанности
     pub fn complete_cycle(&mut self) {
         self.cycle_count += 1;
-        self.last_cycle = Utc::now().timestamp_millis();
+        self.last_cycle = self.clock.now_millis() as i64;
     }
 
     /// Получить количество traces
@@ -347,6 +381,25 @@ mod tests {
         assert_eq!(traces[0].cause, "test_cause");
     }
 
+    #[test]
+    fn test_traces_window_uses_clock() {
+        use soma_core::ManualClock;
+
+        let clock = ManualClock::new(100_000);
+        let mut state = ConsciousState::with_clock(clock.shared());
+
+        state.record_trace(CausalTrace::at("old".to_string(), "e".to_string(), 0.1, &clock));
+        clock.advance(60_000);
+        state.record_trace(CausalTrace::at("new".to_string(), "e".to_string(), 0.1, &clock));
+
+        let window = state.get_traces_window(30_000);
+        assert_eq!(window.len(), 1);
+        assert_eq!(window[0].cause, "new");
+
+        state.complete_cycle();
+        assert_eq!(state.last_cycle, 160_000);
+    }
+
     #[test]
     fn test_insights() {
         let mut state = ConsciousState::new();
@@ -367,7 +420,11 @@ mod tests {
 
     #[test]
     fn test_attention_map() {
-        let mut state = ConsciousState::new();
+        use soma_core::ManualClock;
+
+        let clock = ManualClock::new(1_000);
+        let mut state = ConsciousState::with_clock(clock.shared());
+        assert_eq!(state.get_attention_map().updated_at, 1_000);
 
         let node = AttentionNode {
             node_id: "node_alpha".to_string(),
@@ -376,14 +433,16 @@ mod tests {
             avg_delta: 0.05,
         };
 
-        let mut map = AttentionMap::new();
-        map.update(vec![node]);
+        clock.advance(500);
+        let mut map = AttentionMap::with_clock(&clock);
+        map.update_at(vec![node], state.clock().as_ref());
 
         state.update_attention_map(map);
 
         let attention = state.get_attention_map();
         assert_eq!(attention.top_nodes.len(), 1);
         assert_eq!(attention.top_nodes[0].node_id, "node_alpha");
+        assert_eq!(attention.updated_at, 1_500);
     }
 
     #[test]
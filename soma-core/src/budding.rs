@@ -0,0 +1,106 @@
+//! Почкование - отделение дочерних стволовых процессоров
+//!
+//! Когда процессор разрастается сверх заданного числа клеток или
+//! нагрузки, он отпочковывает дочерний процессор: часть клеток (младшие)
+//! переходит к почке вместе с параметрами родителя. Родитель хранит
+//! почки и учитывает их нагрузку в своём `sense`, почки в свою очередь
+//! могут почковаться дальше - организм растёт фрактально.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::budding;
+
+/// Политика почкования стволового процессора
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuddingPolicy {
+    /// Число клеток, сверх которого процессор почкуется
+    pub max_cells: usize,
+    /// Нагрузка, сверх которой процессор почкуется (None - не учитывается)
+    pub max_load: Option<f64>,
+    /// Доля клеток, переходящих к почке (0.0 - 1.0)
+    pub share: f64,
+    /// Вес средней нагрузки почек в нагрузке родителя (0.0 - 1.0)
+    pub child_weight: f64,
+    /// Максимальное число почек у одного процессора
+    pub max_children: usize,
+}
+
+impl BuddingPolicy {
+    /// Почковаться при превышении числа клеток
+    pub fn new(max_cells: usize) -> Self {
+        Self {
+            max_cells: max_cells.max(1),
+            max_load: None,
+            share: budding::DEFAULT_SHARE,
+            child_weight: budding::DEFAULT_CHILD_WEIGHT,
+            max_children: budding::DEFAULT_MAX_CHILDREN,
+        }
+    }
+
+    /// Почковаться также при превышении нагрузки
+    pub fn with_max_load(mut self, max_load: f64) -> Self {
+        self.max_load = Some(max_load.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Установить долю клеток, переходящих к почке
+    pub fn with_share(mut self, share: f64) -> Self {
+        self.share = share.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Установить вес нагрузки почек в нагрузке родителя
+    pub fn with_child_weight(mut self, weight: f64) -> Self {
+        self.child_weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Установить максимальное число почек
+    pub fn with_max_children(mut self, max_children: usize) -> Self {
+        self.max_children = max_children;
+        self
+    }
+
+    /// Пора ли почковаться
+    pub fn should_bud(&self, cells: usize, load: f64, children: usize) -> bool {
+        if children >= self.max_children || self.bud_size(cells) == 0 {
+            return false;
+        }
+        cells > self.max_cells || self.max_load.is_some_and(|max| load > max)
+    }
+
+    /// Сколько клеток переходит к почке (родитель сохраняет хотя бы одну)
+    pub fn bud_size(&self, cells: usize) -> usize {
+        ((cells as f64 * self.share).round() as usize).min(cells.saturating_sub(1))
+    }
+}
+
+impl Default for BuddingPolicy {
+    fn default() -> Self {
+        Self::new(budding::DEFAULT_MAX_CELLS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_bud_on_cells_or_load() {
+        let policy = BuddingPolicy::new(4).with_max_load(0.8).with_max_children(1);
+
+        assert!(!policy.should_bud(4, 0.5, 0));
+        assert!(policy.should_bud(5, 0.5, 0));
+        assert!(policy.should_bud(2, 0.9, 0));
+        assert!(!policy.should_bud(5, 0.5, 1));
+        assert!(!policy.should_bud(1, 0.9, 0));
+    }
+
+    #[test]
+    fn test_bud_size_keeps_a_cell() {
+        let policy = BuddingPolicy::new(1).with_share(1.0);
+        assert_eq!(policy.bud_size(6), 5);
+        assert_eq!(BuddingPolicy::new(1).bud_size(7), 4);
+        assert_eq!(BuddingPolicy::new(1).with_share(0.0).bud_size(7), 0);
+    }
+}
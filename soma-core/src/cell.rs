@@ -15,6 +15,20 @@ pub trait Cell {
     fn flow(&self) -> f64;
 }
 
+impl<C: Cell + ?Sized> Cell for Box<C> {
+    fn sense(&mut self, input: f64) {
+        (**self).sense(input);
+    }
+
+    fn align(&mut self) {
+        (**self).align();
+    }
+
+    fn flow(&self) -> f64 {
+        (**self).flow()
+    }
+}
+
 /// Стволовая клетка - универсальная недифференцированная клетка
 ///
 /// Может трансформироваться в любой специализированный тип клетки,
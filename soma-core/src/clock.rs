@@ -0,0 +1,159 @@
+//! Часы SOMA - инъектируемый источник времени
+//!
+//! Все зависящие от времени компоненты (возраст клеток и сигналов,
+//! затухание нейронов, окна Хебба, фаза Domino) читают время через
+//! трейт [`Clock`]. В продакшене используется [`SystemClock`], в тестах
+//! и симуляциях - [`ManualClock`] или [`SimulatedClock`].
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Источник времени
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Текущее время в миллисекундах (Unix timestamp)
+    fn now_millis(&self) -> u64;
+
+    /// Текущее время в секундах (Unix timestamp)
+    fn now_secs_f64(&self) -> f64 {
+        self.now_millis() as f64 / 1000.0
+    }
+}
+
+/// Разделяемые часы, которые передаются компонентам SOMA
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Получить системные часы в разделяемом виде
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// Системные часы - реальное время
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+}
+
+/// Ручные часы - время меняется только явными вызовами
+///
+/// Клоны разделяют одно и то же время, поэтому часы можно передать
+/// компоненту и продолжать управлять ими из теста.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    millis: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// Создать часы, показывающие заданное время
+    pub fn new(start_millis: u64) -> Self {
+        Self {
+            millis: Arc::new(AtomicU64::new(start_millis)),
+        }
+    }
+
+    /// Установить текущее время
+    pub fn set(&self, millis: u64) {
+        self.millis.store(millis, Ordering::SeqCst);
+    }
+
+    /// Продвинуть время на заданное количество миллисекунд
+    pub fn advance(&self, millis: u64) {
+        self.millis.fetch_add(millis, Ordering::SeqCst);
+    }
+
+    /// Продвинуть время на заданную длительность
+    pub fn advance_by(&self, duration: Duration) {
+        self.advance(duration.as_millis() as u64);
+    }
+
+    /// Получить часы в разделяемом виде
+    pub fn shared(&self) -> SharedClock {
+        Arc::new(self.clone())
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_millis(&self) -> u64 {
+        self.millis.load(Ordering::SeqCst)
+    }
+}
+
+/// Симулированные часы - время течёт в `speed` раз быстрее реального
+///
+/// Позволяют прогнать длинные сценарии (например, суточную фазу Domino)
+/// за секунды реального времени.
+#[derive(Debug, Clone)]
+pub struct SimulatedClock {
+    /// Время симуляции в момент запуска
+    origin_millis: u64,
+    /// Реальный момент запуска
+    started: Instant,
+    /// Множитель скорости течения времени
+    speed: f64,
+}
+
+impl SimulatedClock {
+    /// Создать часы, стартующие с заданного времени и идущие с заданной скоростью
+    pub fn new(origin_millis: u64, speed: f64) -> Self {
+        Self {
+            origin_millis,
+            started: Instant::now(),
+            speed: speed.max(0.0),
+        }
+    }
+
+    /// Множитель скорости течения времени
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Получить часы в разделяемом виде
+    pub fn shared(&self) -> SharedClock {
+        Arc::new(self.clone())
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now_millis(&self) -> u64 {
+        let elapsed = self.started.elapsed().as_secs_f64() * 1000.0 * self.speed;
+        self.origin_millis + elapsed as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock_is_after_epoch() {
+        assert!(SystemClock.now_millis() > 1_600_000_000_000);
+    }
+
+    #[test]
+    fn test_manual_clock_shared_between_clones() {
+        let clock = ManualClock::new(1_000);
+        let shared = clock.shared();
+
+        clock.advance(500);
+        assert_eq!(shared.now_millis(), 1_500);
+
+        clock.set(10);
+        assert_eq!(shared.now_millis(), 10);
+        assert_eq!(shared.now_secs_f64(), 0.01);
+    }
+
+    #[test]
+    fn test_simulated_clock_runs_faster() {
+        let clock = SimulatedClock::new(0, 1_000_000.0);
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(clock.now_millis() >= 2_000);
+    }
+}
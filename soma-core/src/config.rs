@@ -18,3 +18,122 @@ pub mod cell {
     pub const ACTIVITY_DECAY: f64 = 0.95;
 }
 
+/// Параметры поведения специализированных ролей
+pub mod role {
+    /// Затухание активности клеток памяти (удерживают активность дольше)
+    pub const MEMORY_ACTIVITY_DECAY: f64 = 0.99;
+
+    /// Коэффициент усиления активности соседей хабами
+    pub const HUB_GAIN: f64 = 0.5;
+
+    /// Коэффициент подавления активности соседей ингибиторами
+    pub const INHIBITOR_STRENGTH: f64 = 0.5;
+
+    /// Амплитуда шума, вносимого исследователями
+    pub const EXPLORER_NOISE: f64 = 0.1;
+}
+
+/// Параметры жизненного цикла клеток (апоптоз и регенерация)
+pub mod lifecycle {
+    /// Максимальный возраст клетки по умолчанию (10 минут)
+    pub const DEFAULT_MAX_AGE_MS: u64 = 600_000;
+
+    /// Порог активности, ниже которого клетка считается бездействующей
+    pub const DEFAULT_MIN_ACTIVITY: f64 = 0.01;
+
+    /// Допустимое число тиков бездействия подряд
+    pub const DEFAULT_MAX_IDLE_TICKS: u32 = 100;
+
+    /// Предельная токсичность клетки
+    pub const DEFAULT_MAX_TOXICITY: f64 = 1.0;
+
+    /// Коэффициент выведения токсичности за тик
+    pub const TOXICITY_DECAY: f64 = 0.99;
+
+    /// Ёмкость пула стволовых клеток по умолчанию
+    pub const DEFAULT_STEM_POOL_CAPACITY: usize = 16;
+
+    /// Восполнение пула стволовых клеток за тик
+    pub const DEFAULT_STEM_POOL_REFILL: usize = 1;
+}
+
+/// Параметры родословной клеток
+pub mod lineage {
+    /// Максимальное число хранимых умерших клеток по умолчанию
+    pub const DEFAULT_MAX_DEAD: usize = 10_000;
+}
+
+/// Параметры хранения популяции клеток
+pub mod population {
+    /// Минимальный размер популяции для параллельного тика
+    pub const PARALLEL_MIN_CELLS: usize = 16_384;
+}
+
+/// Параметры событий стволового процессора
+pub mod events {
+    /// Изменение нагрузки, после которого публикуется событие
+    pub const DEFAULT_LOAD_CHANGE_DELTA: f64 = 0.1;
+}
+
+/// Параметры энергетического бюджета
+pub mod energy {
+    /// Ёмкость запаса энергии по умолчанию
+    pub const DEFAULT_CAPACITY: f64 = 10.0;
+
+    /// Восполнение запаса за тик по умолчанию
+    pub const DEFAULT_REPLENISH_RATE: f64 = 1.0;
+
+    /// Стоимость деления по умолчанию
+    pub const DEFAULT_DIVISION_COST: f64 = 1.0;
+
+    /// Базовая стоимость содержания клетки за тик
+    pub const BASE_METABOLIC_COST: f64 = 0.05;
+
+    /// Стоимость содержания клетки памяти (удерживает активность)
+    pub const MEMORY_METABOLIC_COST: f64 = 0.08;
+
+    /// Стоимость содержания хаба (поддерживает много связей)
+    pub const HUB_METABOLIC_COST: f64 = 0.1;
+}
+
+/// Параметры поля морфогенов
+pub mod morphogen {
+    /// Доля концентрации, уходящая к соседям за тик
+    pub const DEFAULT_DIFFUSION: f64 = 0.2;
+
+    /// Доля концентрации, распадающаяся за тик
+    pub const DEFAULT_DECAY: f64 = 0.05;
+
+    /// Выделение морфогена одной клеткой за тик
+    pub const DEFAULT_EMISSION: f64 = 1.0;
+
+    /// Максимальное смещение дочерней клетки от родителя по каждой оси
+    pub const CHILD_OFFSET: f64 = 1.0;
+}
+
+/// Параметры почкования стволовых процессоров
+pub mod budding {
+    /// Число клеток, сверх которого процессор почкуется
+    pub const DEFAULT_MAX_CELLS: usize = 64;
+
+    /// Доля клеток, переходящих к почке
+    pub const DEFAULT_SHARE: f64 = 0.5;
+
+    /// Вес средней нагрузки почек в нагрузке родителя
+    pub const DEFAULT_CHILD_WEIGHT: f64 = 0.5;
+
+    /// Максимальное число почек у одного процессора
+    pub const DEFAULT_MAX_CHILDREN: usize = 4;
+}
+
+/// Параметры трансдифференциации клеток
+pub mod transdifferentiation {
+    /// Длина истории входов клетки (тиков)
+    pub const DEFAULT_WINDOW: usize = 16;
+
+    /// Доля истории, указывающая на другую роль, для смены роли
+    pub const DEFAULT_MISMATCH_RATIO: f64 = 0.8;
+
+    /// Максимум смен роли за тик
+    pub const DEFAULT_MAX_PER_TICK: usize = 1;
+}
@@ -0,0 +1,137 @@
+//! Энергетический бюджет организма - метаболизм и цена деления
+//!
+//! Каждая клетка потребляет энергию каждый тик в зависимости от роли,
+//! деление требует отдельной платы. Резерв восполняется за тик до
+//! ёмкости. Когда резерва не хватает, деление отклоняется, а при
+//! дефиците на содержание клетки погибают от голода - рост популяции
+//! ограничен самой моделью организма.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::energy;
+use crate::stem::CellRole;
+
+/// Энергетический бюджет стволового процессора
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnergyBudget {
+    /// Максимальный запас энергии
+    pub capacity: f64,
+    /// Восполнение запаса за тик
+    pub replenish_rate: f64,
+    /// Стоимость одного деления
+    pub division_cost: f64,
+    /// Переопределённая стоимость содержания клеток по ролям
+    role_costs: HashMap<CellRole, f64>,
+    /// Текущий запас энергии
+    reserve: f64,
+}
+
+impl EnergyBudget {
+    /// Создать бюджет с полным запасом
+    pub fn new(capacity: f64, replenish_rate: f64) -> Self {
+        let capacity = capacity.max(0.0);
+        Self {
+            capacity,
+            replenish_rate: replenish_rate.max(0.0),
+            division_cost: energy::DEFAULT_DIVISION_COST,
+            role_costs: HashMap::new(),
+            reserve: capacity,
+        }
+    }
+
+    /// Установить стоимость деления
+    pub fn with_division_cost(mut self, cost: f64) -> Self {
+        self.division_cost = cost.max(0.0);
+        self
+    }
+
+    /// Установить стоимость содержания клетки роли за тик
+    pub fn with_role_cost(mut self, role: CellRole, cost: f64) -> Self {
+        self.role_costs.insert(role, cost.max(0.0));
+        self
+    }
+
+    /// Установить текущий запас энергии
+    pub fn with_reserve(mut self, reserve: f64) -> Self {
+        self.reserve = reserve.clamp(0.0, self.capacity);
+        self
+    }
+
+    /// Стоимость содержания клетки роли за тик
+    pub fn role_cost(&self, role: CellRole) -> f64 {
+        self.role_costs
+            .get(&role)
+            .copied()
+            .unwrap_or_else(|| role.metabolic_cost())
+    }
+
+    /// Текущий запас энергии
+    pub fn reserve(&self) -> f64 {
+        self.reserve
+    }
+
+    /// Достаточно ли энергии
+    pub fn can_afford(&self, amount: f64) -> bool {
+        self.reserve >= amount
+    }
+
+    /// Потратить энергию
+    ///
+    /// Возвращает false (и ничего не тратит), если запаса недостаточно
+    pub fn spend(&mut self, amount: f64) -> bool {
+        if !self.can_afford(amount) {
+            return false;
+        }
+        self.reserve -= amount;
+        true
+    }
+
+    /// Восполнить запас за тик
+    pub fn replenish(&mut self) {
+        self.reserve = (self.reserve + self.replenish_rate).min(self.capacity);
+    }
+
+    /// Стоимость содержания популяции за тик
+    pub fn upkeep(&self, distribution: &HashMap<CellRole, usize>) -> f64 {
+        distribution
+            .iter()
+            .map(|(role, count)| self.role_cost(*role) * *count as f64)
+            .sum()
+    }
+}
+
+impl Default for EnergyBudget {
+    fn default() -> Self {
+        Self::new(energy::DEFAULT_CAPACITY, energy::DEFAULT_REPLENISH_RATE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spend_and_replenish() {
+        let mut budget = EnergyBudget::new(2.0, 0.5).with_reserve(1.0);
+
+        assert!(budget.spend(0.8));
+        assert!(!budget.spend(0.8));
+        assert!((budget.reserve() - 0.2).abs() < 1e-9);
+
+        for _ in 0..10 {
+            budget.replenish();
+        }
+        assert_eq!(budget.reserve(), 2.0);
+    }
+
+    #[test]
+    fn test_upkeep_uses_role_costs() {
+        let budget = EnergyBudget::default().with_role_cost(CellRole::Logic, 0.5);
+        let distribution = HashMap::from([(CellRole::Logic, 2), (CellRole::Hub, 1)]);
+
+        let expected = 1.0 + CellRole::Hub.metabolic_cost();
+        assert!((budget.upkeep(&distribution) - expected).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,192 @@
+//! События стволового процессора - наблюдение за морфогенезом
+//!
+//! StemProcessor публикует события о пересечении порога, делении и
+//! гибели клеток и существенном изменении нагрузки. Подписчики получают
+//! их синхронно через callback или через канал `std::sync::mpsc`.
+
+use std::fmt;
+use std::sync::mpsc;
+
+use serde::Serialize;
+
+use crate::lifecycle::ApoptosisCause;
+use crate::load::LoadDimension;
+use crate::stem::CellRole;
+
+/// Событие жизненного цикла организма
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StemEvent {
+    /// Сглаженная нагрузка пересекла порог деления
+    ThresholdCrossed {
+        load: f64,
+        threshold: f64,
+        /// true - нагрузка поднялась выше порога, false - опустилась ниже
+        rising: bool,
+    },
+    /// Нагрузка измерения пересекла свой порог (векторная модель)
+    DimensionThresholdCrossed {
+        dimension: LoadDimension,
+        load: f64,
+        threshold: f64,
+        /// true - нагрузка поднялась выше порога, false - опустилась ниже
+        rising: bool,
+    },
+    /// Родилась новая клетка
+    CellDivided {
+        id: String,
+        role: CellRole,
+        /// Клетка-родитель (None - клетка порождена стволовым процессором)
+        parent: Option<String>,
+        generation: u32,
+    },
+    /// Деление отклонено - недостаточно энергии
+    DivisionRefused {
+        role: CellRole,
+        /// Запас энергии на момент отказа
+        reserve: f64,
+        /// Стоимость деления
+        cost: f64,
+    },
+    /// Клетка удалена
+    CellRemoved {
+        id: String,
+        role: CellRole,
+        /// Причина апоптоза (None - удалена вручную)
+        cause: Option<ApoptosisCause>,
+    },
+    /// Клетка сменила роль по истории своих входов
+    Transdifferentiated {
+        id: String,
+        from: CellRole,
+        to: CellRole,
+    },
+    /// Процессор отпочковал дочерний процессор
+    Budded {
+        /// Идентификатор почки
+        child: String,
+        /// Клетки, перешедшие к почке
+        cells: Vec<String>,
+    },
+    /// Нагрузка существенно изменилась с момента последнего события
+    LoadChanged { previous: f64, load: f64 },
+}
+
+/// Идентификатор подписки на события
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// Обработчик событий
+pub type EventCallback = Box<dyn FnMut(&StemEvent) + Send>;
+
+/// Шина событий стволового процессора
+#[derive(Default)]
+pub struct EventBus {
+    callbacks: Vec<(SubscriptionId, EventCallback)>,
+    channels: Vec<mpsc::Sender<StemEvent>>,
+    next_id: u64,
+}
+
+impl EventBus {
+    /// Создать шину без подписчиков
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Подписаться на события через callback
+    pub fn subscribe<F>(&mut self, callback: F) -> SubscriptionId
+    where
+        F: FnMut(&StemEvent) + Send + 'static,
+    {
+        let id = SubscriptionId(self.next_id);
+        self.next_id += 1;
+        self.callbacks.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Отменить подписку
+    ///
+    /// Возвращает false, если подписка не найдена
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let before = self.callbacks.len();
+        self.callbacks.retain(|(sub, _)| *sub != id);
+        self.callbacks.len() != before
+    }
+
+    /// Получить канал событий
+    ///
+    /// Канал отписывается автоматически, когда получатель уничтожен
+    pub fn channel(&mut self) -> mpsc::Receiver<StemEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.channels.push(tx);
+        rx
+    }
+
+    /// Есть ли подписчики (события без подписчиков не создаются)
+    pub fn has_subscribers(&self) -> bool {
+        !self.callbacks.is_empty() || !self.channels.is_empty()
+    }
+
+    /// Опубликовать событие
+    pub fn emit(&mut self, event: StemEvent) {
+        for (_, callback) in &mut self.callbacks {
+            callback(&event);
+        }
+        self.channels.retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBus")
+            .field("callbacks", &self.callbacks.len())
+            .field("channels", &self.channels.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_callbacks_and_unsubscribe() {
+        let mut bus = EventBus::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let id = bus.subscribe(move |e| sink.lock().unwrap().push(e.clone()));
+
+        let event = StemEvent::LoadChanged { previous: 0.1, load: 0.5 };
+        bus.emit(event.clone());
+        assert!(bus.unsubscribe(id));
+        bus.emit(event.clone());
+
+        assert_eq!(*seen.lock().unwrap(), vec![event]);
+        assert!(!bus.has_subscribers());
+    }
+
+    #[test]
+    fn test_channel_drops_closed_receivers() {
+        let mut bus = EventBus::new();
+        let rx = bus.channel();
+        let dropped = bus.channel();
+        drop(dropped);
+
+        bus.emit(StemEvent::LoadChanged { previous: 0.0, load: 1.0 });
+        assert_eq!(rx.try_iter().count(), 1);
+        assert_eq!(bus.channels.len(), 1);
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = StemEvent::CellRemoved {
+            id: "cell_1_0".to_string(),
+            role: CellRole::Logic,
+            cause: Some(ApoptosisCause::Age),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "cell_removed");
+        assert_eq!(json["cause"], "Age");
+    }
+}
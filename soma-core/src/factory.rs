@@ -0,0 +1,109 @@
+//! Реестр фабрик клеток - дифференциация по ролям
+//!
+//! При делении StemProcessor создаёт не только [`CellInfo`], но и живой
+//! экземпляр [`Cell`], если для роли новой клетки зарегистрирована фабрика.
+//! Так стволовая клетка может дифференцироваться, например, в нейрон
+//! для роли `Logic`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::cell::Cell;
+use crate::stem::{CellInfo, CellRole};
+
+/// Живая клетка, принадлежащая стволовому процессору
+pub type LiveCell = Box<dyn Cell + Send>;
+
+/// Фабрика живых клеток для заданной роли
+///
+/// Фабрики разделяются между процессором и его почками
+pub type CellFactory = Arc<dyn Fn(&CellInfo) -> LiveCell + Send + Sync>;
+
+/// Реестр фабрик клеток по ролям
+#[derive(Clone, Default)]
+pub struct CellRegistry {
+    factories: HashMap<CellRole, CellFactory>,
+}
+
+impl CellRegistry {
+    /// Создать пустой реестр
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Зарегистрировать фабрику для роли (заменяет существующую)
+    pub fn register<F>(&mut self, role: CellRole, factory: F)
+    where
+        F: Fn(&CellInfo) -> LiveCell + Send + Sync + 'static,
+    {
+        self.factories.insert(role, Arc::new(factory));
+    }
+
+    /// Зарегистрировать фабрику для роли (builder)
+    pub fn with<F>(mut self, role: CellRole, factory: F) -> Self
+    where
+        F: Fn(&CellInfo) -> LiveCell + Send + Sync + 'static,
+    {
+        self.register(role, factory);
+        self
+    }
+
+    /// Есть ли фабрика для роли
+    pub fn has(&self, role: CellRole) -> bool {
+        self.factories.contains_key(&role)
+    }
+
+    /// Создать живую клетку для описания клетки
+    ///
+    /// Возвращает None, если для роли нет фабрики
+    pub fn create(&self, info: &CellInfo) -> Option<LiveCell> {
+        self.factories.get(&info.role).map(|factory| factory(info))
+    }
+
+    /// Проверить, пустой ли реестр
+    pub fn is_empty(&self) -> bool {
+        self.factories.is_empty()
+    }
+}
+
+impl fmt::Debug for CellRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let roles: Vec<_> = CellRole::ALL.iter().filter(|r| self.has(**r)).collect();
+        f.debug_struct("CellRegistry").field("roles", &roles).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::StemCell;
+
+    struct Echo(f64);
+
+    impl Cell for Echo {
+        fn sense(&mut self, input: f64) {
+            self.0 = input;
+        }
+
+        fn align(&mut self) {}
+
+        fn flow(&self) -> f64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_registry_creates_cells_by_role() {
+        let registry = CellRegistry::new()
+            .with(CellRole::Logic, |_| StemCell::new().differentiate(Box::new(Echo(0.0)) as LiveCell));
+
+        let logic = CellInfo::born_at("l".to_string(), CellRole::Logic, 1, 0);
+        let motor = CellInfo::born_at("m".to_string(), CellRole::Motor, 1, 0);
+
+        let cell = registry.create(&logic).unwrap();
+        assert_eq!(cell.flow(), 0.5);
+        assert!(registry.create(&motor).is_none());
+        assert!(registry.has(CellRole::Logic));
+    }
+}
@@ -7,15 +7,66 @@
 //!
 //! - **Cell**: Базовая единица обработки (Sense-Align-Flow)
 //! - **StemCell**: Универсальная клетка для дифференциации
+//! - **PortCell**: Клетка с именованными типизированными портами
+//! - **WiringGraph**: Граф связей между портами клеток
 //! - **StemProcessor**: Стволовой процессор для порождения новых клеток
+//! - **BuddingPolicy**: Почкование дочерних процессоров
+//! - **CellPopulation**: Хранение клеток структурой массивов
+//! - **CellRegistry**: Фабрики живых клеток по ролям
+//! - **LoadModel**: Многомерная нагрузка (вход, вычисления, выход)
+//! - **StemEvent**: События деления, гибели клеток и изменения нагрузки
+//! - **EnergyBudget**: Метаболизм клеток и цена деления
+//! - **LifecyclePolicy**: Апоптоз и регенерация клеток
+//! - **TransdifferentiationPolicy**: Смена роли клетки по истории входов
+//! - **Lineage**: Родословная клеток
+//! - **Anatomy**: Ткани и органы - иерархия клеток
+//! - **MorphogenField**: Пространство клеток и морфогенные сигналы
+//! - **RoleSelector**: Стратегии выбора роли при делении
+//! - **Clock**: Инъектируемый источник времени
+//! - **OrganismSnapshot**: Версионированный снапшот организма
 //! - **Resonance**: Механизм синхронизации и передачи состояния
 
 pub mod cell;
+pub mod clock;
 pub mod stem;
+pub mod budding;
 pub mod config;
+pub mod energy;
+pub mod events;
+pub mod factory;
+pub mod lifecycle;
+pub mod lineage;
+pub mod load;
+pub mod morphogen;
+pub mod population;
+pub mod port;
+pub mod selector;
+pub mod snapshot;
+pub mod tissue;
+pub mod transdifferentiation;
+pub mod wiring;
 
 pub use cell::{Cell, StemCell};
+pub use clock::{system_clock, Clock, ManualClock, SharedClock, SimulatedClock, SystemClock};
 pub use stem::{CellInfo, CellRole, StemProcessor};
+pub use factory::{CellFactory, CellRegistry, LiveCell};
+pub use budding::BuddingPolicy;
+pub use energy::EnergyBudget;
+pub use events::{EventBus, EventCallback, StemEvent, SubscriptionId};
+pub use lifecycle::{ApoptosisCause, LifecyclePolicy, LifecycleStats};
+pub use lineage::{Lineage, LineageNode, SubtreeStats};
+pub use load::{DimensionParams, LoadDimension, LoadModel, LoadVector};
+pub use morphogen::{MorphogenField, Position};
+pub use population::{CellId, CellPopulation};
+pub use port::{PortCell, PortSpec, PortType, PortValue, ScalarCellAdapter};
+pub use selector::{
+    BalancedSelector, FixedRatioSelector, MorphogenSelector, PressureGradientSelector,
+    RoleSelector, SelectionContext, WeightedRandomSelector,
+};
+pub use snapshot::{OrganismSnapshot, SnapshotError, SnapshotMigrator, SNAPSHOT_SCHEMA_VERSION};
+pub use tissue::{Anatomy, GroupMetrics, Organ, OrganId, Tissue, TissueId};
+pub use transdifferentiation::{InputSource, SignalHistory, TransdifferentiationPolicy};
+pub use wiring::{NodeId, Wire, WiredCell, WiringError, WiringGraph};
 
 /// Версия протокола SOMA
 pub const SOMA_VERSION: &str = "0.1.0";
@@ -0,0 +1,232 @@
+//! Жизненный цикл клеток - апоптоз и регенерация
+//!
+//! Политика жизненного цикла определяет, когда клетка должна умереть
+//! (возраст, длительное бездействие, токсичность), и как популяция
+//! восстанавливается из пула стволовых клеток до гомеостатической нормы.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::lifecycle;
+use crate::stem::CellInfo;
+
+/// Причина запрограммированной гибели клетки
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApoptosisCause {
+    /// Клетка превысила максимальный возраст
+    Age,
+    /// Клетка слишком долго оставалась неактивной
+    Inactivity,
+    /// Клетка накопила слишком много токсичности
+    Toxicity,
+    /// Организму не хватило энергии на содержание клетки
+    Starvation,
+    /// Клетка ушла в почку и живёт дальше в дочернем организме
+    Budded,
+}
+
+impl ApoptosisCause {
+    /// Получить описание причины
+    pub fn description(&self) -> &str {
+        match self {
+            ApoptosisCause::Age => "Age - cell exceeded its lifespan",
+            ApoptosisCause::Inactivity => "Inactivity - cell stayed idle for too long",
+            ApoptosisCause::Toxicity => "Toxicity - cell produced too many bad outputs",
+            ApoptosisCause::Starvation => "Starvation - organism could not afford its upkeep",
+            ApoptosisCause::Budded => "Budded - cell moved to a child organism",
+        }
+    }
+}
+
+/// Политика жизненного цикла клеток
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LifecyclePolicy {
+    /// Максимальный возраст клетки в миллисекундах (None - бессмертие по возрасту)
+    pub max_age_ms: Option<u64>,
+    /// Порог активности, ниже которого клетка считается бездействующей
+    pub min_activity: f64,
+    /// Допустимое число тиков бездействия подряд (0 - не проверять)
+    ///
+    /// Учитываются только клетки с живым экземпляром: активность клеток
+    /// без фабрики никто не поддерживает
+    pub max_idle_ticks: u32,
+    /// Предельная токсичность, после которой клетка уничтожается
+    pub max_toxicity: f64,
+    /// Гомеостатическая численность популяции
+    ///
+    /// None - каждая удалённая клетка замещается один к одному
+    pub target_population: Option<usize>,
+    /// Ёмкость пула стволовых клеток для регенерации
+    pub stem_pool_capacity: usize,
+    /// Восполнение пула стволовых клеток за тик
+    pub stem_pool_refill: usize,
+}
+
+impl LifecyclePolicy {
+    /// Создать политику с параметрами по умолчанию
+    pub fn new() -> Self {
+        Self {
+            max_age_ms: Some(lifecycle::DEFAULT_MAX_AGE_MS),
+            min_activity: lifecycle::DEFAULT_MIN_ACTIVITY,
+            max_idle_ticks: lifecycle::DEFAULT_MAX_IDLE_TICKS,
+            max_toxicity: lifecycle::DEFAULT_MAX_TOXICITY,
+            target_population: None,
+            stem_pool_capacity: lifecycle::DEFAULT_STEM_POOL_CAPACITY,
+            stem_pool_refill: lifecycle::DEFAULT_STEM_POOL_REFILL,
+        }
+    }
+
+    /// Установить максимальный возраст клетки
+    pub fn with_max_age(mut self, max_age_ms: Option<u64>) -> Self {
+        self.max_age_ms = max_age_ms;
+        self
+    }
+
+    /// Установить порог бездействия
+    pub fn with_inactivity(mut self, min_activity: f64, max_idle_ticks: u32) -> Self {
+        self.min_activity = min_activity.max(0.0);
+        self.max_idle_ticks = max_idle_ticks;
+        self
+    }
+
+    /// Установить предельную токсичность
+    pub fn with_max_toxicity(mut self, max_toxicity: f64) -> Self {
+        self.max_toxicity = max_toxicity.max(0.0);
+        self
+    }
+
+    /// Установить гомеостатическую численность популяции
+    pub fn with_target_population(mut self, target: usize) -> Self {
+        self.target_population = Some(target);
+        self
+    }
+
+    /// Настроить пул стволовых клеток
+    pub fn with_stem_pool(mut self, capacity: usize, refill: usize) -> Self {
+        self.stem_pool_capacity = capacity;
+        self.stem_pool_refill = refill;
+        self
+    }
+
+    /// Определить, должна ли клетка погибнуть, и по какой причине
+    ///
+    /// Токсичность проверяется первой, затем возраст, затем бездействие
+    pub fn apoptosis_cause(&self, cell: &CellInfo, now_millis: u64) -> Option<ApoptosisCause> {
+        self.apoptosis_cause_for(cell.toxicity, cell.birth_time, cell.idle_ticks, now_millis)
+    }
+
+    /// Определить причину гибели по отдельным атрибутам клетки
+    pub fn apoptosis_cause_for(
+        &self,
+        toxicity: f64,
+        birth_time: u64,
+        idle_ticks: u32,
+        now_millis: u64,
+    ) -> Option<ApoptosisCause> {
+        if toxicity >= self.max_toxicity {
+            return Some(ApoptosisCause::Toxicity);
+        }
+
+        if let Some(max_age) = self.max_age_ms {
+            if now_millis.saturating_sub(birth_time) >= max_age {
+                return Some(ApoptosisCause::Age);
+            }
+        }
+
+        if self.max_idle_ticks > 0 && idle_ticks >= self.max_idle_ticks {
+            return Some(ApoptosisCause::Inactivity);
+        }
+
+        None
+    }
+}
+
+impl Default for LifecyclePolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Накопленная статистика жизненного цикла
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LifecycleStats {
+    /// Клетки, умершие от старости
+    pub retired_by_age: usize,
+    /// Клетки, умершие от бездействия
+    pub retired_by_inactivity: usize,
+    /// Клетки, умершие от токсичности
+    pub retired_by_toxicity: usize,
+    /// Клетки, умершие от голода
+    #[serde(default)]
+    pub retired_by_starvation: usize,
+    /// Клетки, регенерированные из пула стволовых клеток
+    pub regenerated: usize,
+}
+
+impl LifecycleStats {
+    /// Зарегистрировать гибель клетки
+    pub fn record(&mut self, cause: ApoptosisCause) {
+        match cause {
+            ApoptosisCause::Age => self.retired_by_age += 1,
+            ApoptosisCause::Inactivity => self.retired_by_inactivity += 1,
+            ApoptosisCause::Toxicity => self.retired_by_toxicity += 1,
+            ApoptosisCause::Starvation => self.retired_by_starvation += 1,
+            // Переход в почку - не гибель
+            ApoptosisCause::Budded => {}
+        }
+    }
+
+    /// Общее количество погибших клеток
+    pub fn total_retired(&self) -> usize {
+        self.retired_by_age
+            + self.retired_by_inactivity
+            + self.retired_by_toxicity
+            + self.retired_by_starvation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stem::CellRole;
+
+    #[test]
+    fn test_toxicity_has_priority() {
+        let policy = LifecyclePolicy::new().with_max_age(Some(10));
+        let mut cell = CellInfo::new("c".to_string(), CellRole::Sensor, 1);
+        cell.toxicity = 2.0;
+
+        let cause = policy.apoptosis_cause(&cell, cell.birth_time + 100);
+        assert_eq!(cause, Some(ApoptosisCause::Toxicity));
+    }
+
+    #[test]
+    fn test_age_and_inactivity() {
+        let policy = LifecyclePolicy::new()
+            .with_max_age(Some(1_000))
+            .with_inactivity(0.1, 5);
+        let mut cell = CellInfo::new("c".to_string(), CellRole::Logic, 1);
+
+        assert_eq!(policy.apoptosis_cause(&cell, cell.birth_time), None);
+        assert_eq!(
+            policy.apoptosis_cause(&cell, cell.birth_time + 1_000),
+            Some(ApoptosisCause::Age)
+        );
+
+        cell.idle_ticks = 5;
+        assert_eq!(
+            policy.apoptosis_cause(&cell, cell.birth_time),
+            Some(ApoptosisCause::Inactivity)
+        );
+    }
+
+    #[test]
+    fn test_lifecycle_stats() {
+        let mut stats = LifecycleStats::default();
+        stats.record(ApoptosisCause::Age);
+        stats.record(ApoptosisCause::Toxicity);
+        stats.record(ApoptosisCause::Toxicity);
+
+        assert_eq!(stats.retired_by_toxicity, 2);
+        assert_eq!(stats.total_retired(), 3);
+    }
+}
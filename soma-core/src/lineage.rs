@@ -0,0 +1,447 @@
+//! Родословная клеток - кто от кого произошёл
+//!
+//! Каждое деление записывается как ребро родитель → потомок. Умершие
+//! клетки остаются в дереве (с моментом и причиной гибели), чтобы можно
+//! было разобраться, почему вымирают или переспециализируются целые ветви.
+//! При превышении лимита вытесняются старейшие умершие листья.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::lineage;
+use crate::lifecycle::ApoptosisCause;
+use crate::stem::CellRole;
+
+/// Узел родословной - одна клетка, живая или умершая
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineageNode {
+    /// Идентификатор клетки
+    pub id: String,
+    /// Роль клетки
+    pub role: CellRole,
+    /// Родительская клетка (None - клетка порождена самим стволовым процессором)
+    pub parent: Option<String>,
+    /// Дочерние клетки
+    pub children: Vec<String>,
+    /// Поколение клетки
+    pub generation: u32,
+    /// Время рождения (timestamp в миллисекундах)
+    pub birth_time: u64,
+    /// Время гибели (None - клетка жива)
+    pub death_time: Option<u64>,
+    /// Причина гибели (None - жива или удалена вручную)
+    pub death_cause: Option<ApoptosisCause>,
+}
+
+impl LineageNode {
+    /// Жива ли клетка
+    pub fn is_alive(&self) -> bool {
+        self.death_time.is_none()
+    }
+}
+
+/// Статистика поддерева родословной
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SubtreeStats {
+    /// Всего клеток в поддереве (включая корень)
+    pub total: usize,
+    /// Живых клеток
+    pub alive: usize,
+    /// Умерших клеток
+    pub dead: usize,
+    /// Глубина поддерева (0 - только корень)
+    pub depth: usize,
+    /// Количество клеток по ролям
+    pub by_role: HashMap<CellRole, usize>,
+    /// Количество гибелей по причинам
+    pub deaths_by_cause: HashMap<ApoptosisCause, usize>,
+}
+
+/// Родословное дерево клеток организма
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "LineageRecord", into = "LineageRecord")]
+pub struct Lineage {
+    nodes: BTreeMap<String, LineageNode>,
+    /// Умершие клетки в порядке гибели (может содержать уже вытесненные)
+    dead: VecDeque<String>,
+    /// Умершие клетки без потомков в порядке гибели - первые на вытеснение
+    dead_leaves: VecDeque<String>,
+    /// Число хранимых умерших клеток
+    dead_count: usize,
+    /// Максимальное число хранимых умерших клеток
+    max_dead: usize,
+}
+
+/// Сериализуемое представление родословной
+#[derive(Serialize, Deserialize)]
+struct LineageRecord {
+    nodes: BTreeMap<String, LineageNode>,
+    dead: VecDeque<String>,
+    max_dead: usize,
+}
+
+impl From<LineageRecord> for Lineage {
+    fn from(record: LineageRecord) -> Self {
+        let mut lineage = Self::with_max_dead(record.max_dead);
+        lineage.nodes = record.nodes;
+        for id in record.dead {
+            let Some(node) = lineage.nodes.get(&id) else { continue };
+            if !node.is_alive() {
+                if node.children.is_empty() {
+                    lineage.dead_leaves.push_back(id.clone());
+                }
+                lineage.dead.push_back(id);
+                lineage.dead_count += 1;
+            }
+        }
+        lineage
+    }
+}
+
+impl From<Lineage> for LineageRecord {
+    fn from(mut lineage: Lineage) -> Self {
+        lineage.compact();
+        Self {
+            nodes: lineage.nodes,
+            dead: lineage.dead,
+            max_dead: lineage.max_dead,
+        }
+    }
+}
+
+impl PartialEq for Lineage {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes && self.max_dead == other.max_dead
+    }
+}
+
+impl Lineage {
+    /// Создать пустую родословную
+    pub fn new() -> Self {
+        Self::with_max_dead(lineage::DEFAULT_MAX_DEAD)
+    }
+
+    /// Создать пустую родословную с лимитом хранимых умерших клеток
+    pub fn with_max_dead(max_dead: usize) -> Self {
+        Self {
+            nodes: BTreeMap::new(),
+            dead: VecDeque::new(),
+            dead_leaves: VecDeque::new(),
+            dead_count: 0,
+            max_dead,
+        }
+    }
+
+    /// Записать рождение клетки
+    pub fn record_birth(
+        &mut self,
+        id: &str,
+        role: CellRole,
+        parent: Option<&str>,
+        generation: u32,
+        birth_time: u64,
+    ) {
+        // Родитель мог быть уже вытеснен из истории
+        let parent = parent.filter(|p| self.nodes.contains_key(*p));
+        if let Some(parent) = parent {
+            if let Some(node) = self.nodes.get_mut(parent) {
+                node.children.push(id.to_string());
+            }
+        }
+
+        self.nodes.insert(
+            id.to_string(),
+            LineageNode {
+                id: id.to_string(),
+                role,
+                parent: parent.map(str::to_string),
+                children: Vec::new(),
+                generation,
+                birth_time,
+                death_time: None,
+                death_cause: None,
+            },
+        );
+    }
+
+    /// Записать гибель клетки
+    pub fn record_death(&mut self, id: &str, time: u64, cause: Option<ApoptosisCause>) {
+        let Some(node) = self.nodes.get_mut(id) else { return };
+        if !node.is_alive() {
+            return;
+        }
+        node.death_time = Some(time);
+        node.death_cause = cause;
+        if node.children.is_empty() {
+            self.dead_leaves.push_back(id.to_string());
+        }
+        self.dead.push_back(id.to_string());
+        self.dead_count += 1;
+        self.evict();
+    }
+
+    /// Вытеснить умершие клетки сверх лимита
+    ///
+    /// Сначала вытесняются старейшие умершие листья. Если листьев не
+    /// осталось, вытесняется старейшая умершая клетка, а её потомки
+    /// переходят к её родителю
+    fn evict(&mut self) {
+        while self.dead_count > self.max_dead {
+            let Some(id) = self.dead_leaves.pop_front().or_else(|| self.dead.pop_front()) else { break };
+            self.remove_dead(&id);
+        }
+        if self.dead.len() > 2 * self.dead_count + lineage::DEFAULT_MAX_DEAD {
+            self.compact();
+        }
+    }
+
+    /// Удалить умершую клетку из дерева (уже удалённые пропускаются)
+    fn remove_dead(&mut self, id: &str) {
+        if self.nodes.get(id).is_none_or(LineageNode::is_alive) {
+            return;
+        }
+        let Some(node) = self.nodes.remove(id) else { return };
+        self.dead_count -= 1;
+
+        for child in &node.children {
+            if let Some(child) = self.nodes.get_mut(child) {
+                child.parent = node.parent.clone();
+            }
+        }
+        let Some(parent) = node.parent.as_ref().and_then(|p| self.nodes.get_mut(p)) else { return };
+        let position = parent.children.iter().position(|c| c == id);
+        if let Some(position) = position {
+            parent.children.splice(position..=position, node.children);
+        }
+        if !parent.is_alive() && parent.children.is_empty() {
+            self.dead_leaves.push_back(parent.id.clone());
+        }
+    }
+
+    /// Убрать из очередей умерших уже вытесненные клетки
+    fn compact(&mut self) {
+        let nodes = &self.nodes;
+        self.dead.retain(|id| nodes.contains_key(id));
+        self.dead_leaves.retain(|id| nodes.contains_key(id));
+    }
+
+    /// Получить узел родословной
+    pub fn get(&self, id: &str) -> Option<&LineageNode> {
+        self.nodes.get(id)
+    }
+
+    /// Количество клеток в родословной
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Проверить, пуста ли родословная
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Все узлы родословной, упорядоченные по идентификатору
+    pub fn nodes(&self) -> impl Iterator<Item = &LineageNode> {
+        self.nodes.values()
+    }
+
+    /// Клетки, порождённые непосредственно стволовым процессором
+    pub fn roots(&self) -> Vec<&LineageNode> {
+        self.nodes.values().filter(|n| n.parent.is_none()).collect()
+    }
+
+    /// Предки клетки - от родителя к корню
+    pub fn ancestors(&self, id: &str) -> Vec<&LineageNode> {
+        let mut result = Vec::new();
+        let mut current = self.nodes.get(id).and_then(|n| n.parent.as_deref());
+        while let Some(parent) = current.and_then(|p| self.nodes.get(p)) {
+            result.push(parent);
+            current = parent.parent.as_deref();
+        }
+        result
+    }
+
+    /// Потомки клетки - в порядке обхода в ширину
+    pub fn descendants(&self, id: &str) -> Vec<&LineageNode> {
+        let mut result = Vec::new();
+        let mut queue: VecDeque<&str> = VecDeque::from([id]);
+        while let Some(current) = queue.pop_front() {
+            let Some(node) = self.nodes.get(current) else { continue };
+            for child in node.children.iter().filter_map(|c| self.nodes.get(c)) {
+                result.push(child);
+                queue.push_back(&child.id);
+            }
+        }
+        result
+    }
+
+    /// Статистика поддерева с корнем в заданной клетке
+    pub fn subtree_stats(&self, id: &str) -> Option<SubtreeStats> {
+        let root = self.nodes.get(id)?;
+        let mut stats = SubtreeStats::default();
+        let mut stack = vec![(root, 0usize)];
+
+        while let Some((node, depth)) = stack.pop() {
+            stats.total += 1;
+            stats.depth = stats.depth.max(depth);
+            *stats.by_role.entry(node.role).or_insert(0) += 1;
+            if node.is_alive() {
+                stats.alive += 1;
+            } else {
+                stats.dead += 1;
+                if let Some(cause) = node.death_cause {
+                    *stats.deaths_by_cause.entry(cause).or_insert(0) += 1;
+                }
+            }
+            for child in node.children.iter().filter_map(|c| self.nodes.get(c)) {
+                stack.push((child, depth + 1));
+            }
+        }
+        Some(stats)
+    }
+
+    /// Экспорт родословной в формате GraphViz DOT
+    ///
+    /// Умершие клетки рисуются пунктиром
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph lineage {\n    node [shape=box];\n    stem [shape=doublecircle];\n");
+        for node in self.nodes.values() {
+            let style = if node.is_alive() { "solid" } else { "dashed" };
+            let id = escape_dot(&node.id);
+            let _ = writeln!(
+                dot,
+                "    \"{}\" [label=\"{}\\n{:?} g{}\", style={}];",
+                id, id, node.role, node.generation, style
+            );
+            match &node.parent {
+                Some(parent) => {
+                    let _ = writeln!(dot, "    \"{}\" -> \"{}\";", escape_dot(parent), id);
+                }
+                None => {
+                    let _ = writeln!(dot, "    stem -> \"{}\";", id);
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Экспорт родословной в JSON (список узлов, упорядоченный по идентификатору)
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let nodes: Vec<&LineageNode> = self.nodes().collect();
+        serde_json::to_string_pretty(&nodes)
+    }
+}
+
+impl Default for Lineage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Экранировать строку для идентификатора DOT в кавычках
+fn escape_dot(id: &str) -> String {
+    id.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Lineage {
+        // root -> a -> (b, c); c -> d
+        let mut lineage = Lineage::new();
+        lineage.record_birth("root", CellRole::Sensor, None, 1, 0);
+        lineage.record_birth("a", CellRole::Logic, Some("root"), 2, 1);
+        lineage.record_birth("b", CellRole::Motor, Some("a"), 3, 2);
+        lineage.record_birth("c", CellRole::Logic, Some("a"), 4, 3);
+        lineage.record_birth("d", CellRole::Logic, Some("c"), 5, 4);
+        lineage
+    }
+
+    #[test]
+    fn test_ancestors_and_descendants() {
+        let lineage = tree();
+
+        let ancestors: Vec<_> = lineage.ancestors("d").iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ancestors, vec!["c", "a", "root"]);
+
+        let descendants: Vec<_> = lineage.descendants("a").iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(descendants, vec!["b", "c", "d"]);
+        assert_eq!(lineage.roots().len(), 1);
+    }
+
+    #[test]
+    fn test_subtree_stats_include_dead_cells() {
+        let mut lineage = tree();
+        lineage.record_death("c", 10, Some(ApoptosisCause::Toxicity));
+        lineage.record_death("d", 10, None);
+
+        let stats = lineage.subtree_stats("a").unwrap();
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.alive, 2);
+        assert_eq!(stats.dead, 2);
+        assert_eq!(stats.depth, 2);
+        assert_eq!(stats.by_role[&CellRole::Logic], 3);
+        assert_eq!(stats.deaths_by_cause[&ApoptosisCause::Toxicity], 1);
+    }
+
+    #[test]
+    fn test_evicts_oldest_dead_leaves() {
+        let mut lineage = tree();
+        lineage.max_dead = 1;
+        lineage.record_death("c", 10, None);
+        lineage.record_death("b", 11, None);
+
+        // c ещё имеет потомка d, поэтому вытесняется лист b
+        assert!(lineage.get("b").is_none());
+        assert!(lineage.get("c").is_some());
+        assert_eq!(lineage.get("a").unwrap().children, vec!["c".to_string()]);
+    }
+
+    #[test]
+    fn test_max_dead_is_enforced_for_dead_ancestors() {
+        let mut lineage = tree();
+        lineage.max_dead = 1;
+        lineage.record_death("a", 10, None);
+        lineage.record_death("c", 11, None);
+
+        // Листьев среди умерших нет: a вытесняется, его потомки переходят к root
+        assert!(lineage.get("a").is_none());
+        assert_eq!(lineage.get("root").unwrap().children, vec!["b".to_string(), "c".to_string()]);
+        assert_eq!(lineage.get("c").unwrap().parent.as_deref(), Some("root"));
+
+        // Умерший лист d вытесняется раньше c, и c сам становится листом
+        lineage.record_death("d", 12, None);
+        assert!(lineage.get("d").is_none());
+        assert!(lineage.get("c").unwrap().children.is_empty());
+        assert_eq!(lineage.dead_leaves, VecDeque::from(["c".to_string()]));
+        assert_eq!(lineage.nodes().filter(|n| !n.is_alive()).count(), 1);
+
+        let restored: Lineage = serde_json::from_str(&serde_json::to_string(&lineage).unwrap()).unwrap();
+        assert_eq!(restored, lineage);
+        assert_eq!(restored.dead_count, 1);
+    }
+
+    #[test]
+    fn test_exports() {
+        let mut lineage = tree();
+        lineage.record_death("b", 5, Some(ApoptosisCause::Age));
+
+        let dot = lineage.to_dot();
+        assert!(dot.starts_with("digraph lineage {"));
+        assert!(dot.contains("\"a\" -> \"b\";"));
+        assert!(dot.contains("stem -> \"root\";"));
+        assert!(dot.contains("style=dashed"));
+
+        let json: serde_json::Value = serde_json::from_str(&lineage.to_json().unwrap()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 5);
+        assert_eq!(json[1]["death_cause"], "Age");
+
+        // Идентификаторы экранируются
+        lineage.record_birth("say \"hi\"\\", CellRole::Memory, Some("a"), 6, 5);
+        assert!(lineage.to_dot().contains("\"a\" -> \"say \\\"hi\\\"\\\\\";"));
+    }
+}
@@ -0,0 +1,213 @@
+//! Многомерная нагрузка - давление на вход, вычисления и выход
+//!
+//! Скалярная нагрузка не различает, чего именно не хватает организму.
+//! Векторная модель ведёт отдельное сглаживание и порог для каждого
+//! измерения, а деление направляется в роль перегруженного измерения:
+//! при давлении на вход рождаются сенсоры, а не моторы.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::stem;
+use crate::stem::CellRole;
+
+/// Измерение нагрузки
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LoadDimension {
+    /// Давление на вход (приём данных)
+    Input,
+    /// Давление на вычисления
+    Compute,
+    /// Давление на выход (исполнение)
+    Output,
+}
+
+impl LoadDimension {
+    /// Все измерения нагрузки
+    pub const ALL: [LoadDimension; 3] = [Self::Input, Self::Compute, Self::Output];
+
+    /// Роль, которая снимает давление этого измерения
+    pub fn role(&self) -> CellRole {
+        match self {
+            Self::Input => CellRole::Sensor,
+            Self::Compute => CellRole::Logic,
+            Self::Output => CellRole::Motor,
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Self::Input => 0,
+            Self::Compute => 1,
+            Self::Output => 2,
+        }
+    }
+}
+
+/// Вектор нагрузки по измерениям (каждое 0.0 - 1.0)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoadVector {
+    /// Давление на вход
+    pub input: f64,
+    /// Давление на вычисления
+    pub compute: f64,
+    /// Давление на выход
+    pub output: f64,
+}
+
+impl LoadVector {
+    /// Создать вектор нагрузки
+    pub fn new(input: f64, compute: f64, output: f64) -> Self {
+        Self { input, compute, output }
+    }
+
+    /// Одинаковая нагрузка по всем измерениям
+    pub fn uniform(value: f64) -> Self {
+        Self::new(value, value, value)
+    }
+
+    /// Нагрузка по измерению
+    pub fn get(&self, dimension: LoadDimension) -> f64 {
+        match dimension {
+            LoadDimension::Input => self.input,
+            LoadDimension::Compute => self.compute,
+            LoadDimension::Output => self.output,
+        }
+    }
+
+    /// Установить нагрузку по измерению
+    pub fn set(&mut self, dimension: LoadDimension, value: f64) {
+        match dimension {
+            LoadDimension::Input => self.input = value,
+            LoadDimension::Compute => self.compute = value,
+            LoadDimension::Output => self.output = value,
+        }
+    }
+
+    /// Наибольшая нагрузка по измерениям
+    pub fn max(&self) -> f64 {
+        self.input.max(self.compute).max(self.output)
+    }
+}
+
+/// Параметры одного измерения нагрузки
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DimensionParams {
+    /// Порог деления
+    pub threshold: f64,
+    /// Коэффициент сглаживания
+    pub smoothing: f64,
+}
+
+/// Векторная модель нагрузки
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadModel {
+    params: [DimensionParams; 3],
+    load: LoadVector,
+}
+
+impl LoadModel {
+    /// Создать модель с одинаковыми параметрами для всех измерений
+    pub fn new(threshold: f64, smoothing: f64) -> Self {
+        let params = DimensionParams {
+            threshold: threshold.clamp(0.0, 1.0),
+            smoothing: smoothing.clamp(0.0, 1.0),
+        };
+        Self {
+            params: [params; 3],
+            load: LoadVector::default(),
+        }
+    }
+
+    /// Задать параметры измерения (builder)
+    pub fn with_dimension(mut self, dimension: LoadDimension, threshold: f64, smoothing: f64) -> Self {
+        self.params[dimension.index()] = DimensionParams {
+            threshold: threshold.clamp(0.0, 1.0),
+            smoothing: smoothing.clamp(0.0, 1.0),
+        };
+        self
+    }
+
+    /// Параметры измерения
+    pub fn params(&self, dimension: LoadDimension) -> DimensionParams {
+        self.params[dimension.index()]
+    }
+
+    /// Текущая сглаженная нагрузка
+    pub fn load(&self) -> LoadVector {
+        self.load
+    }
+
+    /// Учесть новое измерение нагрузки (экспоненциальное сглаживание)
+    pub fn update(&mut self, sample: LoadVector) {
+        for dimension in LoadDimension::ALL {
+            let DimensionParams { smoothing, .. } = self.params(dimension);
+            let value = self.load.get(dimension) * smoothing + sample.get(dimension) * (1.0 - smoothing);
+            self.load.set(dimension, value);
+        }
+    }
+
+    /// Превышение порога по измерению (отрицательное - ниже порога)
+    pub fn excess(&self, dimension: LoadDimension) -> f64 {
+        self.load.get(dimension) - self.params(dimension).threshold
+    }
+
+    /// Превышает ли нагрузка порог хотя бы по одному измерению
+    pub fn is_over_threshold(&self) -> bool {
+        LoadDimension::ALL.iter().any(|d| self.excess(*d) > 0.0)
+    }
+
+    /// Наиболее перегруженное измерение (None - все ниже порога)
+    pub fn most_pressured(&self) -> Option<LoadDimension> {
+        LoadDimension::ALL
+            .into_iter()
+            .filter(|d| self.excess(*d) > 0.0)
+            .max_by(|a, b| self.excess(*a).total_cmp(&self.excess(*b)))
+    }
+
+    /// Снизить нагрузку измерения после деления
+    pub fn relieve(&mut self, dimension: LoadDimension) {
+        let value = self.load.get(dimension) * stem::LOAD_REDUCTION_FACTOR;
+        self.load.set(dimension, value);
+    }
+}
+
+impl Default for LoadModel {
+    fn default() -> Self {
+        Self::new(stem::DEFAULT_THRESHOLD, stem::DEFAULT_SMOOTHING)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dimensions_smooth_independently() {
+        let mut model = LoadModel::new(0.5, 0.5).with_dimension(LoadDimension::Output, 0.5, 0.0);
+        model.update(LoadVector::new(1.0, 0.0, 1.0));
+
+        assert_eq!(model.load(), LoadVector::new(0.5, 0.0, 1.0));
+        assert_eq!(model.params(LoadDimension::Output).smoothing, 0.0);
+    }
+
+    #[test]
+    fn test_most_pressured_dimension() {
+        let mut model = LoadModel::new(0.5, 0.0).with_dimension(LoadDimension::Input, 0.2, 0.0);
+        model.update(LoadVector::new(0.4, 0.6, 0.3));
+
+        // Вход превышает свой порог сильнее, чем вычисления
+        assert_eq!(model.most_pressured(), Some(LoadDimension::Input));
+        model.relieve(LoadDimension::Input);
+        assert_eq!(model.most_pressured(), Some(LoadDimension::Compute));
+
+        model.update(LoadVector::uniform(0.1));
+        assert!(!model.is_over_threshold());
+    }
+
+    #[test]
+    fn test_dimension_roles() {
+        assert_eq!(LoadDimension::Input.role(), CellRole::Sensor);
+        assert_eq!(LoadDimension::Compute.role(), CellRole::Logic);
+        assert_eq!(LoadDimension::Output.role(), CellRole::Motor);
+    }
+}
@@ -0,0 +1,313 @@
+//! Морфогены - пространственные сигналы, направляющие дифференциацию
+//!
+//! Клетки получают координаты (2D или 3D), дочерние клетки рождаются
+//! рядом с родителем. Каждая клетка выделяет морфоген своей роли в
+//! решётку поля, которое каждый тик диффундирует и распадается. При
+//! делении стратегия выбора роли видит локальные концентрации в точке
+//! рождения, что позволяет выращивать структурированные ткани.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::morphogen;
+use crate::stem::CellRole;
+
+/// Координаты клетки (для плоских полей `z = 0`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Position {
+    /// Точка в пространстве
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Точка на плоскости
+    pub fn planar(x: f64, y: f64) -> Self {
+        Self::new(x, y, 0.0)
+    }
+
+    /// Евклидово расстояние до другой точки
+    pub fn distance(&self, other: &Position) -> f64 {
+        let (dx, dy, dz) = (self.x - other.x, self.y - other.y, self.z - other.z);
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
+    /// Сдвинутая точка
+    pub fn offset(&self, dx: f64, dy: f64, dz: f64) -> Self {
+        Self::new(self.x + dx, self.y + dy, self.z + dz)
+    }
+}
+
+/// Поле морфогенов на регулярной решётке
+///
+/// Каждая роль выделяет свой морфоген. Решётка покрывает область
+/// `[0, width * spacing) × [0, height * spacing) × [0, depth * spacing)`,
+/// точки вне области притягиваются к ближайшей границе.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "MorphogenRecord")]
+pub struct MorphogenField {
+    width: usize,
+    height: usize,
+    depth: usize,
+    /// Размер ячейки решётки
+    spacing: f64,
+    /// Доля концентрации, уходящая к соседям за тик (0.0 - 1.0)
+    pub diffusion: f64,
+    /// Доля концентрации, распадающаяся за тик (0.0 - 1.0)
+    pub decay: f64,
+    /// Выделение морфогена одной клеткой за тик
+    pub emission: f64,
+    channels: HashMap<CellRole, Vec<f64>>,
+    /// Соседи каждой ячейки по граням (строятся по размерам решётки)
+    #[serde(skip)]
+    neighbours: Vec<Vec<usize>>,
+}
+
+/// Сериализуемое представление поля
+#[derive(Deserialize)]
+struct MorphogenRecord {
+    width: usize,
+    height: usize,
+    depth: usize,
+    spacing: f64,
+    diffusion: f64,
+    decay: f64,
+    emission: f64,
+    channels: HashMap<CellRole, Vec<f64>>,
+}
+
+impl From<MorphogenRecord> for MorphogenField {
+    fn from(record: MorphogenRecord) -> Self {
+        let mut field = Self::new(record.width, record.height, record.depth, record.spacing);
+        field.diffusion = record.diffusion;
+        field.decay = record.decay;
+        field.emission = record.emission;
+        field.channels = record.channels;
+        field
+    }
+}
+
+impl MorphogenField {
+    /// Создать объёмное поле
+    pub fn new(width: usize, height: usize, depth: usize, spacing: f64) -> Self {
+        let mut field = Self {
+            width: width.max(1),
+            height: height.max(1),
+            depth: depth.max(1),
+            spacing: if spacing > 0.0 { spacing } else { 1.0 },
+            diffusion: morphogen::DEFAULT_DIFFUSION,
+            decay: morphogen::DEFAULT_DECAY,
+            emission: morphogen::DEFAULT_EMISSION,
+            channels: HashMap::new(),
+            neighbours: Vec::new(),
+        };
+        let size = field.width * field.height * field.depth;
+        field.neighbours = (0..size).map(|voxel| field.adjacent(voxel)).collect();
+        field
+    }
+
+    /// Создать плоское поле
+    pub fn planar(width: usize, height: usize, spacing: f64) -> Self {
+        Self::new(width, height, 1, spacing)
+    }
+
+    /// Установить коэффициент диффузии
+    pub fn with_diffusion(mut self, diffusion: f64) -> Self {
+        self.diffusion = diffusion.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Установить коэффициент распада
+    pub fn with_decay(mut self, decay: f64) -> Self {
+        self.decay = decay.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Установить выделение морфогена клеткой
+    pub fn with_emission(mut self, emission: f64) -> Self {
+        self.emission = emission.max(0.0);
+        self
+    }
+
+    /// Плоское ли поле
+    pub fn is_planar(&self) -> bool {
+        self.depth == 1
+    }
+
+    /// Размеры решётки (ширина, высота, глубина)
+    pub fn dimensions(&self) -> (usize, usize, usize) {
+        (self.width, self.height, self.depth)
+    }
+
+    /// Центр области поля
+    pub fn center(&self) -> Position {
+        let z = if self.is_planar() { 0.0 } else { self.extent(self.depth) / 2.0 };
+        Position::new(self.extent(self.width) / 2.0, self.extent(self.height) / 2.0, z)
+    }
+
+    fn extent(&self, cells: usize) -> f64 {
+        cells as f64 * self.spacing
+    }
+
+    /// Притянуть точку к области поля
+    pub fn clamp(&self, position: Position) -> Position {
+        let limit = |value: f64, cells: usize| value.clamp(0.0, self.extent(cells) - f64::EPSILON);
+        let z = if self.is_planar() { 0.0 } else { limit(position.z, self.depth) };
+        Position::new(limit(position.x, self.width), limit(position.y, self.height), z)
+    }
+
+    /// Ячейка решётки, содержащая точку
+    fn voxel(&self, position: &Position) -> usize {
+        let axis = |value: f64, cells: usize| ((value / self.spacing).floor().max(0.0) as usize).min(cells - 1);
+        let x = axis(position.x, self.width);
+        let y = axis(position.y, self.height);
+        let z = axis(position.z, self.depth);
+        (z * self.height + y) * self.width + x
+    }
+
+    /// Выделить морфоген роли в точке
+    pub fn emit(&mut self, role: CellRole, position: &Position, amount: f64) {
+        let voxel = self.voxel(position);
+        let size = self.width * self.height * self.depth;
+        self.channels.entry(role).or_insert_with(|| vec![0.0; size])[voxel] += amount;
+    }
+
+    /// Концентрация морфогена роли в точке
+    pub fn concentration(&self, role: CellRole, position: &Position) -> f64 {
+        self.channels
+            .get(&role)
+            .map_or(0.0, |channel| channel[self.voxel(position)])
+    }
+
+    /// Концентрации всех морфогенов в точке
+    pub fn concentrations(&self, position: &Position) -> HashMap<CellRole, f64> {
+        let voxel = self.voxel(position);
+        self.channels
+            .iter()
+            .map(|(role, channel)| (*role, channel[voxel]))
+            .collect()
+    }
+
+    /// Суммарное количество морфогена роли в поле
+    pub fn total(&self, role: CellRole) -> f64 {
+        self.channels.get(&role).map_or(0.0, |channel| channel.iter().sum())
+    }
+
+    /// Соседи ячейки по граням (границы отражающие)
+    fn adjacent(&self, voxel: usize) -> Vec<usize> {
+        let x = voxel % self.width;
+        let y = (voxel / self.width) % self.height;
+        let z = voxel / (self.width * self.height);
+        let layer = self.width * self.height;
+
+        let mut result = Vec::with_capacity(6);
+        if x > 0 {
+            result.push(voxel - 1);
+        }
+        if x + 1 < self.width {
+            result.push(voxel + 1);
+        }
+        if y > 0 {
+            result.push(voxel - self.width);
+        }
+        if y + 1 < self.height {
+            result.push(voxel + self.width);
+        }
+        if z > 0 {
+            result.push(voxel - layer);
+        }
+        if z + 1 < self.depth {
+            result.push(voxel + layer);
+        }
+        result
+    }
+
+    /// Шаг поля - диффузия к соседям и распад
+    ///
+    /// Диффузия сохраняет количество морфогена, убыль даёт только распад
+    pub fn step(&mut self) {
+        let retain = 1.0 - self.decay;
+
+        for channel in self.channels.values_mut() {
+            let mut next = channel.clone();
+            for (voxel, adjacent) in self.neighbours.iter().enumerate() {
+                if adjacent.is_empty() {
+                    continue;
+                }
+                let outflow = channel[voxel] * self.diffusion;
+                next[voxel] -= outflow;
+                let share = outflow / adjacent.len() as f64;
+                for &n in adjacent {
+                    next[n] += share;
+                }
+            }
+            for (value, next) in channel.iter_mut().zip(next) {
+                *value = next * retain;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diffusion_conserves_and_spreads() {
+        let mut field = MorphogenField::planar(5, 5, 1.0).with_diffusion(0.4).with_decay(0.0);
+        let center = field.center();
+        field.emit(CellRole::Sensor, &center, 1.0);
+        field.step();
+
+        assert!((field.total(CellRole::Sensor) - 1.0).abs() < 1e-9);
+        assert!((field.concentration(CellRole::Sensor, &center) - 0.6).abs() < 1e-9);
+        assert!((field.concentration(CellRole::Sensor, &center.offset(1.0, 0.0, 0.0)) - 0.1).abs() < 1e-9);
+        assert_eq!(field.concentration(CellRole::Sensor, &Position::planar(0.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_decay_and_channels() {
+        let mut field = MorphogenField::new(2, 2, 2, 0.5).with_diffusion(0.0).with_decay(0.5);
+        let corner = Position::new(0.9, 0.9, 0.9);
+        field.emit(CellRole::Motor, &corner, 2.0);
+        field.step();
+
+        let local = field.concentrations(&corner);
+        assert_eq!(local[&CellRole::Motor], 1.0);
+        assert!(!local.contains_key(&CellRole::Logic));
+        assert!(!field.is_planar());
+    }
+
+    #[test]
+    fn test_neighbours_rebuilt_after_deserialize() {
+        let mut field = MorphogenField::new(3, 2, 2, 1.0).with_diffusion(0.3);
+        field.emit(CellRole::Hub, &Position::new(0.5, 0.5, 0.5), 1.0);
+
+        let json = serde_json::to_value(&field).unwrap();
+        assert!(json.get("neighbours").is_none());
+        let mut restored: MorphogenField = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, field);
+
+        field.step();
+        restored.step();
+        assert_eq!(restored, field);
+        assert!(field.concentration(CellRole::Hub, &Position::new(1.5, 0.5, 0.5)) > 0.0);
+    }
+
+    #[test]
+    fn test_clamp_keeps_points_inside() {
+        let field = MorphogenField::planar(4, 2, 0.5);
+        let clamped = field.clamp(Position::new(-1.0, 5.0, 3.0));
+
+        assert_eq!(clamped.x, 0.0);
+        assert!(clamped.y < 1.0 && clamped.y > 0.99);
+        assert_eq!(clamped.z, 0.0);
+        assert_eq!(field.center(), Position::planar(1.0, 0.5));
+    }
+}
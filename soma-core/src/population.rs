@@ -0,0 +1,553 @@
+//! Популяция клеток - хранение структурой массивов (SoA)
+//!
+//! Атрибуты клеток лежат в плотных параллельных массивах, поэтому тик
+//! проходит по непрерывной памяти без хэширования и строк. Клетка
+//! адресуется плотным [`CellId`] (индекс слота, слоты переиспользуются).
+//!
+//! Строковые идентификаторы вычисляются по требованию в каноническом
+//! формате `cell_{поколение}_{слот}` и не хранятся. Клетки с другими
+//! именами (например, из старых снапшотов) хранят имя явно. Методы
+//! `get`, `keys`, `values`, `contains_key` образуют совместимое
+//! представление со строковыми ключами.
+
+use std::collections::HashMap;
+use std::thread;
+
+use crate::config::population;
+use crate::morphogen::Position;
+use crate::stem::{CellInfo, CellRole};
+use crate::tissue::TissueId;
+
+/// Плотный идентификатор клетки - индекс слота в популяции
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CellId(u32);
+
+impl CellId {
+    /// Индекс слота
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Популяция клеток в виде структуры массивов
+///
+/// Массивы упакованы: при удалении последняя клетка переносится на место
+/// удалённой, поэтому порядок клеток в массивах не стабилен.
+#[derive(Debug, Clone, Default)]
+pub struct CellPopulation {
+    pub(crate) ids: Vec<CellId>,
+    pub(crate) roles: Vec<CellRole>,
+    pub(crate) generations: Vec<u32>,
+    pub(crate) birth_times: Vec<u64>,
+    pub(crate) activity: Vec<f64>,
+    pub(crate) toxicity: Vec<f64>,
+    pub(crate) idle_ticks: Vec<u32>,
+    /// Активность задаётся живым экземпляром клетки (без затухания)
+    pub(crate) driven: Vec<bool>,
+    /// Координаты клеток в пространстве организма
+    pub(crate) positions: Vec<Option<Position>>,
+    /// Ткани, которым принадлежат клетки
+    pub(crate) memberships: Vec<Option<TissueId>>,
+    /// Слот -> индекс в упакованных массивах
+    slots: Vec<Option<u32>>,
+    /// Свободные слоты для переиспользования
+    free: Vec<u32>,
+    /// Неканонические имена клеток
+    aliases: HashMap<String, CellId>,
+    names: HashMap<CellId, String>,
+    /// Префикс канонических имён (у почек - идентификатор процессора)
+    prefix: String,
+}
+
+impl CellPopulation {
+    /// Создать пустую популяцию
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Создать популяцию с заранее выделенной памятью
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            ids: Vec::with_capacity(capacity),
+            roles: Vec::with_capacity(capacity),
+            generations: Vec::with_capacity(capacity),
+            birth_times: Vec::with_capacity(capacity),
+            activity: Vec::with_capacity(capacity),
+            toxicity: Vec::with_capacity(capacity),
+            idle_ticks: Vec::with_capacity(capacity),
+            driven: Vec::with_capacity(capacity),
+            positions: Vec::with_capacity(capacity),
+            memberships: Vec::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// Количество клеток
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Проверить, пуста ли популяция
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Удалить все клетки (префикс имён сохраняется)
+    pub fn clear(&mut self) {
+        let prefix = std::mem::take(&mut self.prefix);
+        *self = Self::default().with_name_prefix(prefix);
+    }
+
+    /// Задать префикс канонических имён клеток
+    ///
+    /// Почки получают префикс, чтобы имена их клеток не совпадали с
+    /// именами клеток родителя. Задаётся только для пустой популяции:
+    /// индекс имён уже добавленных клеток не перестраивается.
+    pub fn with_name_prefix(mut self, prefix: impl Into<String>) -> Self {
+        debug_assert!(self.is_empty(), "префикс задаётся только для пустой популяции");
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Префикс канонических имён клеток
+    pub fn name_prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Создать популяцию из описаний клеток
+    ///
+    /// Клетки с каноническими именами по возможности занимают свои
+    /// прежние слоты, чтобы имена сохранились без явного хранения.
+    pub fn from_infos(cells: Vec<CellInfo>) -> Self {
+        Self::from_infos_with_prefix(String::new(), cells)
+    }
+
+    /// Создать популяцию из описаний клеток с префиксом имён
+    pub fn from_infos_with_prefix(prefix: impl Into<String>, mut cells: Vec<CellInfo>) -> Self {
+        let prefix = prefix.into();
+        cells.sort_by_key(|cell| preferred_slot(&prefix, cell).unwrap_or(u32::MAX));
+        let mut population = Self::with_capacity(cells.len()).with_name_prefix(prefix);
+        for cell in cells {
+            population.insert_info(cell);
+        }
+        population
+    }
+
+    /// Выделить слот (по возможности - предпочтительный)
+    fn allocate(&mut self, preferred: Option<u32>) -> u32 {
+        if let Some(slot) = preferred {
+            let index = slot as usize;
+            if index >= self.slots.len() {
+                for gap in self.slots.len()..index {
+                    self.free.push(gap as u32);
+                }
+                self.slots.resize(index + 1, None);
+                return slot;
+            }
+            if self.slots[index].is_none() {
+                if let Some(pos) = self.free.iter().position(|&free| free == slot) {
+                    self.free.swap_remove(pos);
+                    return slot;
+                }
+            }
+        }
+
+        self.free.pop().unwrap_or_else(|| {
+            self.slots.push(None);
+            (self.slots.len() - 1) as u32
+        })
+    }
+
+    /// Добавить клетку и получить её идентификатор
+    pub fn insert(&mut self, role: CellRole, generation: u32, birth_time: u64) -> CellId {
+        self.insert_in(None, role, generation, birth_time)
+    }
+
+    /// Добавить клетку в заданной точке пространства
+    pub fn insert_at(&mut self, role: CellRole, generation: u32, birth_time: u64, position: Option<Position>) -> CellId {
+        let id = self.insert(role, generation, birth_time);
+        if let Some(last) = self.positions.last_mut() {
+            *last = position;
+        }
+        id
+    }
+
+    fn insert_in(&mut self, slot: Option<u32>, role: CellRole, generation: u32, birth_time: u64) -> CellId {
+        let id = CellId(self.allocate(slot));
+        self.slots[id.index()] = Some(self.ids.len() as u32);
+
+        self.ids.push(id);
+        self.roles.push(role);
+        self.generations.push(generation);
+        self.birth_times.push(birth_time);
+        self.activity.push(0.0);
+        self.toxicity.push(0.0);
+        self.idle_ticks.push(0);
+        self.driven.push(false);
+        self.positions.push(None);
+        self.memberships.push(None);
+        id
+    }
+
+    /// Добавить клетку из полного описания, сохранив её строковое имя
+    pub fn insert_info(&mut self, info: CellInfo) -> CellId {
+        let id = self.insert_in(preferred_slot(&self.prefix, &info), info.role, info.generation, info.birth_time);
+        let index = self.len() - 1;
+        self.activity[index] = info.activity;
+        self.toxicity[index] = info.toxicity;
+        self.idle_ticks[index] = info.idle_ticks;
+        self.positions[index] = info.position;
+        self.memberships[index] = info.tissue;
+
+        if info.id != canonical_name(&self.prefix, info.generation, id) {
+            self.aliases.insert(info.id.clone(), id);
+            self.names.insert(id, info.id);
+        }
+        id
+    }
+
+    /// Удалить клетку
+    pub fn remove(&mut self, id: CellId) -> Option<CellInfo> {
+        let index = self.index_of(id)?;
+        let info = self.info_at(index);
+
+        self.slots[id.index()] = None;
+        self.ids.swap_remove(index);
+        self.roles.swap_remove(index);
+        self.generations.swap_remove(index);
+        self.birth_times.swap_remove(index);
+        self.activity.swap_remove(index);
+        self.toxicity.swap_remove(index);
+        self.idle_ticks.swap_remove(index);
+        self.driven.swap_remove(index);
+        self.positions.swap_remove(index);
+        self.memberships.swap_remove(index);
+        if let Some(&moved) = self.ids.get(index) {
+            self.slots[moved.index()] = Some(index as u32);
+        }
+        self.free.push(id.0);
+
+        if let Some(name) = self.names.remove(&id) {
+            self.aliases.remove(&name);
+        }
+        Some(info)
+    }
+
+    /// Индекс клетки в упакованных массивах
+    pub fn index_of(&self, id: CellId) -> Option<usize> {
+        self.slots.get(id.index()).copied().flatten().map(|i| i as usize)
+    }
+
+    /// Существует ли клетка
+    pub fn contains(&self, id: CellId) -> bool {
+        self.index_of(id).is_some()
+    }
+
+    /// Найти клетку по строковому имени
+    pub fn resolve(&self, name: &str) -> Option<CellId> {
+        if let Some(&id) = self.aliases.get(name) {
+            return Some(id);
+        }
+
+        let (generation, slot) = parse_canonical(&self.prefix, name)?;
+        let id = CellId(slot);
+        let index = self.index_of(id)?;
+        (self.generations[index] == generation && !self.names.contains_key(&id)).then_some(id)
+    }
+
+    /// Строковое имя клетки
+    pub fn name(&self, id: CellId) -> Option<String> {
+        let index = self.index_of(id)?;
+        Some(self.name_at(index))
+    }
+
+    fn name_at(&self, index: usize) -> String {
+        let id = self.ids[index];
+        match self.names.get(&id) {
+            Some(name) => name.clone(),
+            None => canonical_name(&self.prefix, self.generations[index], id),
+        }
+    }
+
+    fn info_at(&self, index: usize) -> CellInfo {
+        CellInfo {
+            id: self.name_at(index),
+            role: self.roles[index],
+            birth_time: self.birth_times[index],
+            generation: self.generations[index],
+            activity: self.activity[index],
+            toxicity: self.toxicity[index],
+            idle_ticks: self.idle_ticks[index],
+            position: self.positions[index],
+            tissue: self.memberships[index],
+        }
+    }
+
+    /// Полное описание клетки
+    pub fn info(&self, id: CellId) -> Option<CellInfo> {
+        self.index_of(id).map(|index| self.info_at(index))
+    }
+
+    /// Роль клетки
+    pub fn role_of(&self, id: CellId) -> Option<CellRole> {
+        self.index_of(id).map(|index| self.roles[index])
+    }
+
+    /// Координаты клетки
+    pub fn position_of(&self, id: CellId) -> Option<Position> {
+        self.index_of(id).and_then(|index| self.positions[index])
+    }
+
+    /// Ткань клетки
+    pub fn tissue_of(&self, id: CellId) -> Option<TissueId> {
+        self.index_of(id).and_then(|index| self.memberships[index])
+    }
+
+    /// Включить клетку в ткань (None - исключить из ткани)
+    pub fn set_tissue(&mut self, id: CellId, tissue: Option<TissueId>) -> bool {
+        match self.index_of(id) {
+            Some(index) => {
+                self.memberships[index] = tissue;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Активность клетки
+    pub fn activity_of(&self, id: CellId) -> Option<f64> {
+        self.index_of(id).map(|index| self.activity[index])
+    }
+
+    /// Установить активность клетки
+    pub fn set_activity_of(&mut self, id: CellId, activity: f64) -> bool {
+        match self.index_of(id) {
+            Some(index) => {
+                self.activity[index] = activity;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Отметить, что активность клетки задаётся живым экземпляром
+    pub(crate) fn set_driven(&mut self, id: CellId, driven: bool) {
+        if let Some(index) = self.index_of(id) {
+            self.driven[index] = driven;
+        }
+    }
+
+    /// Сменить роль клетки
+    pub(crate) fn set_role(&mut self, id: CellId, role: CellRole) -> bool {
+        match self.index_of(id) {
+            Some(index) => {
+                self.roles[index] = role;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Идентификаторы клеток в порядке упакованных массивов
+    pub fn ids(&self) -> &[CellId] {
+        &self.ids
+    }
+
+    /// Роли клеток
+    pub fn roles(&self) -> &[CellRole] {
+        &self.roles
+    }
+
+    /// Поколения клеток
+    pub fn generations(&self) -> &[u32] {
+        &self.generations
+    }
+
+    /// Координаты клеток
+    pub fn positions(&self) -> &[Option<Position>] {
+        &self.positions
+    }
+
+    /// Ткани клеток
+    pub fn memberships(&self) -> &[Option<TissueId>] {
+        &self.memberships
+    }
+
+    /// Активности клеток
+    pub fn activities(&self) -> &[f64] {
+        &self.activity
+    }
+
+    /// Изменяемые активности клеток
+    pub fn activities_mut(&mut self) -> &mut [f64] {
+        &mut self.activity
+    }
+
+    /// Обновить активность каждой клетки функцией `(индекс, роль, ведомая, активность)`
+    ///
+    /// При `threads > 1` и достаточно большой популяции массивы делятся
+    /// на блоки, которые обрабатываются параллельно.
+    pub fn update_activity<F>(&mut self, threads: usize, update: F)
+    where
+        F: Fn(usize, CellRole, bool, f64) -> f64 + Sync,
+    {
+        let len = self.len();
+        if threads <= 1 || len < population::PARALLEL_MIN_CELLS {
+            let cells = self.activity.iter_mut().zip(&self.roles).zip(&self.driven);
+            for (index, ((activity, &role), &driven)) in cells.enumerate() {
+                *activity = update(index, role, driven, *activity);
+            }
+            return;
+        }
+
+        let chunk = len.div_ceil(threads);
+        let update = &update;
+        thread::scope(|scope| {
+            let chunks = self
+                .activity
+                .chunks_mut(chunk)
+                .zip(self.roles.chunks(chunk))
+                .zip(self.driven.chunks(chunk));
+            for (block, ((activity, roles), driven)) in chunks.enumerate() {
+                scope.spawn(move || {
+                    let cells = activity.iter_mut().zip(roles).zip(driven);
+                    for (offset, ((activity, &role), &driven)) in cells.enumerate() {
+                        *activity = update(block * chunk + offset, role, driven, *activity);
+                    }
+                });
+            }
+        });
+    }
+
+    /// Получить описание клетки по строковому имени
+    pub fn get(&self, name: &str) -> Option<CellInfo> {
+        self.resolve(name).and_then(|id| self.info(id))
+    }
+
+    /// Есть ли клетка с заданным строковым именем
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.resolve(name).is_some()
+    }
+
+    /// Установить активность клетки по строковому имени
+    pub fn set_activity(&mut self, name: &str, activity: f64) -> bool {
+        match self.resolve(name) {
+            Some(id) => self.set_activity_of(id, activity),
+            None => false,
+        }
+    }
+
+    /// Строковые имена всех клеток
+    pub fn keys(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.len()).map(|index| self.name_at(index))
+    }
+
+    /// Описания всех клеток
+    pub fn values(&self) -> impl Iterator<Item = CellInfo> + '_ {
+        (0..self.len()).map(|index| self.info_at(index))
+    }
+}
+
+/// Каноническое имя клетки
+fn canonical_name(prefix: &str, generation: u32, id: CellId) -> String {
+    format!("{}cell_{}_{}", prefix, generation, id.0)
+}
+
+/// Слот, закодированный в каноническом имени клетки
+fn preferred_slot(prefix: &str, cell: &CellInfo) -> Option<u32> {
+    parse_canonical(prefix, &cell.id)
+        .filter(|&(generation, _)| generation == cell.generation)
+        .map(|(_, slot)| slot)
+}
+
+/// Разобрать каноническое имя клетки на поколение и слот
+fn parse_canonical(prefix: &str, name: &str) -> Option<(u32, u32)> {
+    let (generation, slot) = name.strip_prefix(prefix)?.strip_prefix("cell_")?.split_once('_')?;
+    Some((generation.parse().ok()?, slot.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove_reuses_slots() {
+        let mut population = CellPopulation::new();
+        let a = population.insert(CellRole::Sensor, 1, 0);
+        let b = population.insert(CellRole::Logic, 2, 0);
+        let c = population.insert(CellRole::Motor, 3, 0);
+
+        let removed = population.remove(a).unwrap();
+        assert_eq!(removed.id, "cell_1_0");
+        assert_eq!(population.len(), 2);
+        assert!(!population.contains(a));
+        assert_eq!(population.role_of(c), Some(CellRole::Motor));
+        assert_eq!(population.role_of(b), Some(CellRole::Logic));
+
+        // Слот переиспользуется, старое имя больше не находится
+        let d = population.insert(CellRole::Hub, 4, 0);
+        assert_eq!(d.index(), a.index());
+        assert!(population.get("cell_1_0").is_none());
+        assert_eq!(population.get("cell_4_0").unwrap().role, CellRole::Hub);
+    }
+
+    #[test]
+    fn test_string_compat_view() {
+        let mut population = CellPopulation::new();
+        let id = population.insert(CellRole::Sensor, 7, 100);
+        let legacy = CellInfo::born_at("legacy_cell".to_string(), CellRole::Memory, 3, 50);
+        let legacy_id = population.insert_info(legacy);
+
+        assert!(population.set_activity("cell_7_0", 0.4));
+        assert_eq!(population.activity_of(id), Some(0.4));
+        assert_eq!(population.resolve("legacy_cell"), Some(legacy_id));
+        // Клетка с явным именем не находится по каноническому
+        assert!(population.get("cell_3_1").is_none());
+
+        let mut keys: Vec<String> = population.keys().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["cell_7_0".to_string(), "legacy_cell".to_string()]);
+        assert_eq!(population.values().filter(|c| c.role == CellRole::Memory).count(), 1);
+    }
+
+    #[test]
+    fn test_from_infos_keeps_canonical_slots() {
+        let mut population = CellPopulation::new();
+        for generation in 1..=5 {
+            population.insert(CellRole::Logic, generation, 0);
+        }
+        population.remove(CellId(1));
+        population.remove(CellId(3));
+
+        let restored = CellPopulation::from_infos(population.values().collect());
+        assert!(restored.aliases.is_empty());
+        assert_eq!(restored.get("cell_5_4").unwrap().generation, 5);
+        assert_eq!(restored.resolve("cell_3_2"), Some(CellId(2)));
+
+        // Освободившиеся слоты снова доступны
+        let mut restored = restored;
+        let id = restored.insert(CellRole::Motor, 6, 0);
+        assert!(id == CellId(1) || id == CellId(3));
+    }
+
+    #[test]
+    fn test_parallel_update_matches_sequential() {
+        let cells = population::PARALLEL_MIN_CELLS + 17;
+        let mut sequential = CellPopulation::with_capacity(cells);
+        for i in 0..cells {
+            let role = CellRole::ALL[i % CellRole::ALL.len()];
+            let id = sequential.insert(role, i as u32, 0);
+            sequential.set_activity_of(id, (i % 100) as f64 / 100.0);
+        }
+        let mut parallel = sequential.clone();
+
+        let update = |index: usize, role: CellRole, _driven: bool, activity: f64| {
+            activity * role.activity_decay() + index as f64 * 1e-9
+        };
+        sequential.update_activity(1, update);
+        parallel.update_activity(4, update);
+
+        assert_eq!(sequential.activities(), parallel.activities());
+    }
+}
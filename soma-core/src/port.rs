@@ -0,0 +1,259 @@
+//! Многопортовые клетки - именованные типизированные входы и выходы
+//!
+//! [`Cell`] умеет только `sense(f64)` и `flow() -> f64`: клетка не
+//! различает источники и не может выдать больше одного результата.
+//! [`PortCell`] объявляет именованные порты, а [`ScalarCellAdapter`]
+//! позволяет подключать существующие однопортовые клетки к графу
+//! связей ([`WiringGraph`](crate::WiringGraph)).
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cell::Cell;
+
+/// Имя входного порта адаптера однопортовой клетки
+pub const SCALAR_INPUT: &str = "in";
+
+/// Имя выходного порта адаптера однопортовой клетки
+pub const SCALAR_OUTPUT: &str = "out";
+
+/// Тип значения, передаваемого через порт
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PortType {
+    /// Скалярный сигнал
+    Scalar,
+    /// Вектор сигналов
+    Vector,
+    /// Импульс (спайк)
+    Spike,
+}
+
+impl fmt::Display for PortType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortType::Scalar => write!(f, "scalar"),
+            PortType::Vector => write!(f, "vector"),
+            PortType::Spike => write!(f, "spike"),
+        }
+    }
+}
+
+/// Значение порта
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PortValue {
+    /// Скалярный сигнал
+    Scalar(f64),
+    /// Вектор сигналов
+    Vector(Vec<f64>),
+    /// Импульс (true - спайк произошёл)
+    Spike(bool),
+}
+
+impl PortValue {
+    /// Тип значения
+    pub fn port_type(&self) -> PortType {
+        match self {
+            PortValue::Scalar(_) => PortType::Scalar,
+            PortValue::Vector(_) => PortType::Vector,
+            PortValue::Spike(_) => PortType::Spike,
+        }
+    }
+
+    /// Значение как скаляр
+    ///
+    /// Вектор сводится к среднему, спайк - к 1.0 или 0.0
+    pub fn as_scalar(&self) -> f64 {
+        match self {
+            PortValue::Scalar(value) => *value,
+            PortValue::Vector(values) if values.is_empty() => 0.0,
+            PortValue::Vector(values) => values.iter().sum::<f64>() / values.len() as f64,
+            PortValue::Spike(fired) => {
+                if *fired {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// Описание порта клетки
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortSpec {
+    /// Имя порта (уникально среди входов или выходов клетки)
+    pub name: String,
+    /// Тип значений порта
+    pub port_type: PortType,
+}
+
+impl PortSpec {
+    /// Создать описание порта
+    pub fn new(name: impl Into<String>, port_type: PortType) -> Self {
+        Self {
+            name: name.into(),
+            port_type,
+        }
+    }
+
+    /// Скалярный порт
+    pub fn scalar(name: impl Into<String>) -> Self {
+        Self::new(name, PortType::Scalar)
+    }
+}
+
+/// Клетка с именованными входными и выходными портами
+///
+/// Цикл тот же, что у [`Cell`]: значения подаются на входы через
+/// `sense_port`, `align` обрабатывает их, выходы читаются `flow_port`
+pub trait PortCell {
+    /// Входные порты клетки
+    fn inputs(&self) -> &[PortSpec];
+
+    /// Выходные порты клетки
+    fn outputs(&self) -> &[PortSpec];
+
+    /// Воспринять значение на входном порту
+    fn sense_port(&mut self, port: &str, value: PortValue);
+
+    /// Выровнять внутреннее состояние
+    fn align(&mut self);
+
+    /// Значение выходного порта (None - порт не существует)
+    fn flow_port(&self, port: &str) -> Option<PortValue>;
+
+    /// Найти входной порт по имени
+    fn input(&self, name: &str) -> Option<&PortSpec> {
+        self.inputs().iter().find(|p| p.name == name)
+    }
+
+    /// Найти выходной порт по имени
+    fn output(&self, name: &str) -> Option<&PortSpec> {
+        self.outputs().iter().find(|p| p.name == name)
+    }
+}
+
+/// Адаптер однопортовой клетки: скалярный вход `in`, скалярный выход `out`
+pub struct ScalarCellAdapter<C> {
+    cell: C,
+    inputs: [PortSpec; 1],
+    outputs: [PortSpec; 1],
+}
+
+impl<C: Cell> ScalarCellAdapter<C> {
+    /// Обернуть однопортовую клетку
+    pub fn new(cell: C) -> Self {
+        Self {
+            cell,
+            inputs: [PortSpec::scalar(SCALAR_INPUT)],
+            outputs: [PortSpec::scalar(SCALAR_OUTPUT)],
+        }
+    }
+
+    /// Обёрнутая клетка
+    pub fn inner(&self) -> &C {
+        &self.cell
+    }
+
+    /// Извлечь обёрнутую клетку
+    pub fn into_inner(self) -> C {
+        self.cell
+    }
+}
+
+impl<C: Cell> PortCell for ScalarCellAdapter<C> {
+    fn inputs(&self) -> &[PortSpec] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[PortSpec] {
+        &self.outputs
+    }
+
+    fn sense_port(&mut self, port: &str, value: PortValue) {
+        if port == SCALAR_INPUT {
+            self.cell.sense(value.as_scalar());
+        }
+    }
+
+    fn align(&mut self) {
+        self.cell.align();
+    }
+
+    fn flow_port(&self, port: &str) -> Option<PortValue> {
+        (port == SCALAR_OUTPUT).then(|| PortValue::Scalar(self.cell.flow()))
+    }
+}
+
+impl<C: PortCell + ?Sized> PortCell for Box<C> {
+    fn inputs(&self) -> &[PortSpec] {
+        (**self).inputs()
+    }
+
+    fn outputs(&self) -> &[PortSpec] {
+        (**self).outputs()
+    }
+
+    fn sense_port(&mut self, port: &str, value: PortValue) {
+        (**self).sense_port(port, value);
+    }
+
+    fn align(&mut self) {
+        (**self).align();
+    }
+
+    fn flow_port(&self, port: &str) -> Option<PortValue> {
+        (**self).flow_port(port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::StemCell;
+
+    struct Doubler(f64);
+
+    impl Cell for Doubler {
+        fn sense(&mut self, input: f64) {
+            self.0 = input;
+        }
+
+        fn align(&mut self) {
+            self.0 *= 2.0;
+        }
+
+        fn flow(&self) -> f64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_scalar_adapter_exposes_single_ports() {
+        let mut cell = ScalarCellAdapter::new(Doubler(0.0));
+        assert_eq!(cell.inputs()[0].name, SCALAR_INPUT);
+        assert_eq!(cell.output(SCALAR_OUTPUT).unwrap().port_type, PortType::Scalar);
+
+        cell.sense_port(SCALAR_INPUT, PortValue::Scalar(0.25));
+        cell.align();
+        assert_eq!(cell.flow_port(SCALAR_OUTPUT), Some(PortValue::Scalar(0.5)));
+        assert_eq!(cell.flow_port("missing"), None);
+    }
+
+    #[test]
+    fn test_port_value_as_scalar() {
+        assert_eq!(PortValue::Vector(vec![0.25, 0.75]).as_scalar(), 0.5);
+        assert_eq!(PortValue::Vector(Vec::new()).as_scalar(), 0.0);
+        assert_eq!(PortValue::Spike(true).as_scalar(), 1.0);
+        assert_eq!(PortValue::Scalar(0.7).port_type(), PortType::Scalar);
+    }
+
+    #[test]
+    fn test_adapter_wraps_differentiated_cell() {
+        let cell = StemCell::with_resonance(0.2).differentiate(Doubler(0.0));
+        let adapter = ScalarCellAdapter::new(cell);
+        assert_eq!(adapter.flow_port(SCALAR_OUTPUT).unwrap().as_scalar(), 0.4);
+        assert_eq!(adapter.into_inner().0, 0.4);
+    }
+}
@@ -0,0 +1,425 @@
+//! Стратегии выбора роли при делении стволового процессора
+//!
+//! StemProcessor делегирует выбор роли новой клетки реализации трейта
+//! [`RoleSelector`], что позволяет настраивать состав организма
+//! без изменения ядра.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::stem::CellRole;
+
+/// Базовые роли, между которыми по умолчанию распределяются клетки
+pub const BASE_ROLES: [CellRole; 3] = [CellRole::Sensor, CellRole::Logic, CellRole::Motor];
+
+/// Состояние организма, доступное стратегии в момент деления
+#[derive(Debug, Clone, Copy)]
+pub struct SelectionContext<'a> {
+    /// Текущее распределение клеток по ролям
+    pub distribution: &'a HashMap<CellRole, usize>,
+    /// Сигналы спроса на роли (чем выше - тем нужнее роль)
+    pub demand: &'a HashMap<CellRole, f64>,
+    /// Сглаженная нагрузка процессора
+    pub load: f64,
+    /// Текущее поколение процессора
+    pub generation: u32,
+    /// Концентрации морфогенов в точке рождения (пусто - организм без пространства)
+    pub morphogens: &'a HashMap<CellRole, f64>,
+}
+
+impl SelectionContext<'_> {
+    /// Количество клеток заданной роли
+    pub fn count(&self, role: CellRole) -> usize {
+        self.distribution.get(&role).copied().unwrap_or(0)
+    }
+
+    /// Общее количество клеток
+    pub fn total(&self) -> usize {
+        self.distribution.values().sum()
+    }
+
+    /// Спрос на заданную роль
+    pub fn demand(&self, role: CellRole) -> f64 {
+        self.demand.get(&role).copied().unwrap_or(0.0)
+    }
+
+    /// Локальная концентрация морфогена роли
+    pub fn morphogen(&self, role: CellRole) -> f64 {
+        self.morphogens.get(&role).copied().unwrap_or(0.0)
+    }
+}
+
+/// Стратегия выбора роли для новой клетки
+pub trait RoleSelector: fmt::Debug + Send {
+    /// Выбрать роль для клетки, рождающейся при делении
+    fn select(&mut self, ctx: &SelectionContext<'_>) -> CellRole;
+
+    /// Копия стратегии для дочернего процессора (почки)
+    ///
+    /// Случайные стратегии засевают копию из своего генератора, чтобы
+    /// почка не повторяла выбор родителя
+    fn fork(&mut self) -> Box<dyn RoleSelector>;
+}
+
+/// Сбалансированная стратегия - выбирает наименее представленную роль
+///
+/// При равенстве побеждает роль, стоящая раньше в списке
+#[derive(Debug, Clone)]
+pub struct BalancedSelector {
+    roles: Vec<CellRole>,
+}
+
+impl BalancedSelector {
+    /// Создать стратегию для базовых ролей
+    pub fn new() -> Self {
+        Self::with_roles(&BASE_ROLES)
+    }
+
+    /// Создать стратегию для заданного набора ролей
+    pub fn with_roles(roles: &[CellRole]) -> Self {
+        let roles = if roles.is_empty() { BASE_ROLES.to_vec() } else { roles.to_vec() };
+        Self { roles }
+    }
+}
+
+impl Default for BalancedSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RoleSelector for BalancedSelector {
+    fn select(&mut self, ctx: &SelectionContext<'_>) -> CellRole {
+        let mut best = self.roles[0];
+        for &role in &self.roles[1..] {
+            if ctx.count(role) < ctx.count(best) {
+                best = role;
+            }
+        }
+        best
+    }
+
+    fn fork(&mut self) -> Box<dyn RoleSelector> {
+        Box::new(self.clone())
+    }
+}
+
+/// Градиентная стратегия - клетки рождаются там, где выше давление спроса
+///
+/// Давление роли = спрос / (количество клеток роли + 1).
+/// Без сигналов спроса ведёт себя как [`BalancedSelector`].
+#[derive(Debug, Clone)]
+pub struct PressureGradientSelector {
+    fallback: BalancedSelector,
+    roles: Vec<CellRole>,
+}
+
+impl PressureGradientSelector {
+    /// Создать стратегию для базовых ролей
+    pub fn new() -> Self {
+        Self::with_roles(&BASE_ROLES)
+    }
+
+    /// Создать стратегию для заданного набора ролей
+    pub fn with_roles(roles: &[CellRole]) -> Self {
+        let fallback = BalancedSelector::with_roles(roles);
+        let roles = fallback.roles.clone();
+        Self { fallback, roles }
+    }
+
+    /// Давление спроса на роль
+    pub fn pressure(ctx: &SelectionContext<'_>, role: CellRole) -> f64 {
+        ctx.demand(role).max(0.0) / (ctx.count(role) + 1) as f64
+    }
+}
+
+impl Default for PressureGradientSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RoleSelector for PressureGradientSelector {
+    fn select(&mut self, ctx: &SelectionContext<'_>) -> CellRole {
+        let mut best = self.roles[0];
+        let mut best_pressure = Self::pressure(ctx, best);
+        for &role in &self.roles[1..] {
+            let pressure = Self::pressure(ctx, role);
+            if pressure > best_pressure {
+                best = role;
+                best_pressure = pressure;
+            }
+        }
+
+        if best_pressure > 0.0 {
+            best
+        } else {
+            self.fallback.select(ctx)
+        }
+    }
+
+    fn fork(&mut self) -> Box<dyn RoleSelector> {
+        Box::new(self.clone())
+    }
+}
+
+/// Взвешенная случайная стратегия с воспроизводимым генератором
+#[derive(Debug, Clone)]
+pub struct WeightedRandomSelector {
+    weights: Vec<(CellRole, f64)>,
+    rng: StdRng,
+}
+
+impl WeightedRandomSelector {
+    /// Создать стратегию с весами ролей и зерном генератора
+    ///
+    /// Отрицательные веса считаются нулевыми
+    pub fn new(weights: &[(CellRole, f64)], seed: u64) -> Self {
+        let weights = weights
+            .iter()
+            .map(|&(role, weight)| (role, weight.max(0.0)))
+            .collect();
+        Self {
+            weights,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl RoleSelector for WeightedRandomSelector {
+    fn select(&mut self, ctx: &SelectionContext<'_>) -> CellRole {
+        let total: f64 = self.weights.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 {
+            return BalancedSelector::new().select(ctx);
+        }
+
+        let mut roll = self.rng.gen_range(0.0..total);
+        for &(role, weight) in &self.weights {
+            if roll < weight {
+                return role;
+            }
+            roll -= weight;
+        }
+        // Защита от ошибок округления
+        self.weights[self.weights.len() - 1].0
+    }
+
+    fn fork(&mut self) -> Box<dyn RoleSelector> {
+        Box::new(Self {
+            weights: self.weights.clone(),
+            rng: StdRng::seed_from_u64(self.rng.gen()),
+        })
+    }
+}
+
+/// Стратегия фиксированных пропорций - поддерживает заданное соотношение ролей
+///
+/// Выбирает роль с наибольшим дефицитом относительно целевой доли
+#[derive(Debug, Clone)]
+pub struct FixedRatioSelector {
+    ratios: Vec<(CellRole, f64)>,
+}
+
+impl FixedRatioSelector {
+    /// Создать стратегию с целевыми пропорциями (нормализуются автоматически)
+    pub fn new(ratios: &[(CellRole, f64)]) -> Self {
+        let total: f64 = ratios.iter().map(|(_, r)| r.max(0.0)).sum();
+        let ratios = if total > 0.0 {
+            ratios
+                .iter()
+                .map(|&(role, ratio)| (role, ratio.max(0.0) / total))
+                .collect()
+        } else {
+            BASE_ROLES.iter().map(|&role| (role, 1.0 / BASE_ROLES.len() as f64)).collect()
+        };
+        Self { ratios }
+    }
+
+    /// Целевая доля роли
+    pub fn ratio(&self, role: CellRole) -> f64 {
+        self.ratios
+            .iter()
+            .find(|(r, _)| *r == role)
+            .map_or(0.0, |(_, ratio)| *ratio)
+    }
+}
+
+impl RoleSelector for FixedRatioSelector {
+    fn select(&mut self, ctx: &SelectionContext<'_>) -> CellRole {
+        let next_total = (ctx.total() + 1) as f64;
+        let deficit = |&(role, ratio): &(CellRole, f64)| ratio * next_total - ctx.count(role) as f64;
+
+        let mut best = self.ratios[0];
+        for entry in &self.ratios[1..] {
+            if deficit(entry) > deficit(&best) {
+                best = *entry;
+            }
+        }
+        best.0
+    }
+
+    fn fork(&mut self) -> Box<dyn RoleSelector> {
+        Box::new(self.clone())
+    }
+}
+
+/// Морфогенная стратегия - латеральное ингибирование
+///
+/// Клетка принимает роль с наименьшей локальной концентрацией морфогена:
+/// соседи одной роли подавляют её появление рядом, и ткань складывается
+/// в чередующийся узор. Без морфогенов ведёт себя как [`BalancedSelector`].
+#[derive(Debug, Clone)]
+pub struct MorphogenSelector {
+    fallback: BalancedSelector,
+    roles: Vec<CellRole>,
+}
+
+impl MorphogenSelector {
+    /// Создать стратегию для базовых ролей
+    pub fn new() -> Self {
+        Self::with_roles(&BASE_ROLES)
+    }
+
+    /// Создать стратегию для заданного набора ролей
+    pub fn with_roles(roles: &[CellRole]) -> Self {
+        let fallback = BalancedSelector::with_roles(roles);
+        let roles = fallback.roles.clone();
+        Self { fallback, roles }
+    }
+}
+
+impl Default for MorphogenSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RoleSelector for MorphogenSelector {
+    fn select(&mut self, ctx: &SelectionContext<'_>) -> CellRole {
+        if ctx.morphogens.is_empty() {
+            return self.fallback.select(ctx);
+        }
+
+        let mut best = self.roles[0];
+        for &role in &self.roles[1..] {
+            if ctx.morphogen(role) < ctx.morphogen(best) {
+                best = role;
+            }
+        }
+        best
+    }
+
+    fn fork(&mut self) -> Box<dyn RoleSelector> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::LazyLock;
+
+    static NO_MORPHOGENS: LazyLock<HashMap<CellRole, f64>> = LazyLock::new(HashMap::new);
+
+    fn context<'a>(
+        distribution: &'a HashMap<CellRole, usize>,
+        demand: &'a HashMap<CellRole, f64>,
+    ) -> SelectionContext<'a> {
+        SelectionContext {
+            distribution,
+            demand,
+            load: 0.5,
+            generation: 0,
+            morphogens: &NO_MORPHOGENS,
+        }
+    }
+
+    #[test]
+    fn test_balanced_selector() {
+        let distribution = HashMap::from([(CellRole::Sensor, 2), (CellRole::Logic, 1)]);
+        let demand = HashMap::new();
+
+        let mut selector = BalancedSelector::new();
+        assert_eq!(selector.select(&context(&distribution, &demand)), CellRole::Motor);
+    }
+
+    #[test]
+    fn test_pressure_gradient_selector() {
+        let distribution = HashMap::from([(CellRole::Sensor, 3)]);
+        let demand = HashMap::from([(CellRole::Sensor, 1.0), (CellRole::Motor, 0.5)]);
+
+        let mut selector = PressureGradientSelector::new();
+        // Sensor: 1.0 / 4 = 0.25, Motor: 0.5 / 1 = 0.5
+        assert_eq!(selector.select(&context(&distribution, &demand)), CellRole::Motor);
+
+        // Без спроса - сбалансированный выбор
+        let empty = HashMap::new();
+        assert_eq!(selector.select(&context(&distribution, &empty)), CellRole::Logic);
+    }
+
+    #[test]
+    fn test_weighted_random_is_reproducible() {
+        let distribution = HashMap::new();
+        let demand = HashMap::new();
+        let weights = [(CellRole::Sensor, 1.0), (CellRole::Motor, 3.0)];
+
+        let mut a = WeightedRandomSelector::new(&weights, 42);
+        let mut b = WeightedRandomSelector::new(&weights, 42);
+        let picks_a: Vec<_> = (0..20).map(|_| a.select(&context(&distribution, &demand))).collect();
+        let picks_b: Vec<_> = (0..20).map(|_| b.select(&context(&distribution, &demand))).collect();
+
+        assert_eq!(picks_a, picks_b);
+        assert!(picks_a.iter().all(|r| *r != CellRole::Logic));
+    }
+
+    #[test]
+    fn test_weighted_random_fork_is_reseeded() {
+        let distribution = HashMap::new();
+        let demand = HashMap::new();
+        let weights = [(CellRole::Sensor, 1.0), (CellRole::Motor, 1.0)];
+
+        let mut parent = WeightedRandomSelector::new(&weights, 42);
+        let mut fork = parent.fork();
+        let picks_parent: Vec<_> = (0..32).map(|_| parent.select(&context(&distribution, &demand))).collect();
+        let picks_fork: Vec<_> = (0..32).map(|_| fork.select(&context(&distribution, &demand))).collect();
+
+        // Почка не повторяет последовательность выбора родителя
+        assert_ne!(picks_parent, picks_fork);
+    }
+
+    #[test]
+    fn test_fixed_ratio_selector() {
+        let mut selector = FixedRatioSelector::new(&[(CellRole::Sensor, 3.0), (CellRole::Motor, 1.0)]);
+        let demand = HashMap::new();
+        let mut distribution = HashMap::new();
+
+        for _ in 0..8 {
+            let role = selector.select(&context(&distribution, &demand));
+            *distribution.entry(role).or_insert(0) += 1;
+        }
+
+        assert_eq!(distribution[&CellRole::Sensor], 6);
+        assert_eq!(distribution[&CellRole::Motor], 2);
+        assert_eq!(selector.ratio(CellRole::Sensor), 0.75);
+    }
+
+    #[test]
+    fn test_morphogen_selector_inhibits_local_roles() {
+        let distribution = HashMap::from([(CellRole::Logic, 1)]);
+        let demand = HashMap::new();
+        let morphogens = HashMap::from([(CellRole::Sensor, 0.8), (CellRole::Logic, 0.1), (CellRole::Motor, 0.3)]);
+
+        let mut selector = MorphogenSelector::new();
+        let ctx = SelectionContext {
+            morphogens: &morphogens,
+            ..context(&distribution, &demand)
+        };
+        assert_eq!(selector.select(&ctx), CellRole::Logic);
+
+        // Без морфогенов - сбалансированный выбор
+        assert_eq!(selector.select(&context(&distribution, &demand)), CellRole::Sensor);
+    }
+}
@@ -0,0 +1,447 @@
+//! Снапшоты организма - сохранение и восстановление StemProcessor
+//!
+//! Снапшот содержит всё состояние организма (клетки, поколение, нагрузку,
+//! параметры и статистику ролей) и позволяет узлу пережить перезапуск.
+//! Формат версионируется: [`SNAPSHOT_SCHEMA_VERSION`] растёт при изменении
+//! схемы, а старые снапшоты приводятся к текущей схеме через
+//! [`SnapshotMigrator`]. Снапшоты другой мажорной версии SOMA не загружаются.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::budding::BuddingPolicy;
+use crate::energy::EnergyBudget;
+use crate::lifecycle::{LifecyclePolicy, LifecycleStats};
+use crate::lineage::Lineage;
+use crate::load::LoadModel;
+use crate::morphogen::MorphogenField;
+use crate::stem::{CellInfo, CellRole};
+use crate::tissue::Anatomy;
+use crate::transdifferentiation::TransdifferentiationPolicy;
+use crate::SOMA_VERSION;
+
+/// Текущая версия схемы снапшота
+///
+/// Версия 0 - снапшоты без поля `schema_version` (до введения версионирования),
+/// версия 2 - клетки с координатами и тканями, почки, энергия и родословная
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 2;
+
+/// Ошибки сохранения и восстановления снапшота
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotError {
+    /// Ошибка чтения или записи файла
+    Io(String),
+    /// Ошибка сериализации или разбора
+    Serialization(String),
+    /// Снапшот создан несовместимой версией SOMA
+    IncompatibleVersion { found: String, supported: String },
+    /// Нет миграции со старой версии схемы
+    MissingMigration(u32),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(msg) => write!(f, "IO error: {}", msg),
+            SnapshotError::Serialization(msg) => write!(f, "Serialization error: {}", msg),
+            SnapshotError::IncompatibleVersion { found, supported } => write!(
+                f,
+                "Incompatible SOMA version: snapshot {}, supported {}",
+                found, supported
+            ),
+            SnapshotError::MissingMigration(version) => {
+                write!(f, "No migration from snapshot schema v{}", version)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(err: std::io::Error) -> Self {
+        SnapshotError::Io(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for SnapshotError {
+    fn from(err: serde_json::Error) -> Self {
+        SnapshotError::Serialization(err.to_string())
+    }
+}
+
+/// Снимок состояния организма
+///
+/// Неизвестные поля игнорируются, отсутствующие необязательные поля
+/// заполняются значениями по умолчанию - так снапшот более новой схемы
+/// той же мажорной версии SOMA остаётся загружаемым.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrganismSnapshot {
+    /// Версия схемы снапшота
+    pub schema_version: u32,
+    /// Версия SOMA, создавшая снапшот
+    pub soma_version: String,
+    /// Момент создания снапшота (timestamp в миллисекундах)
+    #[serde(default)]
+    pub taken_at: u64,
+    /// Идентификатор стволового процессора
+    pub id: String,
+    /// Поколение (счётчик делений)
+    pub generation: u32,
+    /// Клетки организма (упорядочены по идентификатору)
+    pub cells: Vec<CellInfo>,
+    /// Сглаженная нагрузка
+    pub load: f64,
+    /// Порог нагрузки для деления
+    pub threshold: f64,
+    /// Коэффициент сглаживания нагрузки
+    pub smoothing: f64,
+    /// Статистика по ролям
+    pub role_stats: HashMap<CellRole, usize>,
+    /// Политика жизненного цикла
+    #[serde(default)]
+    pub lifecycle: Option<LifecyclePolicy>,
+    /// Доступные стволовые клетки для регенерации
+    #[serde(default)]
+    pub stem_pool: usize,
+    /// Статистика апоптоза и регенерации
+    #[serde(default)]
+    pub lifecycle_stats: LifecycleStats,
+    /// Родословная клеток
+    #[serde(default)]
+    pub lineage: Lineage,
+    /// Векторная модель нагрузки
+    #[serde(default)]
+    pub load_model: Option<LoadModel>,
+    /// Энергетический бюджет
+    #[serde(default)]
+    pub energy: Option<EnergyBudget>,
+    /// Поле морфогенов
+    #[serde(default)]
+    pub morphogen: Option<MorphogenField>,
+    /// Ткани и органы
+    #[serde(default)]
+    pub anatomy: Anatomy,
+    /// Политика почкования
+    #[serde(default)]
+    pub budding: Option<BuddingPolicy>,
+    /// Снапшоты почек
+    #[serde(default)]
+    pub children: Vec<OrganismSnapshot>,
+    /// Политика трансдифференциации
+    #[serde(default)]
+    pub transdifferentiation: Option<TransdifferentiationPolicy>,
+}
+
+impl OrganismSnapshot {
+    /// Сериализовать снапшот в JSON
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Разобрать снапшот из JSON с миграциями по умолчанию
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        Self::from_json_with(json, &SnapshotMigrator::default())
+    }
+
+    /// Разобрать снапшот из JSON с заданным набором миграций
+    pub fn from_json_with(json: &str, migrator: &SnapshotMigrator) -> Result<Self, SnapshotError> {
+        let value: Value = serde_json::from_str(json)?;
+        let value = migrator.migrate(value)?;
+        let snapshot: OrganismSnapshot = serde_json::from_value(value)?;
+
+        if !is_compatible(&snapshot.soma_version) {
+            return Err(SnapshotError::IncompatibleVersion {
+                found: snapshot.soma_version,
+                supported: SOMA_VERSION.to_string(),
+            });
+        }
+        Ok(snapshot)
+    }
+
+    /// Сохранить снапшот в файл
+    ///
+    /// Запись атомарна: данные пишутся во временный файл и переименовываются
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, self.to_json()?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Загрузить снапшот из файла с миграциями по умолчанию
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        Self::load_with(path, &SnapshotMigrator::default())
+    }
+
+    /// Загрузить снапшот из файла с заданным набором миграций
+    pub fn load_with(path: impl AsRef<Path>, migrator: &SnapshotMigrator) -> Result<Self, SnapshotError> {
+        let json = fs::read_to_string(path)?;
+        Self::from_json_with(&json, migrator)
+    }
+}
+
+/// Совпадает ли мажорная версия SOMA снапшота с текущей
+fn is_compatible(soma_version: &str) -> bool {
+    let major = |v: &str| v.split('.').next().unwrap_or("").to_string();
+    major(soma_version) == major(SOMA_VERSION)
+}
+
+/// Шаг миграции: приводит JSON снапшота версии N к версии N + 1
+pub type MigrationFn = Box<dyn Fn(&mut Value) -> Result<(), SnapshotError> + Send + Sync>;
+
+/// Реестр миграций снапшотов между версиями схемы
+///
+/// По умолчанию содержит миграции v0 -> v1 -> v2. Дополнительные шаги
+/// регистрируются через [`SnapshotMigrator::register`].
+pub struct SnapshotMigrator {
+    steps: HashMap<u32, MigrationFn>,
+}
+
+impl SnapshotMigrator {
+    /// Создать реестр без миграций
+    pub fn empty() -> Self {
+        Self {
+            steps: HashMap::new(),
+        }
+    }
+
+    /// Зарегистрировать миграцию с версии `from` на `from + 1`
+    pub fn register<F>(&mut self, from: u32, step: F)
+    where
+        F: Fn(&mut Value) -> Result<(), SnapshotError> + Send + Sync + 'static,
+    {
+        self.steps.insert(from, Box::new(step));
+    }
+
+    /// Привести JSON снапшота к текущей версии схемы
+    ///
+    /// Снапшоты почек мигрируют вместе с родителем. Снапшоты более новой
+    /// схемы возвращаются без изменений
+    pub fn migrate(&self, mut value: Value) -> Result<Value, SnapshotError> {
+        let mut version = schema_version_of(&value);
+        while version < SNAPSHOT_SCHEMA_VERSION {
+            let step = self
+                .steps
+                .get(&version)
+                .ok_or(SnapshotError::MissingMigration(version))?;
+            step(&mut value)?;
+            version += 1;
+            set_field(&mut value, "schema_version", Value::from(version));
+        }
+
+        if let Some(children) = value.get_mut("children").and_then(Value::as_array_mut) {
+            for child in children {
+                *child = self.migrate(child.take())?;
+            }
+        }
+        Ok(value)
+    }
+}
+
+impl Default for SnapshotMigrator {
+    fn default() -> Self {
+        let mut migrator = Self::empty();
+        migrator.register(0, migrate_v0_to_v1);
+        migrator.register(1, migrate_v1_to_v2);
+        migrator
+    }
+}
+
+impl fmt::Debug for SnapshotMigrator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut versions: Vec<_> = self.steps.keys().collect();
+        versions.sort();
+        f.debug_struct("SnapshotMigrator")
+            .field("steps", &versions)
+            .finish()
+    }
+}
+
+/// Версия схемы JSON снапшота (отсутствие поля - версия 0)
+fn schema_version_of(value: &Value) -> u32 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map_or(0, |v| v as u32)
+}
+
+fn set_field(value: &mut Value, key: &str, field: Value) {
+    if let Some(object) = value.as_object_mut() {
+        object.insert(key.to_string(), field);
+    }
+}
+
+/// v0 -> v1: добавлены версия SOMA и статистика ролей
+///
+/// Статистика ролей восстанавливается по списку клеток
+fn migrate_v0_to_v1(value: &mut Value) -> Result<(), SnapshotError> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| SnapshotError::Serialization("snapshot is not an object".to_string()))?;
+
+    object
+        .entry("soma_version")
+        .or_insert_with(|| Value::from(SOMA_VERSION));
+
+    if !object.contains_key("role_stats") {
+        let mut stats: HashMap<String, usize> = HashMap::new();
+        if let Some(cells) = object.get("cells").and_then(Value::as_array) {
+            for role in cells.iter().filter_map(|c| c.get("role").and_then(Value::as_str)) {
+                *stats.entry(role.to_string()).or_insert(0) += 1;
+            }
+        }
+        object.insert("role_stats".to_string(), serde_json::to_value(stats)?);
+    }
+    Ok(())
+}
+
+/// v1 -> v2: клетки получили токсичность, счётчик бездействия,
+/// координаты и ткань
+///
+/// Отсутствующие поля клеток заполняются значениями по умолчанию
+fn migrate_v1_to_v2(value: &mut Value) -> Result<(), SnapshotError> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| SnapshotError::Serialization("snapshot is not an object".to_string()))?;
+
+    let cells = object.get_mut("cells").and_then(Value::as_array_mut);
+    for cell in cells.into_iter().flatten().filter_map(Value::as_object_mut) {
+        cell.entry("toxicity").or_insert_with(|| Value::from(0.0));
+        cell.entry("idle_ticks").or_insert_with(|| Value::from(0));
+        cell.entry("position").or_insert(Value::Null);
+        cell.entry("tissue").or_insert(Value::Null);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::stem::StemProcessor;
+
+    fn organism() -> StemProcessor {
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_clock(ManualClock::new(5_000).shared())
+            .with_lifecycle(LifecyclePolicy::new().with_target_population(4));
+        for _ in 0..6 {
+            stem.sense(0.8);
+        }
+        stem
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let stem = organism();
+        let snapshot = stem.snapshot();
+        assert_eq!(snapshot.schema_version, SNAPSHOT_SCHEMA_VERSION);
+        assert_eq!(snapshot.taken_at, 5_000);
+
+        let json = snapshot.to_json().unwrap();
+        let restored = StemProcessor::from_snapshot(OrganismSnapshot::from_json(&json).unwrap());
+
+        assert_eq!(restored.id, stem.id);
+        assert_eq!(restored.generation, stem.generation);
+        assert_eq!(restored.cell_count(), stem.cell_count());
+        assert_eq!(restored.load, stem.load);
+        assert_eq!(restored.role_distribution(), stem.role_distribution());
+        assert_eq!(restored.lifecycle(), stem.lifecycle());
+        assert_eq!(restored.snapshot().cells, snapshot.cells);
+        assert_eq!(restored.lineage(), stem.lineage());
+    }
+
+    #[test]
+    fn test_save_and_load_file() {
+        let stem = organism();
+        let path = std::env::temp_dir().join(format!("soma_snapshot_{}.json", std::process::id()));
+
+        stem.save_snapshot(&path).unwrap();
+        let restored = StemProcessor::load_snapshot(&path).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(restored.generation, stem.generation);
+        assert_eq!(restored.cells().len(), stem.cells().len());
+    }
+
+    #[test]
+    fn test_migrates_legacy_snapshot() {
+        let legacy = r#"{
+            "id": "stem_1",
+            "generation": 2,
+            "cells": [
+                {"id": "cell_1_1", "role": "Sensor", "birth_time": 1, "generation": 1, "activity": 0.5},
+                {"id": "cell_2_2", "role": "Sensor", "birth_time": 2, "generation": 2, "activity": 0.1}
+            ],
+            "load": 0.4,
+            "threshold": 0.7,
+            "smoothing": 0.9,
+            "unknown_future_field": true
+        }"#;
+
+        let snapshot = OrganismSnapshot::from_json(legacy).unwrap();
+        assert_eq!(snapshot.schema_version, SNAPSHOT_SCHEMA_VERSION);
+        assert_eq!(snapshot.role_stats[&CellRole::Sensor], 2);
+        assert_eq!(snapshot.cells[0].toxicity, 0.0);
+        assert_eq!(StemProcessor::from_snapshot(snapshot).lineage().roots().len(), 2);
+
+        let err = OrganismSnapshot::from_json_with(legacy, &SnapshotMigrator::empty()).unwrap_err();
+        assert_eq!(err, SnapshotError::MissingMigration(0));
+    }
+
+    #[test]
+    fn test_migrates_v1_snapshot_with_buds() {
+        let cell = r#"{"id": "cell_1_0", "role": "Logic", "birth_time": 1, "generation": 1, "activity": 0.5}"#;
+        let v1 = format!(
+            r#"{{
+                "schema_version": 1,
+                "soma_version": "{version}",
+                "id": "stem_1",
+                "generation": 1,
+                "cells": [{cell}],
+                "load": 0.4,
+                "threshold": 0.7,
+                "smoothing": 0.9,
+                "role_stats": {{"Logic": 1}},
+                "children": [{{
+                    "schema_version": 1,
+                    "soma_version": "{version}",
+                    "id": "stem_1.1",
+                    "generation": 1,
+                    "cells": [{cell}],
+                    "load": 0.2,
+                    "threshold": 0.7,
+                    "smoothing": 0.9,
+                    "role_stats": {{"Logic": 1}}
+                }}]
+            }}"#,
+            version = SOMA_VERSION,
+        );
+
+        let snapshot = OrganismSnapshot::from_json(&v1).unwrap();
+        assert_eq!(snapshot.schema_version, 2);
+        assert_eq!(snapshot.children[0].schema_version, 2);
+        assert_eq!(snapshot.children[0].cells[0].position, None);
+        assert_eq!(StemProcessor::from_snapshot(snapshot).total_cell_count(), 2);
+
+        let mut migrator = SnapshotMigrator::empty();
+        migrator.register(0, migrate_v0_to_v1);
+        let err = OrganismSnapshot::from_json_with(&v1, &migrator).unwrap_err();
+        assert_eq!(err, SnapshotError::MissingMigration(1));
+    }
+
+    #[test]
+    fn test_rejects_incompatible_major_version() {
+        let mut snapshot = organism().snapshot();
+        snapshot.soma_version = "99.0.0".to_string();
+
+        let err = OrganismSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap_err();
+        assert!(matches!(err, SnapshotError::IncompatibleVersion { .. }));
+    }
+}
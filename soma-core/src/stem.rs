@@ -1,10 +1,31 @@
 use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::fmt;
+use std::path::Path;
+use std::sync::mpsc;
 
-use crate::config::{stem, cell};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::budding::BuddingPolicy;
+use crate::clock::{system_clock, Clock, SharedClock, SystemClock};
+use crate::config::{stem, cell, energy, events, lifecycle, morphogen, role};
+use crate::energy::EnergyBudget;
+use crate::events::{EventBus, StemEvent, SubscriptionId};
+use crate::factory::{CellRegistry, LiveCell};
+use crate::lifecycle::{ApoptosisCause, LifecyclePolicy, LifecycleStats};
+use crate::lineage::Lineage;
+use crate::load::{LoadDimension, LoadModel, LoadVector};
+use crate::morphogen::{MorphogenField, Position};
+use crate::population::{CellId, CellPopulation};
+use crate::selector::{BalancedSelector, RoleSelector, SelectionContext};
+use crate::snapshot::{OrganismSnapshot, SnapshotError, SNAPSHOT_SCHEMA_VERSION};
+use crate::tissue::{Anatomy, GroupMetrics, OrganId, TissueId};
+use crate::transdifferentiation::{InputSource, SignalHistory, TransdifferentiationPolicy};
+use crate::SOMA_VERSION;
 
 /// Роли клеток - разные специализации процессоров
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CellRole {
     /// Сенсорная клетка - обработка входных данных
     Sensor,
@@ -12,21 +33,61 @@ pub enum CellRole {
     Logic,
     /// Моторная клетка - управление и выход
     Motor,
+    /// Клетка памяти - удерживает активность дольше остальных
+    Memory,
+    /// Хаб - координатор связей, усиливает активность соседей
+    Hub,
+    /// Ингибитор - подавляет избыточную активность соседей
+    Inhibitor,
+    /// Исследователь - вносит шум для разведки новых состояний
+    Explorer,
 }
 
 impl CellRole {
+    /// Все роли клеток
+    pub const ALL: [CellRole; 7] = [
+        CellRole::Sensor,
+        CellRole::Logic,
+        CellRole::Motor,
+        CellRole::Memory,
+        CellRole::Hub,
+        CellRole::Inhibitor,
+        CellRole::Explorer,
+    ];
+
     /// Получить описание роли
     pub fn description(&self) -> &str {
         match self {
             CellRole::Sensor => "Sensor - processes input data",
             CellRole::Logic => "Logic - performs computations",
             CellRole::Motor => "Motor - controls output and actions",
+            CellRole::Memory => "Memory - retains activity over time",
+            CellRole::Hub => "Hub - coordinates and amplifies neighbours",
+            CellRole::Inhibitor => "Inhibitor - damps excessive neighbour activity",
+            CellRole::Explorer => "Explorer - injects noise to explore new states",
+        }
+    }
+
+    /// Коэффициент затухания активности для роли
+    pub fn activity_decay(&self) -> f64 {
+        match self {
+            CellRole::Memory => role::MEMORY_ACTIVITY_DECAY,
+            _ => cell::ACTIVITY_DECAY,
+        }
+    }
+
+    /// Стоимость содержания клетки роли за тик по умолчанию
+    pub fn metabolic_cost(&self) -> f64 {
+        match self {
+            CellRole::Memory => energy::MEMORY_METABOLIC_COST,
+            CellRole::Hub => energy::HUB_METABOLIC_COST,
+            _ => energy::BASE_METABOLIC_COST,
         }
     }
 }
 
 /// Информация о клетке в системе
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CellInfo {
     /// Уникальный идентификатор клетки
     pub id: String,
@@ -38,38 +99,71 @@ pub struct CellInfo {
     pub generation: u32,
     /// Текущая активность
     pub activity: f64,
+    /// Накопленная токсичность (от плохих выходов)
+    #[serde(default)]
+    pub toxicity: f64,
+    /// Количество тиков бездействия подряд
+    #[serde(default)]
+    pub idle_ticks: u32,
+    /// Координаты клетки (None - организм без пространства)
+    #[serde(default)]
+    pub position: Option<Position>,
+    /// Ткань, которой принадлежит клетка
+    #[serde(default)]
+    pub tissue: Option<TissueId>,
 }
 
 impl CellInfo {
-    /// Создать новую информацию о клетке
+    /// Создать новую информацию о клетке (время рождения - системное)
     pub fn new(id: String, role: CellRole, generation: u32) -> Self {
+        Self::born_at(id, role, generation, SystemClock.now_millis())
+    }
+
+    /// Создать информацию о клетке с заданным временем рождения
+    pub fn born_at(id: String, role: CellRole, generation: u32, birth_time: u64) -> Self {
         Self {
             id,
             role,
-            birth_time: current_timestamp_millis(),
+            birth_time,
             generation,
             activity: 0.0,
+            toxicity: 0.0,
+            idle_ticks: 0,
+            position: None,
+            tissue: None,
         }
     }
 
-    /// Получить возраст клетки в миллисекундах
+    /// Получить возраст клетки в миллисекундах (по системным часам)
+    ///
+    /// Для воспроизводимого возраста используйте [`Self::age_at`]
     pub fn age_millis(&self) -> u64 {
-        current_timestamp_millis() - self.birth_time
+        self.age_at(SystemClock.now_millis())
+    }
+
+    /// Получить возраст клетки на заданный момент времени
+    pub fn age_at(&self, now_millis: u64) -> u64 {
+        now_millis.saturating_sub(self.birth_time)
     }
 }
 
 /// Стволовой процессор - ядро системы, порождающее новые клетки
 ///
 /// StemProcessor наблюдает за нагрузкой системы и создаёт новые
-/// специализированные клетки когда это необходимо.
-#[derive(Debug)]
+/// специализированные клетки когда это необходимо. Для ролей с
+/// зарегистрированной фабрикой процессор владеет живыми экземплярами
+/// [`Cell`](crate::Cell) и прогоняет через них входной сигнал каждый тик.
 pub struct StemProcessor {
     /// Уникальный идентификатор стволового процессора
     pub id: String,
     /// Текущее поколение (счётчик делений)
     pub generation: u32,
-    /// Реестр всех клеток в системе
-    pub cells: HashMap<String, CellInfo>,
+    /// Популяция всех клеток в системе
+    ///
+    /// Изменяется только через методы процессора: живые экземпляры,
+    /// входящие сигналы, статистика и родословная должны оставаться
+    /// согласованными с популяцией
+    pub(crate) cells: CellPopulation,
     /// Текущая нагрузка системы (0.0 - 1.0)
     pub load: f64,
     /// Порог нагрузки для деления
@@ -78,200 +172,2224 @@ pub struct StemProcessor {
     pub smoothing: f64,
     /// Счётчик статистики по ролям
     role_stats: HashMap<CellRole, usize>,
+    /// Политика жизненного цикла (None - клетки живут вечно)
+    lifecycle: Option<LifecyclePolicy>,
+    /// Доступные стволовые клетки для регенерации
+    stem_pool: usize,
+    /// Статистика апоптоза и регенерации
+    lifecycle_stats: LifecycleStats,
+    /// Стратегия выбора роли при делении
+    selector: Box<dyn RoleSelector>,
+    /// Сигналы спроса на роли для стратегии выбора
+    role_demand: HashMap<CellRole, f64>,
+    /// Генератор случайных чисел (шум исследователей)
+    rng: StdRng,
+    /// Источник времени (возраст клеток, апоптоз)
+    clock: SharedClock,
+    /// Фабрики живых клеток по ролям
+    registry: CellRegistry,
+    /// Живые экземпляры клеток
+    live: HashMap<CellId, LiveCell>,
+    /// Последний внешний входной сигнал
+    input: f64,
+    /// Выход организма за последний тик
+    output: f64,
+    /// Родословная клеток (включая умершие)
+    lineage: Lineage,
+    /// Вести ли родословную
+    track_lineage: bool,
+    /// Живые клетки по ролям (переиспользуемый буфер маршрутизации)
+    stages: [Vec<CellId>; CellRole::ALL.len()],
+    /// Количество потоков для обновления активности в тике
+    tick_threads: usize,
+    /// Подписчики на события процессора
+    events: EventBus,
+    /// Изменение нагрузки, после которого публикуется событие
+    load_change_delta: f64,
+    /// Нагрузка на момент последнего события LoadChanged
+    reported_load: f64,
+    /// Векторная модель нагрузки (None - только скалярная нагрузка)
+    load_model: Option<LoadModel>,
+    /// Энергетический бюджет (None - деление и содержание бесплатны)
+    energy: Option<EnergyBudget>,
+    /// Поле морфогенов (None - организм без пространства)
+    morphogen: Option<MorphogenField>,
+    /// Ткани и органы организма
+    anatomy: Anatomy,
+    /// Сигналы соседей, ожидающие следующего `sense` клетки
+    inbox: HashMap<CellId, f64>,
+    /// Политика почкования (None - процессор не почкуется)
+    budding: Option<BuddingPolicy>,
+    /// Отпочкованные дочерние процессоры
+    children: Vec<StemProcessor>,
+    /// Политика трансдифференциации (None - роли неизменны)
+    transdifferentiation: Option<TransdifferentiationPolicy>,
+    /// Истории источников входа живых клеток
+    histories: HashMap<CellId, SignalHistory>,
 }
 
 impl StemProcessor {
     /// Создать новый стволовой процессор
     pub fn new() -> Self {
-        Self {
-            id: format!("stem_{}", current_timestamp_millis()),
-            generation: 0,
-            cells: HashMap::new(),
-            load: 0.0,
-            threshold: stem::DEFAULT_THRESHOLD,
-            smoothing: stem::DEFAULT_SMOOTHING,
-            role_stats: HashMap::new(),
-        }
+        Self::with_params(stem::DEFAULT_THRESHOLD, stem::DEFAULT_SMOOTHING)
     }
 
     /// Создать процессор с настраиваемыми параметрами
     pub fn with_params(threshold: f64, smoothing: f64) -> Self {
+        let clock = system_clock();
+        let now = clock.now_millis();
         Self {
-            id: format!("stem_{}", current_timestamp_millis()),
+            id: format!("stem_{}", now),
             generation: 0,
-            cells: HashMap::new(),
+            cells: CellPopulation::new(),
             load: 0.0,
             threshold: threshold.clamp(0.0, 1.0),
             smoothing: smoothing.clamp(0.0, 1.0),
             role_stats: HashMap::new(),
+            lifecycle: None,
+            stem_pool: 0,
+            lifecycle_stats: LifecycleStats::default(),
+            selector: Box::new(BalancedSelector::new()),
+            role_demand: HashMap::new(),
+            rng: StdRng::seed_from_u64(now),
+            clock,
+            registry: CellRegistry::new(),
+            live: HashMap::new(),
+            input: 0.0,
+            output: 0.0,
+            lineage: Lineage::new(),
+            track_lineage: true,
+            stages: Default::default(),
+            tick_threads: 1,
+            events: EventBus::new(),
+            load_change_delta: events::DEFAULT_LOAD_CHANGE_DELTA,
+            reported_load: 0.0,
+            load_model: None,
+            energy: None,
+            morphogen: None,
+            anatomy: Anatomy::new(),
+            inbox: HashMap::new(),
+            budding: None,
+            children: Vec::new(),
+            transdifferentiation: None,
+            histories: HashMap::new(),
         }
     }
 
-    /// Воспринять активность сети (Sense)
+    /// Использовать заданный источник времени
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Получить источник времени процессора
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// Получить возраст клетки по часам процессора
+    pub fn cell_age_millis(&self, id: &str) -> Option<u64> {
+        let now = self.clock.now_millis();
+        self.cells.get(id).map(|cell| cell.age_at(now))
+    }
+
+    /// Зафиксировать зерно генератора случайных чисел (воспроизводимая динамика)
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Обновлять активность клеток в тике параллельно
     ///
-    /// Обновляет нагрузку системы и инициирует деление при необходимости
-    pub fn sense(&mut self, activity: f64) {
-        // Экспоненциальное сглаживание нагрузки
-        self.load = (self.load * self.smoothing) + (activity * (1.0 - self.smoothing));
+    /// `threads` - число потоков (0 - по числу ядер). Параллелизм
+    /// включается только для больших популяций.
+    pub fn with_parallel_tick(mut self, threads: usize) -> Self {
+        self.set_parallel_tick(threads);
+        self
+    }
 
-        // Если нагрузка превышает порог - делимся
-        if self.load > self.threshold {
-            self.divide();
+    /// Установить число потоков для обновления активности в тике
+    pub fn set_parallel_tick(&mut self, threads: usize) {
+        self.tick_threads = match threads {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+    }
+
+    /// Включить или отключить ведение родословной
+    ///
+    /// Для очень больших популяций родословная может занимать
+    /// заметную память, её можно отключить.
+    pub fn with_lineage_tracking(mut self, enabled: bool) -> Self {
+        self.set_lineage_tracking(enabled);
+        self
+    }
+
+    /// Включить или отключить ведение родословной
+    ///
+    /// При включении с пустой родословной живые клетки становятся корнями
+    pub fn set_lineage_tracking(&mut self, enabled: bool) {
+        self.track_lineage = enabled;
+        self.seed_lineage_roots();
+    }
+
+    /// Записать живые клетки корнями пустой родословной
+    fn seed_lineage_roots(&mut self) {
+        if !self.track_lineage || !self.lineage.is_empty() {
+            return;
+        }
+        let mut cells: Vec<CellInfo> = self.cells.values().collect();
+        cells.sort_by(|a, b| a.id.cmp(&b.id));
+        for cell in cells {
+            self.lineage
+                .record_birth(&cell.id, cell.role, None, cell.generation, cell.birth_time);
         }
     }
 
-    /// Деление - создание новой клетки (Align)
-    fn divide(&mut self) {
-        self.generation += 1;
+    /// Подписаться на события процессора
+    pub fn subscribe<F>(&mut self, callback: F) -> SubscriptionId
+    where
+        F: FnMut(&StemEvent) + Send + 'static,
+    {
+        self.events.subscribe(callback)
+    }
 
-        // Выбираем роль для новой клетки
-        let role = self.choose_role();
+    /// Отменить подписку на события
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        self.events.unsubscribe(id)
+    }
 
-        // Создаём уникальный ID
-        let id = format!("cell_{}_{}", self.generation, self.cells.len() + 1);
+    /// Получить канал событий процессора
+    pub fn event_channel(&mut self) -> mpsc::Receiver<StemEvent> {
+        self.events.channel()
+    }
 
-        // Создаём информацию о клетке
-        let cell_info = CellInfo::new(id.clone(), role, self.generation);
+    /// Установить изменение нагрузки, после которого публикуется LoadChanged
+    pub fn with_load_change_delta(mut self, delta: f64) -> Self {
+        self.load_change_delta = delta.max(0.0);
+        self
+    }
 
-        // Добавляем в реестр
-        self.cells.insert(id, cell_info);
+    /// Использовать заданную стратегию выбора роли при делении
+    pub fn with_role_selector<S: RoleSelector + 'static>(mut self, selector: S) -> Self {
+        self.set_role_selector(Box::new(selector));
+        self
+    }
 
-        // Обновляем статистику
-        *self.role_stats.entry(role).or_insert(0) += 1;
+    /// Заменить стратегию выбора роли
+    pub fn set_role_selector(&mut self, selector: Box<dyn RoleSelector>) {
+        self.selector = selector;
+    }
 
-        // Сбрасываем нагрузку после деления
-        self.load *= stem::LOAD_REDUCTION_FACTOR;
+    /// Установить сигнал спроса на роль (используется градиентными стратегиями)
+    pub fn set_role_demand(&mut self, role: CellRole, demand: f64) {
+        self.role_demand.insert(role, demand.max(0.0));
+    }
+
+    /// Получить текущие сигналы спроса на роли
+    pub fn role_demand(&self) -> &HashMap<CellRole, f64> {
+        &self.role_demand
+    }
+
+    /// Использовать заданный реестр фабрик клеток
+    ///
+    /// Существующие клетки с зарегистрированной ролью получают живые экземпляры
+    pub fn with_cell_registry(mut self, registry: CellRegistry) -> Self {
+        self.registry = registry;
+        self.populate_live_cells();
+        self
+    }
+
+    /// Зарегистрировать фабрику живых клеток для роли (builder)
+    pub fn with_cell_factory<F>(mut self, role: CellRole, factory: F) -> Self
+    where
+        F: Fn(&CellInfo) -> LiveCell + Send + Sync + 'static,
+    {
+        self.register_cell_factory(role, factory);
+        self
+    }
+
+    /// Зарегистрировать фабрику живых клеток для роли
+    pub fn register_cell_factory<F>(&mut self, role: CellRole, factory: F)
+    where
+        F: Fn(&CellInfo) -> LiveCell + Send + Sync + 'static,
+    {
+        self.registry.register(role, factory);
+        self.populate_live_cells();
+    }
+
+    /// Получить реестр фабрик клеток
+    pub fn cell_registry(&self) -> &CellRegistry {
+        &self.registry
+    }
+
+    /// Получить живой экземпляр клетки
+    pub fn live_cell(&self, id: &str) -> Option<&LiveCell> {
+        self.cells.resolve(id).and_then(|id| self.live.get(&id))
+    }
+
+    /// Количество живых экземпляров клеток
+    pub fn live_cell_count(&self) -> usize {
+        self.live.len()
     }
 
-    /// Выбрать роль для новой клетки на основе текущего состояния
-    fn choose_role(&self) -> CellRole {
-        // Простая эвристика: циклическое распределение с учётом баланса
-        let sensor_count = self.role_stats.get(&CellRole::Sensor).unwrap_or(&0);
-        let logic_count = self.role_stats.get(&CellRole::Logic).unwrap_or(&0);
-        let motor_count = self.role_stats.get(&CellRole::Motor).unwrap_or(&0);
+    /// Выход организма за последний тик (средний поток последней стадии)
+    pub fn output(&self) -> f64 {
+        self.output
+    }
 
-        // Выбираем наименее представленную роль
-        if sensor_count <= logic_count && sensor_count <= motor_count {
-            CellRole::Sensor
-        } else if logic_count <= motor_count {
-            CellRole::Logic
-        } else {
-            CellRole::Motor
+    /// Создать живые экземпляры для клеток, у которых их ещё нет
+    fn populate_live_cells(&mut self) {
+        for index in 0..self.cells.len() {
+            let id = self.cells.ids[index];
+            if !self.live.contains_key(&id) && self.registry.has(self.cells.roles[index]) {
+                self.instantiate(id);
+            }
         }
     }
 
-    /// Тик системы - обновление состояния всех клеток (Flow)
-    pub fn tick(&mut self) {
-        // Обновляем активность каждой клетки
-        for cell in self.cells.values_mut() {
-            // Простое затухание активности
-            cell.activity *= cell::ACTIVITY_DECAY;
+    /// Создать живой экземпляр клетки через фабрику её роли
+    fn instantiate(&mut self, id: CellId) {
+        let Some(info) = self.cells.info(id) else { return };
+        if let Some(cell) = self.registry.create(&info) {
+            self.live.insert(id, cell);
+            self.cells.set_driven(id, true);
         }
     }
 
-    /// Получить количество клеток
-    pub fn cell_count(&self) -> usize {
-        self.cells.len()
+    /// Включить политику жизненного цикла клеток
+    ///
+    /// Пул стволовых клеток заполняется до ёмкости политики
+    pub fn with_lifecycle(mut self, policy: LifecyclePolicy) -> Self {
+        self.set_lifecycle(Some(policy));
+        self
     }
 
-    /// Получить статистику по ролям
-    pub fn role_distribution(&self) -> HashMap<CellRole, usize> {
-        self.role_stats.clone()
+    /// Установить или отключить политику жизненного цикла
+    pub fn set_lifecycle(&mut self, policy: Option<LifecyclePolicy>) {
+        self.stem_pool = policy.as_ref().map_or(0, |p| p.stem_pool_capacity);
+        self.lifecycle = policy;
     }
 
-    /// Удалить клетку (апоптоз)
-    pub fn remove_cell(&mut self, id: &str) -> Option<CellInfo> {
-        if let Some(cell) = self.cells.remove(id) {
-            if let Some(count) = self.role_stats.get_mut(&cell.role) {
-                *count = count.saturating_sub(1);
+    /// Получить текущую политику жизненного цикла
+    pub fn lifecycle(&self) -> Option<&LifecyclePolicy> {
+        self.lifecycle.as_ref()
+    }
+
+    /// Получить статистику апоптоза и регенерации
+    pub fn lifecycle_stats(&self) -> &LifecycleStats {
+        &self.lifecycle_stats
+    }
+
+    /// Количество стволовых клеток, доступных для регенерации
+    pub fn stem_pool(&self) -> usize {
+        self.stem_pool
+    }
+
+    /// Использовать энергетический бюджет
+    pub fn with_energy(mut self, budget: EnergyBudget) -> Self {
+        self.energy = Some(budget);
+        self
+    }
+
+    /// Заменить энергетический бюджет (None - отключить)
+    pub fn set_energy(&mut self, budget: Option<EnergyBudget>) {
+        self.energy = budget;
+    }
+
+    /// Получить энергетический бюджет
+    pub fn energy(&self) -> Option<&EnergyBudget> {
+        self.energy.as_ref()
+    }
+
+    /// Включить трансдифференциацию живых клеток
+    pub fn with_transdifferentiation(mut self, policy: TransdifferentiationPolicy) -> Self {
+        self.transdifferentiation = Some(policy);
+        self
+    }
+
+    /// Заменить политику трансдифференциации (None - отключить)
+    pub fn set_transdifferentiation(&mut self, policy: Option<TransdifferentiationPolicy>) {
+        self.transdifferentiation = policy;
+        if self.transdifferentiation.is_none() {
+            self.histories.clear();
+        }
+    }
+
+    /// Получить политику трансдифференциации
+    pub fn transdifferentiation(&self) -> Option<&TransdifferentiationPolicy> {
+        self.transdifferentiation.as_ref()
+    }
+
+    /// История источников входа клетки
+    pub fn signal_history(&self, id: &str) -> Option<&SignalHistory> {
+        self.cells.resolve(id).and_then(|id| self.histories.get(&id))
+    }
+
+    /// Включить почкование дочерних процессоров
+    pub fn with_budding(mut self, policy: BuddingPolicy) -> Self {
+        self.budding = Some(policy);
+        self
+    }
+
+    /// Заменить политику почкования (None - отключить)
+    pub fn set_budding(&mut self, policy: Option<BuddingPolicy>) {
+        self.budding = policy;
+    }
+
+    /// Получить политику почкования
+    pub fn budding(&self) -> Option<&BuddingPolicy> {
+        self.budding.as_ref()
+    }
+
+    /// Отпочкованные дочерние процессоры
+    pub fn children(&self) -> &[StemProcessor] {
+        &self.children
+    }
+
+    /// Средняя нагрузка почек (0.0 - почек нет)
+    pub fn child_load(&self) -> f64 {
+        if self.children.is_empty() {
+            return 0.0;
+        }
+        self.children.iter().map(|c| c.load).sum::<f64>() / self.children.len() as f64
+    }
+
+    /// Количество клеток процессора вместе со всеми почками
+    pub fn total_cell_count(&self) -> usize {
+        self.cells.len() + self.children.iter().map(|c| c.total_cell_count()).sum::<usize>()
+    }
+
+    /// Отпочковать дочерний процессор
+    ///
+    /// Почке переходят младшие клетки (доля по политике почкования) вместе
+    /// с живыми экземплярами, параметры, стратегия выбора роли, фабрики и
+    /// доля энергетического запаса родителя. В родословной родителя
+    /// перешедшие клетки остаются живыми, в родословной почки они - корни.
+    /// Возвращает None, если передавать нечего
+    pub fn bud(&mut self) -> Option<&StemProcessor> {
+        let policy = self.budding.clone().unwrap_or_default();
+        let size = policy.bud_size(self.cells.len());
+        if size == 0 {
+            return None;
+        }
+
+        let cells = &self.cells;
+        let mut order: Vec<usize> = (0..cells.len()).collect();
+        order.sort_by(|&a, &b| cells.generations[b].cmp(&cells.generations[a]).then(b.cmp(&a)));
+        let moved: Vec<CellId> = order[..size].iter().map(|&index| cells.ids[index]).collect();
+
+        let mut child = self.new_bud();
+        if let Some(budget) = self.energy.as_mut() {
+            let share = budget.reserve() * policy.share;
+            budget.spend(share);
+            child.energy = Some(budget.clone().with_reserve(share));
+        }
+
+        let now = self.clock.now_millis();
+        let mut names = Vec::with_capacity(size);
+        for id in moved {
+            let Some((info, live)) = self.detach_cell(id) else { continue };
+            // В родословной родителя клетка больше не живёт
+            if self.track_lineage {
+                self.lineage.record_death(&info.id, now, Some(ApoptosisCause::Budded));
             }
-            Some(cell)
-        } else {
-            None
+            names.push(info.id.clone());
+            child.adopt_cell(info, live);
         }
+
+        if self.events.has_subscribers() {
+            self.events.emit(StemEvent::Budded {
+                child: child.id.clone(),
+                cells: names,
+            });
+        }
+        self.children.push(child);
+        self.children.last()
     }
 
-    /// Получить список всех клеток
-    pub fn cells(&self) -> &HashMap<String, CellInfo> {
-        &self.cells
+    /// Почковаться, если этого требует политика
+    fn try_bud(&mut self) {
+        let Some(policy) = &self.budding else { return };
+        if policy.should_bud(self.cells.len(), self.load, self.children.len()) {
+            self.bud();
+        }
     }
-}
 
-impl Default for StemProcessor {
-    fn default() -> Self {
-        Self::new()
+    /// Пустая почка с параметрами родителя
+    fn new_bud(&mut self) -> StemProcessor {
+        let mut child = StemProcessor::with_params(self.threshold, self.smoothing)
+            .with_clock(self.clock.clone())
+            .with_seed(self.rng.gen());
+        child.id = format!("{}.{}", self.id, self.children.len() + 1);
+        child.cells = CellPopulation::new().with_name_prefix(format!("{}.", child.id));
+        child.generation = self.generation;
+        child.selector = self.selector.fork();
+        child.role_demand = self.role_demand.clone();
+        child.registry = self.registry.clone();
+        child.lifecycle = self.lifecycle.clone();
+        child.load_model = self.load_model.clone();
+        child.morphogen = self.morphogen.clone();
+        child.anatomy = self.anatomy.clone();
+        child.budding = self.budding.clone();
+        child.transdifferentiation = self.transdifferentiation.clone();
+        child.track_lineage = self.track_lineage;
+        child.tick_threads = self.tick_threads;
+        child.load_change_delta = self.load_change_delta;
+        child
     }
-}
 
-/// Получить текущую временную метку в миллисекундах
-fn current_timestamp_millis() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
-}
+    /// Принять клетку, перешедшую от родителя
+    fn adopt_cell(&mut self, info: CellInfo, live: Option<LiveCell>) {
+        if self.track_lineage {
+            self.lineage
+                .record_birth(&info.id, info.role, None, info.generation, info.birth_time);
+        }
+        *self.role_stats.entry(info.role).or_insert(0) += 1;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let role = info.role;
+        let id = self.cells.insert_info(info);
+        match live {
+            Some(cell) => {
+                self.live.insert(id, cell);
+                self.cells.set_driven(id, true);
+            }
+            None if self.registry.has(role) => self.instantiate(id),
+            None => {}
+        }
+    }
 
-    #[test]
-    fn test_stem_processor_creation() {
-        let stem = StemProcessor::new();
-        assert_eq!(stem.generation, 0);
-        assert_eq!(stem.cell_count(), 0);
-        assert_eq!(stem.load, 0.0);
+    /// Разместить клетки в пространстве с полем морфогенов
+    ///
+    /// Новые клетки получают координаты рядом с родителем, а стратегия
+    /// выбора роли видит локальные концентрации морфогенов
+    pub fn with_morphogen_field(mut self, field: MorphogenField) -> Self {
+        self.morphogen = Some(field);
+        self
     }
 
-    #[test]
-    fn test_cell_division() {
-        let mut stem = StemProcessor::with_params(0.3, 0.5);
+    /// Заменить поле морфогенов (None - отключить)
+    pub fn set_morphogen_field(&mut self, field: Option<MorphogenField>) {
+        self.morphogen = field;
+    }
 
-        // Стимулируем деление несколько раз для накопления нагрузки
-        stem.sense(0.6);
-        stem.sense(0.6);
+    /// Получить поле морфогенов
+    pub fn morphogen_field(&self) -> Option<&MorphogenField> {
+        self.morphogen.as_ref()
+    }
 
-        assert_eq!(stem.generation, 1);
-        assert_eq!(stem.cell_count(), 1);
-        assert!(stem.load < 0.6); // Нагрузка должна снизиться после деления
+    /// Создать ткань с собственным порогом (сглаживание - как у процессора)
+    pub fn create_tissue(&mut self, name: &str, threshold: f64) -> TissueId {
+        self.anatomy.add_tissue(name, threshold, self.smoothing)
     }
 
-    #[test]
-    fn test_role_distribution() {
-        let mut stem = StemProcessor::with_params(0.3, 0.5);
+    /// Создать орган с порогом средней нагрузки тканей
+    pub fn create_organ(&mut self, name: &str, threshold: f64) -> OrganId {
+        self.anatomy.add_organ(name, threshold)
+    }
 
-        // Создаём несколько клеток
-        for _ in 0..6 {
-            stem.sense(0.5);
+    /// Включить ткань в орган
+    pub fn attach_tissue(&mut self, tissue: TissueId, organ: OrganId) -> bool {
+        self.anatomy.attach(tissue, organ)
+    }
+
+    /// Включить клетку в ткань (None - исключить из ткани)
+    ///
+    /// Возвращает false, если клетка или ткань не найдены
+    pub fn assign_cell(&mut self, id: &str, tissue: Option<TissueId>) -> bool {
+        if tissue.is_some_and(|t| self.anatomy.tissue(t).is_none()) {
+            return false;
+        }
+        match self.cells.resolve(id) {
+            Some(id) => self.cells.set_tissue(id, tissue),
+            None => false,
         }
+    }
 
-        let distribution = stem.role_distribution();
-        let total: usize = distribution.values().sum();
-        assert_eq!(total, stem.cell_count());
+    /// Ткань клетки
+    pub fn cell_tissue(&self, id: &str) -> Option<TissueId> {
+        self.cells.resolve(id).and_then(|id| self.cells.tissue_of(id))
     }
 
-    #[test]
-    fn test_cell_removal() {
-        let mut stem = StemProcessor::with_params(0.3, 0.5);
+    /// Ткани и органы организма
+    pub fn anatomy(&self) -> &Anatomy {
+        &self.anatomy
+    }
 
-        // Создаём клетку
-        stem.sense(0.6);
-        stem.sense(0.6);
+    /// Клетки ткани
+    pub fn tissue_cells(&self, tissue: TissueId) -> Vec<CellId> {
+        let cells = &self.cells;
+        (0..cells.len())
+            .filter(|&index| cells.memberships[index] == Some(tissue))
+            .map(|index| cells.ids[index])
+            .collect()
+    }
 
-        assert!(stem.cell_count() > 0);
-        let initial_count = stem.cell_count();
+    /// Сводные метрики ткани
+    pub fn tissue_metrics(&self, tissue: TissueId) -> Option<GroupMetrics> {
+        self.anatomy.tissue_metrics(tissue, &self.cells)
+    }
 
-        let cell_id = stem.cells.keys().next().unwrap().clone();
-        let removed = stem.remove_cell(&cell_id);
+    /// Сводные метрики органа
+    pub fn organ_metrics(&self, organ: OrganId) -> Option<GroupMetrics> {
+        self.anatomy.organ_metrics(organ, &self.cells)
+    }
 
-        assert!(removed.is_some());
-        assert_eq!(stem.cell_count(), initial_count - 1);
+    /// Стимулировать ткань целиком
+    ///
+    /// Нагрузка ткани сглаживается отдельно от нагрузки процессора. При
+    /// превышении порога ткани делится её самая активная клетка, потомок
+    /// остаётся в ткани. Возвращает false, если ткань не найдена
+    pub fn stimulate_tissue(&mut self, tissue: TissueId, activity: f64) -> bool {
+        let Some(entry) = self.anatomy.tissue_mut(tissue) else { return false };
+        if entry.sense(activity) {
+            self.divide_tissue(tissue);
+        }
+        true
     }
 
-    #[test]
-    fn test_cell_info_age() {
-        let cell = CellInfo::new("test".to_string(), CellRole::Sensor, 1);
-        std::thread::sleep(std::time::Duration::from_millis(10));
-        assert!(cell.age_millis() >= 10);
+    /// Стимулировать все ткани органа
+    ///
+    /// Возвращает false, если орган не найден
+    pub fn stimulate_organ(&mut self, organ: OrganId, activity: f64) -> bool {
+        let Some(tissues) = self.anatomy.organ(organ).map(|o| o.tissues.clone()) else {
+            return false;
+        };
+        for tissue in tissues {
+            self.stimulate_tissue(tissue, activity);
+        }
+        true
+    }
+
+    /// Деление внутри ткани
+    fn divide_tissue(&mut self, tissue: TissueId) {
+        let cells = &self.cells;
+        let parent = self.most_active_of((0..cells.len()).filter(|&i| cells.memberships[i] == Some(tissue)));
+        let position = self.birth_position(parent);
+
+        let role = self.choose_role_at(position, Some(tissue));
+        if !self.pay_for_division(role) {
+            return;
+        }
+        let id = self.spawn_cell_at(role, parent, position);
+        self.cells.set_tissue(id, Some(tissue));
+
+        if let Some(entry) = self.anatomy.tissue_mut(tissue) {
+            entry.load *= stem::LOAD_REDUCTION_FACTOR;
+        }
+    }
+
+    /// Координаты клетки
+    pub fn cell_position(&self, id: &str) -> Option<Position> {
+        self.cells.resolve(id).and_then(|id| self.cells.position_of(id))
+    }
+
+    /// Воспринять активность сети (Sense)
+    ///
+    /// Обновляет нагрузку системы и инициирует деление при необходимости
+    pub fn sense(&mut self, activity: f64) {
+        self.input = activity;
+        self.sense_children(|child| child.sense(activity));
+        let activity = self.with_child_load(activity);
+
+        // Экспоненциальное сглаживание нагрузки
+        let previous = self.load;
+        self.load = (self.load * self.smoothing) + (activity * (1.0 - self.smoothing));
+
+        let rising = self.load > self.threshold;
+        if self.events.has_subscribers() && (previous > self.threshold) != rising {
+            self.events.emit(StemEvent::ThresholdCrossed {
+                load: self.load,
+                threshold: self.threshold,
+                rising,
+            });
+        }
+
+        // Если нагрузка превышает порог - делимся
+        if rising {
+            self.divide();
+        }
+
+        self.try_bud();
+        self.report_load_change();
+    }
+
+    /// Передать вход почкам
+    fn sense_children(&mut self, sense: impl FnMut(&mut StemProcessor)) {
+        self.children.iter_mut().for_each(sense);
+    }
+
+    /// Подмешать к нагрузке среднюю нагрузку почек с весом политики почкования
+    fn with_child_load(&self, activity: f64) -> f64 {
+        if self.children.is_empty() {
+            return activity;
+        }
+        let weight = self.budding.as_ref().map_or(0.0, |p| p.child_weight);
+        activity * (1.0 - weight) + self.child_load() * weight
+    }
+
+    /// Использовать векторную модель нагрузки
+    pub fn with_load_model(mut self, model: LoadModel) -> Self {
+        self.load_model = Some(model);
+        self
+    }
+
+    /// Заменить векторную модель нагрузки (None - отключить)
+    pub fn set_load_model(&mut self, model: Option<LoadModel>) {
+        self.load_model = model;
+    }
+
+    /// Получить векторную модель нагрузки
+    pub fn load_model(&self) -> Option<&LoadModel> {
+        self.load_model.as_ref()
+    }
+
+    /// Текущая сглаженная нагрузка по измерениям
+    pub fn load_vector(&self) -> Option<LoadVector> {
+        self.load_model.as_ref().map(LoadModel::load)
+    }
+
+    /// Воспринять многомерную нагрузку
+    ///
+    /// Каждое измерение сглаживается и сравнивается со своим порогом.
+    /// Делится клетка роли самого перегруженного измерения, сглаженная
+    /// нагрузка измерений становится спросом на соответствующие роли,
+    /// а скалярная `load` - максимумом по измерениям. Без модели
+    /// создаётся модель с порогом и сглаживанием процессора. Почки
+    /// получают тот же вектор, их нагрузка подмешивается как в [`Self::sense`].
+    pub fn sense_vector(&mut self, sample: LoadVector) {
+        self.input = sample.input;
+        self.sense_children(|child| child.sense_vector(sample));
+        let sample = LoadVector::new(
+            self.with_child_load(sample.input),
+            self.with_child_load(sample.compute),
+            self.with_child_load(sample.output),
+        );
+
+        let mut model = self
+            .load_model
+            .take()
+            .unwrap_or_else(|| LoadModel::new(self.threshold, self.smoothing));
+        let before = model.clone();
+        model.update(sample);
+
+        if self.events.has_subscribers() {
+            for dimension in LoadDimension::ALL {
+                let rising = model.excess(dimension) > 0.0;
+                if (before.excess(dimension) > 0.0) != rising {
+                    self.events.emit(StemEvent::DimensionThresholdCrossed {
+                        dimension,
+                        load: model.load().get(dimension),
+                        threshold: model.params(dimension).threshold,
+                        rising,
+                    });
+                }
+            }
+        }
+
+        for dimension in LoadDimension::ALL {
+            self.set_role_demand(dimension.role(), model.load().get(dimension));
+        }
+
+        // Направленное деление - в роль перегруженного измерения
+        if let Some(dimension) = model.most_pressured() {
+            if self.pay_for_division(dimension.role()) {
+                let parent = self.most_active_cell();
+                self.spawn_cell_from(dimension.role(), parent);
+                model.relieve(dimension);
+            }
+        }
+
+        self.load = model.load().max();
+        self.load_model = Some(model);
+        self.try_bud();
+        self.report_load_change();
+    }
+
+    /// Опубликовать LoadChanged, если нагрузка ушла достаточно далеко
+    fn report_load_change(&mut self) {
+        if (self.load - self.reported_load).abs() < self.load_change_delta {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.reported_load, self.load);
+        if self.events.has_subscribers() {
+            self.events.emit(StemEvent::LoadChanged {
+                previous,
+                load: self.load,
+            });
+        }
+    }
+
+    /// Деление - создание новой клетки (Align)
+    fn divide(&mut self) {
+        // Делится самая активная клетка (или сам стволовой процессор)
+        let parent = self.most_active_cell();
+        let position = self.birth_position(parent);
+
+        // Выбираем роль для новой клетки
+        let role = self.choose_role_at(position, None);
+        if !self.pay_for_division(role) {
+            return;
+        }
+        self.spawn_cell_at(role, parent, position);
+
+        // Сбрасываем нагрузку после деления
+        self.load *= stem::LOAD_REDUCTION_FACTOR;
+    }
+
+    /// Оплатить деление из энергетического бюджета
+    ///
+    /// При нехватке энергии публикует DivisionRefused и возвращает false
+    fn pay_for_division(&mut self, role: CellRole) -> bool {
+        let Some(budget) = self.energy.as_mut() else { return true };
+        let cost = budget.division_cost;
+        if budget.spend(cost) {
+            return true;
+        }
+
+        let reserve = budget.reserve();
+        if self.events.has_subscribers() {
+            self.events.emit(StemEvent::DivisionRefused { role, reserve, cost });
+        }
+        false
+    }
+
+    /// Самая активная клетка (при равенстве - старшая)
+    fn most_active_cell(&self) -> Option<CellId> {
+        self.most_active_of(0..self.cells.len())
+    }
+
+    /// Самая активная клетка среди заданных индексов (при равенстве - старшая)
+    fn most_active_of(&self, indices: impl Iterator<Item = usize>) -> Option<CellId> {
+        let cells = &self.cells;
+        indices
+            .max_by(|&a, &b| {
+                cells.activity[a]
+                    .total_cmp(&cells.activity[b])
+                    .then_with(|| cells.generations[b].cmp(&cells.generations[a]))
+            })
+            .map(|index| cells.ids[index])
+    }
+
+    /// Породить клетку заданной роли непосредственно из стволового процессора
+    #[cfg(test)]
+    fn spawn_cell(&mut self, role: CellRole) -> CellId {
+        self.spawn_cell_from(role, None)
+    }
+
+    /// Породить клетку заданной роли и зарегистрировать её
+    ///
+    /// `parent` - клетка, от деления которой произошла новая
+    fn spawn_cell_from(&mut self, role: CellRole, parent: Option<CellId>) -> CellId {
+        let position = self.birth_position(parent);
+        self.spawn_cell_at(role, parent, position)
+    }
+
+    /// Точка рождения клетки - рядом с родителем (или с центром поля)
+    ///
+    /// None - у организма нет поля морфогенов
+    fn birth_position(&mut self, parent: Option<CellId>) -> Option<Position> {
+        let field = self.morphogen.as_ref()?;
+        let origin = parent
+            .and_then(|p| self.cells.position_of(p))
+            .unwrap_or_else(|| field.center());
+
+        let reach = morphogen::CHILD_OFFSET;
+        let dx = self.rng.gen_range(-reach..=reach);
+        let dy = self.rng.gen_range(-reach..=reach);
+        let dz = if field.is_planar() { 0.0 } else { self.rng.gen_range(-reach..=reach) };
+        Some(field.clamp(origin.offset(dx, dy, dz)))
+    }
+
+    /// Породить клетку в заданной точке пространства
+    fn spawn_cell_at(&mut self, role: CellRole, parent: Option<CellId>, position: Option<Position>) -> CellId {
+        self.generation += 1;
+
+        // Добавляем клетку в популяцию
+        let birth_time = self.clock.now_millis();
+        let id = self.cells.insert_at(role, self.generation, birth_time, position);
+
+        // Потомок остаётся в ткани родителя
+        let tissue = parent.and_then(|p| self.cells.tissue_of(p));
+        self.cells.set_tissue(id, tissue);
+
+        let notify = self.events.has_subscribers();
+        if self.track_lineage || notify {
+            let name = self.cells.name(id).unwrap_or_default();
+            let parent = parent.and_then(|p| self.cells.name(p));
+            if self.track_lineage {
+                self.lineage
+                    .record_birth(&name, role, parent.as_deref(), self.generation, birth_time);
+            }
+            if notify {
+                self.events.emit(StemEvent::CellDivided {
+                    id: name,
+                    role,
+                    parent,
+                    generation: self.generation,
+                });
+            }
+        }
+
+        // Дифференцируем живую клетку, если для роли есть фабрика
+        if self.registry.has(role) {
+            self.instantiate(id);
+        }
+
+        // Обновляем статистику
+        *self.role_stats.entry(role).or_insert(0) += 1;
+
+        id
+    }
+
+    /// Выбрать роль для новой клетки через стратегию выбора
+    ///
+    /// В точке рождения стратегии доступны локальные концентрации морфогенов.
+    /// При делении внутри ткани стратегия видит состав ролей этой ткани
+    fn choose_role_at(&mut self, position: Option<Position>, tissue: Option<TissueId>) -> CellRole {
+        let morphogens = match (&self.morphogen, position) {
+            (Some(field), Some(position)) => field.concentrations(&position),
+            _ => HashMap::new(),
+        };
+        let local = tissue
+            .and_then(|t| self.anatomy.tissue_metrics(t, &self.cells))
+            .map(|metrics| metrics.role_mix);
+        let ctx = SelectionContext {
+            distribution: local.as_ref().unwrap_or(&self.role_stats),
+            demand: &self.role_demand,
+            load: self.load,
+            generation: self.generation,
+            morphogens: &morphogens,
+        };
+        self.selector.select(&ctx)
+    }
+
+    /// Тик системы - обновление состояния всех клеток (Flow)
+    pub fn tick(&mut self) {
+        self.route_signals();
+        self.absorb_signals();
+        self.run_transdifferentiation();
+        self.apply_role_dynamics();
+        self.run_metabolism();
+        self.run_morphogens();
+        self.run_lifecycle();
+        for child in &mut self.children {
+            child.tick();
+        }
+    }
+
+    /// Маршрутизация входа через живые клетки: Sensor → Logic → Motor
+    ///
+    /// Сенсоры получают внешний вход, логика - средний поток сенсоров,
+    /// моторы - средний поток логики. Если стадии нет, её вход передаётся
+    /// дальше. Прочие роли получают внешний вход. Активность живой клетки
+    /// равна её выходному потоку.
+    fn route_signals(&mut self) {
+        if self.live.is_empty() {
+            self.output = 0.0;
+            return;
+        }
+
+        // Раскладка живых клеток по ролям за один проход
+        let mut stages = std::mem::take(&mut self.stages);
+        stages.iter_mut().for_each(Vec::clear);
+        for index in 0..self.cells.len() {
+            if self.cells.driven[index] {
+                stages[self.cells.roles[index] as usize].push(self.cells.ids[index]);
+            }
+        }
+
+        // Вход каждой стадии передаётся вместе с его источником
+        let external = (self.input, InputSource::Raw);
+        let sensor = self
+            .run_stage(CellRole::Sensor, &stages[CellRole::Sensor as usize], external)
+            .map(|flow| (flow, InputSource::Sensed));
+        let logic = self
+            .run_stage(CellRole::Logic, &stages[CellRole::Logic as usize], sensor.unwrap_or(external))
+            .map(|flow| (flow, InputSource::Processed));
+        let motor = self.run_stage(
+            CellRole::Motor,
+            &stages[CellRole::Motor as usize],
+            logic.or(sensor).unwrap_or(external),
+        );
+        for role in [CellRole::Memory, CellRole::Hub, CellRole::Inhibitor, CellRole::Explorer] {
+            self.run_stage(role, &stages[role as usize], external);
+        }
+        self.stages = stages;
+
+        self.output = motor.or(logic.or(sensor).map(|(flow, _)| flow)).unwrap_or(0.0);
+    }
+
+    /// Прогнать вход через живые клетки роли `ids`
+    ///
+    /// При включённой трансдифференциации клетки конвейера запоминают
+    /// источник входа. Возвращает средний выходной поток стадии (None -
+    /// живых клеток роли нет)
+    fn run_stage(&mut self, role: CellRole, ids: &[CellId], (input, source): (f64, InputSource)) -> Option<f64> {
+        if ids.is_empty() {
+            return None;
+        }
+
+        let mut total = 0.0;
+        for &id in ids {
+            let Some(cell) = self.live.get_mut(&id) else { continue };
+            let signal = self.inbox.remove(&id).unwrap_or(0.0);
+            cell.sense(input + signal);
+            if let Some(policy) = &self.transdifferentiation {
+                if InputSource::is_pipeline_role(role) {
+                    let window = policy.window;
+                    self.histories.entry(id).or_insert_with(|| SignalHistory::new(window)).record(source);
+                }
+            }
+            cell.align();
+            let flow = cell.flow();
+            total += flow;
+            self.cells.set_activity_of(id, flow.clamp(0.0, 1.0));
+        }
+        Some(total / ids.len() as f64)
+    }
+
+    /// Смена ролей клеток, чья история входов устойчиво указывает на другую роль
+    ///
+    /// За тик меняют роль не больше `max_per_tick` клеток (старшие первыми),
+    /// история сменившей роль клетки начинается заново
+    fn run_transdifferentiation(&mut self) {
+        let Some(policy) = &self.transdifferentiation else { return };
+
+        let mut candidates: Vec<(usize, CellRole)> = (0..self.cells.len())
+            .filter_map(|index| {
+                let history = self.histories.get(&self.cells.ids[index])?;
+                policy.target_role(self.cells.roles[index], history).map(|role| (index, role))
+            })
+            .collect();
+        candidates.sort_by_key(|&(index, _)| (self.cells.generations[index], index));
+        candidates.truncate(policy.max_per_tick);
+
+        let changes: Vec<(CellId, CellRole)> = candidates
+            .into_iter()
+            .map(|(index, role)| (self.cells.ids[index], role))
+            .collect();
+        for (id, role) in changes {
+            self.transdifferentiate(id, role);
+        }
+    }
+
+    /// Сменить роль клетки: живой экземпляр пересоздаётся фабрикой новой роли
+    fn transdifferentiate(&mut self, id: CellId, role: CellRole) {
+        let Some(from) = self.cells.role_of(id) else { return };
+        self.cells.set_role(id, role);
+        self.histories.remove(&id);
+        if let Some(count) = self.role_stats.get_mut(&from) {
+            *count = count.saturating_sub(1);
+        }
+        *self.role_stats.entry(role).or_insert(0) += 1;
+
+        self.live.remove(&id);
+        self.cells.set_driven(id, false);
+        if self.registry.has(role) {
+            self.instantiate(id);
+        }
+
+        if self.events.has_subscribers() {
+            self.events.emit(StemEvent::Transdifferentiated {
+                id: self.cells.name(id).unwrap_or_default(),
+                from,
+                to: role,
+            });
+        }
+    }
+
+    /// Сигналы соседей для клеток без живого экземпляра добавляются к активности
+    fn absorb_signals(&mut self) {
+        for (id, signal) in self.inbox.drain() {
+            if let Some(activity) = self.cells.activity_of(id) {
+                self.cells.set_activity_of(id, (activity + signal).clamp(0.0, 1.0));
+            }
+        }
+    }
+
+    /// Доставить сигнал соседа клетке
+    ///
+    /// Сигналы копятся до следующего тика: живая клетка получает их
+    /// вместе со входом своей стадии, остальные - прибавкой к активности.
+    /// Возвращает false, если клетка не найдена
+    pub fn deliver_signal(&mut self, id: &str, value: f64) -> bool {
+        let Some(id) = self.cells.resolve(id) else { return false };
+        *self.inbox.entry(id).or_insert(0.0) += value;
+        true
+    }
+
+    /// Выходной поток клетки: поток живого экземпляра или активность
+    pub fn cell_flow(&self, id: &str) -> Option<f64> {
+        let id = self.cells.resolve(id)?;
+        match self.live.get(&id) {
+            Some(cell) => Some(cell.flow()),
+            None => self.cells.activity_of(id),
+        }
+    }
+
+    /// Поведение ролей: затухание, усиление хабами, подавление ингибиторами, шум
+    ///
+    /// Хабы и ингибиторы действуют только на соседей - клетки своей ткани.
+    /// Клетки вне тканей соседствуют друг с другом.
+    fn apply_role_dynamics(&mut self) {
+        if self.cells.is_empty() {
+            return;
+        }
+
+        // Поля хабов и ингибиторов считаются по активности до затухания:
+        // (клеток, поле хабов, поле ингибиторов) для каждого окружения.
+        // Слот 0 - клетки вне тканей, слот i + 1 - ткань с индексом i
+        let slot = |tissue: Option<TissueId>| tissue.map_or(0, |tissue| tissue.index() + 1);
+        let mut fields: Vec<(f64, f64, f64)> = Vec::new();
+        let mut explorers = 0;
+        let cells = self.cells.roles.iter().zip(&self.cells.activity).zip(&self.cells.memberships);
+        for ((&role, &activity), &tissue) in cells {
+            let slot = slot(tissue);
+            if slot >= fields.len() {
+                fields.resize(slot + 1, (0.0, 0.0, 0.0));
+            }
+            let field = &mut fields[slot];
+            field.0 += 1.0;
+            match role {
+                CellRole::Hub => field.1 += activity,
+                CellRole::Inhibitor => field.2 += activity,
+                CellRole::Explorer => explorers += 1,
+                _ => {}
+            }
+        }
+        let effects: Vec<(f64, f64)> = fields
+            .into_iter()
+            .map(|(cells, hubs, inhibitors)| {
+                if cells == 0.0 {
+                    return (1.0, 1.0);
+                }
+                let amplification = 1.0 + role::HUB_GAIN * hubs / cells;
+                let damping = (1.0 - role::INHIBITOR_STRENGTH * inhibitors / cells).max(0.0);
+                (amplification, damping)
+            })
+            .collect();
+        let neighbourhood: Vec<(f64, f64)> = self
+            .cells
+            .memberships
+            .iter()
+            .map(|&tissue| effects.get(slot(tissue)).copied().unwrap_or((1.0, 1.0)))
+            .collect();
+
+        self.cells.update_activity(self.tick_threads, |index, role, driven, activity| {
+            // Активность живых клеток уже получена из их потока - без затухания
+            let decay = if driven { 1.0 } else { role.activity_decay() };
+            let (amplification, damping) = neighbourhood[index];
+            let mut activity = activity * decay;
+            if role != CellRole::Hub {
+                activity *= amplification;
+            }
+            if role != CellRole::Inhibitor {
+                activity *= damping;
+            }
+            activity.clamp(0.0, 1.0)
+        });
+
+        // Исследователи вносят шум в случайные клетки
+        if explorers > 0 {
+            let len = self.cells.len();
+            let targets: Vec<usize> = (0..explorers).map(|_| self.rng.gen_range(0..len)).collect();
+
+            for index in targets {
+                let noise = self.rng.gen_range(-role::EXPLORER_NOISE..=role::EXPLORER_NOISE);
+                let activity = &mut self.cells.activity[index];
+                *activity = (*activity + noise).clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    /// Метаболизм: восполнение энергии и плата за содержание клеток
+    ///
+    /// При дефиците гибнут от голода наименее активные клетки (при
+    /// равенстве - младшие), пока содержание остальных не станет по карману
+    fn run_metabolism(&mut self) {
+        let Some(budget) = self.energy.as_mut() else { return };
+        budget.replenish();
+        let mut upkeep = budget.upkeep(&self.role_stats);
+        if budget.spend(upkeep) {
+            return;
+        }
+
+        let cells = &self.cells;
+        let mut candidates: Vec<usize> = (0..cells.len()).collect();
+        candidates.sort_by(|&a, &b| {
+            cells.activity[a]
+                .total_cmp(&cells.activity[b])
+                .then_with(|| cells.generations[b].cmp(&cells.generations[a]))
+        });
+
+        let reserve = budget.reserve();
+        let mut starved = Vec::new();
+        for index in candidates {
+            if upkeep <= reserve {
+                break;
+            }
+            upkeep -= budget.role_cost(cells.roles[index]);
+            starved.push(cells.ids[index]);
+        }
+        budget.spend(upkeep.clamp(0.0, reserve));
+
+        for id in starved {
+            if self.retire_cell(id, Some(ApoptosisCause::Starvation)).is_some() {
+                self.lifecycle_stats.record(ApoptosisCause::Starvation);
+            }
+        }
+    }
+
+    /// Клетки выделяют морфогены своих ролей, поле диффундирует и распадается
+    fn run_morphogens(&mut self) {
+        let Some(field) = self.morphogen.as_mut() else { return };
+        let emission = field.emission;
+        for (&role, position) in self.cells.roles.iter().zip(&self.cells.positions) {
+            if let Some(position) = position {
+                field.emit(role, position, emission);
+            }
+        }
+        field.step();
+    }
+
+    /// Отметить плохой выход клетки (повышает её токсичность)
+    ///
+    /// Возвращает false, если клетка не найдена
+    pub fn report_toxicity(&mut self, id: &str, amount: f64) -> bool {
+        let index = self.cells.resolve(id).and_then(|id| self.cells.index_of(id));
+        match index {
+            Some(index) => {
+                self.cells.toxicity[index] += amount.max(0.0);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Апоптоз и регенерация согласно политике жизненного цикла
+    fn run_lifecycle(&mut self) {
+        let policy = match &self.lifecycle {
+            Some(policy) => policy.clone(),
+            None => return,
+        };
+        let now = self.clock.now_millis();
+
+        // Обновляем счётчики бездействия и ищем клетки для апоптоза
+        let mut doomed: Vec<(u32, CellId, ApoptosisCause)> = Vec::new();
+        let cells = &mut self.cells;
+        for index in 0..cells.len() {
+            cells.toxicity[index] *= lifecycle::TOXICITY_DECAY;
+            // Активность клеток без живого экземпляра только затухает -
+            // бездействие считается лишь для клеток, которые ведёт фабрика
+            if cells.driven[index] && cells.activity[index] < policy.min_activity {
+                cells.idle_ticks[index] += 1;
+            } else {
+                cells.idle_ticks[index] = 0;
+            }
+
+            let cause = policy.apoptosis_cause_for(
+                cells.toxicity[index],
+                cells.birth_times[index],
+                cells.idle_ticks[index],
+                now,
+            );
+            if let Some(cause) = cause {
+                doomed.push((cells.generations[index], cells.ids[index], cause));
+            }
+        }
+        // Детерминированный порядок удаления - по порядку рождения
+        doomed.sort_by_key(|&(generation, id, _)| (generation, id));
+
+        let mut vacated = Vec::with_capacity(doomed.len());
+        for (_, id, cause) in doomed {
+            if let Some(cell) = self.retire_cell(id, Some(cause)) {
+                self.lifecycle_stats.record(cause);
+                vacated.push(cell.role);
+            }
+        }
+
+        // Восполняем пул стволовых клеток
+        self.stem_pool = (self.stem_pool + policy.stem_pool_refill).min(policy.stem_pool_capacity);
+
+        // Регенерация до гомеостатической численности
+        let target = policy
+            .target_population
+            .unwrap_or(self.cells.len() + vacated.len());
+        let mut vacated = vacated.into_iter();
+        while self.cells.len() < target && self.stem_pool > 0 {
+            // Сначала замещаем погибшие клетки той же роли
+            let position = self.birth_position(None);
+            let role = vacated.next().unwrap_or_else(|| self.choose_role_at(position, None));
+            if !self.pay_for_division(role) {
+                break;
+            }
+            self.spawn_cell_at(role, None, position);
+            self.stem_pool -= 1;
+            self.lifecycle_stats.regenerated += 1;
+        }
+    }
+
+    /// Получить количество клеток
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Получить статистику по ролям
+    pub fn role_distribution(&self) -> HashMap<CellRole, usize> {
+        self.role_stats.clone()
+    }
+
+    /// Удалить клетку (апоптоз)
+    pub fn remove_cell(&mut self, id: &str) -> Option<CellInfo> {
+        let id = self.cells.resolve(id)?;
+        self.retire_cell(id, None)
+    }
+
+    /// Удалить клетку по плотному идентификатору
+    pub fn remove_cell_by_id(&mut self, id: CellId) -> Option<CellInfo> {
+        self.retire_cell(id, None)
+    }
+
+    /// Удалить клетку и отметить её гибель в родословной
+    fn retire_cell(&mut self, id: CellId, cause: Option<ApoptosisCause>) -> Option<CellInfo> {
+        let (cell, _) = self.detach_cell(id)?;
+        if self.track_lineage {
+            self.lineage.record_death(&cell.id, self.clock.now_millis(), cause);
+        }
+        if self.events.has_subscribers() {
+            self.events.emit(StemEvent::CellRemoved {
+                id: cell.id.clone(),
+                role: cell.role,
+                cause,
+            });
+        }
+        Some(cell)
+    }
+
+    /// Изъять клетку из популяции вместе с живым экземпляром
+    fn detach_cell(&mut self, id: CellId) -> Option<(CellInfo, Option<LiveCell>)> {
+        let cell = self.cells.remove(id)?;
+        let live = self.live.remove(&id);
+        self.inbox.remove(&id);
+        self.histories.remove(&id);
+        if let Some(count) = self.role_stats.get_mut(&cell.role) {
+            *count = count.saturating_sub(1);
+        }
+        Some((cell, live))
+    }
+
+    /// Получить популяцию всех клеток
+    pub fn cells(&self) -> &CellPopulation {
+        &self.cells
+    }
+
+    /// Популяция клеток только для чтения
+    pub fn population(&self) -> &CellPopulation {
+        &self.cells
+    }
+
+    /// Получить родословную клеток
+    pub fn lineage(&self) -> &Lineage {
+        &self.lineage
+    }
+
+    /// Снять снапшот состояния организма
+    pub fn snapshot(&self) -> OrganismSnapshot {
+        let mut cells: Vec<CellInfo> = self.cells.values().collect();
+        cells.sort_by(|a, b| a.id.cmp(&b.id));
+
+        OrganismSnapshot {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            soma_version: SOMA_VERSION.to_string(),
+            taken_at: self.clock.now_millis(),
+            id: self.id.clone(),
+            generation: self.generation,
+            cells,
+            load: self.load,
+            threshold: self.threshold,
+            smoothing: self.smoothing,
+            role_stats: self.role_stats.clone(),
+            lifecycle: self.lifecycle.clone(),
+            stem_pool: self.stem_pool,
+            lifecycle_stats: self.lifecycle_stats.clone(),
+            lineage: self.lineage.clone(),
+            load_model: self.load_model.clone(),
+            energy: self.energy.clone(),
+            morphogen: self.morphogen.clone(),
+            anatomy: self.anatomy.clone(),
+            budding: self.budding.clone(),
+            children: self.children.iter().map(|c| c.snapshot()).collect(),
+            transdifferentiation: self.transdifferentiation.clone(),
+        }
+    }
+
+    /// Создать процессор из снапшота
+    ///
+    /// Стратегия выбора роли, часы и генератор - по умолчанию
+    pub fn from_snapshot(snapshot: OrganismSnapshot) -> Self {
+        let mut stem = Self::with_params(snapshot.threshold, snapshot.smoothing);
+        stem.restore(snapshot);
+        stem
+    }
+
+    /// Восстановить состояние организма из снапшота
+    ///
+    /// Стратегия выбора роли, часы и генератор процессора сохраняются
+    pub fn restore(&mut self, snapshot: OrganismSnapshot) {
+        self.id = snapshot.id;
+        self.generation = snapshot.generation;
+        self.cells = CellPopulation::from_infos_with_prefix(self.cells.name_prefix(), snapshot.cells);
+        self.load = snapshot.load;
+        self.reported_load = snapshot.load;
+        self.threshold = snapshot.threshold.clamp(0.0, 1.0);
+        self.smoothing = snapshot.smoothing.clamp(0.0, 1.0);
+        self.role_stats = snapshot.role_stats;
+        self.lifecycle = snapshot.lifecycle;
+        self.stem_pool = snapshot.stem_pool;
+        self.lifecycle_stats = snapshot.lifecycle_stats;
+        self.lineage = snapshot.lineage;
+        self.load_model = snapshot.load_model;
+        self.energy = snapshot.energy;
+        self.morphogen = snapshot.morphogen;
+        self.anatomy = snapshot.anatomy;
+        self.budding = snapshot.budding;
+        self.transdifferentiation = snapshot.transdifferentiation;
+
+        // Снапшоты без родословной - живые клетки становятся корнями
+        self.seed_lineage_roots();
+
+        // Живые клетки пересоздаются фабриками текущего реестра
+        self.live.clear();
+        self.inbox.clear();
+        self.histories.clear();
+        self.populate_live_cells();
+
+        // Почки наследуют часы, стратегию и фабрики восстановленного родителя
+        self.children.clear();
+        for child in snapshot.children {
+            let mut bud = self.new_bud();
+            bud.restore(child);
+            self.children.push(bud);
+        }
+    }
+
+    /// Сохранить снапшот организма в файл
+    pub fn save_snapshot(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        self.snapshot().save(path)
+    }
+
+    /// Загрузить организм из файла снапшота
+    pub fn load_snapshot(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        OrganismSnapshot::load(path).map(Self::from_snapshot)
+    }
+}
+
+impl fmt::Debug for StemProcessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StemProcessor")
+            .field("id", &self.id)
+            .field("generation", &self.generation)
+            .field("cell_count", &self.cells.len())
+            .field("load", &self.load)
+            .field("threshold", &self.threshold)
+            .field("smoothing", &self.smoothing)
+            .field("role_stats", &self.role_stats)
+            .field("lifecycle", &self.lifecycle)
+            .field("stem_pool", &self.stem_pool)
+            .field("lifecycle_stats", &self.lifecycle_stats)
+            .field("selector", &self.selector)
+            .field("role_demand", &self.role_demand)
+            .field("clock", &self.clock)
+            .field("registry", &self.registry)
+            .field("live_cells", &self.live.len())
+            .field("output", &self.output)
+            .field("lineage_size", &self.lineage.len())
+            .field("tick_threads", &self.tick_threads)
+            .field("events", &self.events)
+            .field("load_model", &self.load_model)
+            .field("energy", &self.energy)
+            .field("morphogen", &self.morphogen.as_ref().map(MorphogenField::dimensions))
+            .field("anatomy", &self.anatomy)
+            .field("budding", &self.budding)
+            .field("children", &self.children)
+            .field("transdifferentiation", &self.transdifferentiation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for StemProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selector::{FixedRatioSelector, MorphogenSelector};
+
+    #[test]
+    fn test_stem_processor_creation() {
+        let stem = StemProcessor::new();
+        assert_eq!(stem.generation, 0);
+        assert_eq!(stem.cell_count(), 0);
+        assert_eq!(stem.load, 0.0);
+    }
+
+    #[test]
+    fn test_cell_division() {
+        let mut stem = StemProcessor::with_params(0.3, 0.5);
+
+        // Стимулируем деление несколько раз для накопления нагрузки
+        stem.sense(0.6);
+        stem.sense(0.6);
+
+        assert_eq!(stem.generation, 1);
+        assert_eq!(stem.cell_count(), 1);
+        assert!(stem.load < 0.6); // Нагрузка должна снизиться после деления
+    }
+
+    #[test]
+    fn test_role_distribution() {
+        let mut stem = StemProcessor::with_params(0.3, 0.5);
+
+        // Создаём несколько клеток
+        for _ in 0..6 {
+            stem.sense(0.5);
+        }
+
+        let distribution = stem.role_distribution();
+        let total: usize = distribution.values().sum();
+        assert_eq!(total, stem.cell_count());
+    }
+
+    #[test]
+    fn test_cell_removal() {
+        let mut stem = StemProcessor::with_params(0.3, 0.5);
+
+        // Создаём клетку
+        stem.sense(0.6);
+        stem.sense(0.6);
+
+        assert!(stem.cell_count() > 0);
+        let initial_count = stem.cell_count();
+
+        let cell_id = stem.cells.keys().next().unwrap();
+        let removed = stem.remove_cell(&cell_id);
+
+        assert!(removed.is_some());
+        assert_eq!(stem.cell_count(), initial_count - 1);
+    }
+
+    #[test]
+    fn test_lifecycle_retires_idle_cells_and_regenerates() {
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_role_selector(FixedRatioSelector::new(&[(CellRole::Logic, 1.0)]))
+            .with_cell_factory(CellRole::Logic, gain(1.0))
+            .with_lifecycle(LifecyclePolicy::new().with_inactivity(0.01, 3));
+
+        stem.sense(0.6);
+        stem.sense(0.6);
+        assert_eq!(stem.cell_count(), 1);
+        let original = stem.cells.keys().next().unwrap();
+
+        // Без входа живая клетка замолкает
+        stem.sense(0.0);
+        for _ in 0..3 {
+            stem.tick();
+        }
+
+        // Неактивная клетка заменена новой той же роли
+        assert_eq!(stem.cell_count(), 1);
+        assert!(!stem.cells.contains_key(&original));
+        assert_eq!(stem.lifecycle_stats().retired_by_inactivity, 1);
+        assert_eq!(stem.lifecycle_stats().regenerated, 1);
+        assert_eq!(stem.role_distribution().values().sum::<usize>(), 1);
+    }
+
+    #[test]
+    fn test_lifecycle_keeps_idle_cells_without_factory() {
+        let mut stem = StemProcessor::with_params(0.3, 0.5).with_lifecycle(LifecyclePolicy::default());
+        for _ in 0..10 {
+            stem.sense(0.9);
+        }
+        let cells = stem.cell_count();
+        assert!(cells > 1);
+
+        // Активность клеток без фабрики затухает, но это не бездействие
+        for _ in 0..2 * lifecycle::DEFAULT_MAX_IDLE_TICKS {
+            stem.tick();
+        }
+        assert_eq!(stem.cell_count(), cells);
+        assert_eq!(stem.lifecycle_stats().retired_by_inactivity, 0);
+    }
+
+    #[test]
+    fn test_lifecycle_toxicity_and_empty_pool() {
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_lifecycle(LifecyclePolicy::new().with_stem_pool(0, 0));
+
+        stem.sense(0.6);
+        stem.sense(0.6);
+        let id = stem.cells.keys().next().unwrap();
+
+        assert!(stem.report_toxicity(&id, 5.0));
+        stem.tick();
+
+        // Пул пуст - регенерации нет
+        assert_eq!(stem.cell_count(), 0);
+        assert_eq!(stem.lifecycle_stats().retired_by_toxicity, 1);
+        assert_eq!(stem.lifecycle_stats().regenerated, 0);
+    }
+
+    #[test]
+    fn test_lifecycle_homeostatic_target() {
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_lifecycle(LifecyclePolicy::new().with_target_population(4));
+
+        stem.tick();
+
+        assert_eq!(stem.cell_count(), 4);
+        assert_eq!(stem.stem_pool(), lifecycle::DEFAULT_STEM_POOL_CAPACITY - 4);
+    }
+
+    #[test]
+    fn test_custom_role_selector() {
+        use crate::selector::FixedRatioSelector;
+
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_role_selector(FixedRatioSelector::new(&[(CellRole::Motor, 1.0)]));
+
+        for _ in 0..6 {
+            stem.sense(0.5);
+        }
+
+        let distribution = stem.role_distribution();
+        assert!(stem.cell_count() > 0);
+        assert_eq!(distribution.get(&CellRole::Motor), Some(&stem.cell_count()));
+    }
+
+    #[test]
+    fn test_role_demand_drives_pressure_selector() {
+        use crate::selector::PressureGradientSelector;
+
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_role_selector(PressureGradientSelector::new());
+        stem.set_role_demand(CellRole::Logic, 1.0);
+
+        stem.sense(0.6);
+        stem.sense(0.6);
+
+        assert_eq!(stem.role_distribution().get(&CellRole::Logic), Some(&1));
+    }
+
+    /// Процессор с одной клеткой каждой из заданных ролей и активностью 0.5
+    fn organism(roles: &[CellRole]) -> StemProcessor {
+        let mut stem = StemProcessor::new().with_seed(7);
+        for &role in roles {
+            let id = stem.spawn_cell(role);
+            stem.cells.set_activity_of(id, 0.5);
+        }
+        stem
+    }
+
+    fn activity_of(stem: &StemProcessor, role: CellRole) -> f64 {
+        stem.cells.values().find(|c| c.role == role).unwrap().activity
+    }
+
+    #[test]
+    fn test_memory_retains_activity() {
+        let mut stem = organism(&[CellRole::Logic, CellRole::Memory]);
+        stem.tick();
+
+        assert!(activity_of(&stem, CellRole::Memory) > activity_of(&stem, CellRole::Logic));
+    }
+
+    #[test]
+    fn test_hub_amplifies_and_inhibitor_damps() {
+        let mut plain = organism(&[CellRole::Logic, CellRole::Motor]);
+        let mut hubbed = organism(&[CellRole::Logic, CellRole::Hub]);
+        let mut inhibited = organism(&[CellRole::Logic, CellRole::Inhibitor]);
+
+        plain.tick();
+        hubbed.tick();
+        inhibited.tick();
+
+        let baseline = activity_of(&plain, CellRole::Logic);
+        assert!(activity_of(&hubbed, CellRole::Logic) > baseline);
+        assert!(activity_of(&inhibited, CellRole::Logic) < baseline);
+    }
+
+    #[test]
+    fn test_hub_and_inhibitor_affect_only_their_tissue() {
+        let mut plain = organism(&[CellRole::Logic]);
+        plain.tick();
+        let baseline = activity_of(&plain, CellRole::Logic);
+
+        for role in [CellRole::Hub, CellRole::Inhibitor] {
+            let mut stem = organism(&[CellRole::Logic, role]);
+            let tissue = stem.create_tissue("cortex", 0.9);
+            let neighbour = stem.cells.values().find(|c| c.role == role).unwrap().id;
+            assert!(stem.assign_cell(&neighbour, Some(tissue)));
+            stem.tick();
+
+            // Логическая клетка вне ткани соседом не является
+            assert_eq!(activity_of(&stem, CellRole::Logic), baseline);
+        }
+    }
+
+    #[test]
+    fn test_explorer_injects_noise() {
+        let mut stem = StemProcessor::new().with_seed(3);
+        for _ in 0..4 {
+            stem.spawn_cell(CellRole::Explorer);
+        }
+
+        for _ in 0..10 {
+            stem.tick();
+        }
+
+        // Без шума активность осталась бы нулевой
+        assert!(stem.cells.values().any(|c| c.activity > 0.0));
+    }
+
+    #[test]
+    fn test_lifecycle_max_age_with_manual_clock() {
+        use crate::clock::ManualClock;
+
+        let clock = ManualClock::new(1_000);
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_clock(clock.shared())
+            .with_lifecycle(
+                LifecyclePolicy::new()
+                    .with_max_age(Some(5_000))
+                    .with_inactivity(0.0, 0)
+                    .with_stem_pool(0, 0),
+            );
+
+        stem.sense(0.6);
+        stem.sense(0.6);
+        let id = stem.cells.keys().next().unwrap();
+
+        clock.advance(4_999);
+        assert_eq!(stem.cell_age_millis(&id), Some(4_999));
+        stem.tick();
+        assert_eq!(stem.cell_count(), 1);
+
+        clock.advance(1);
+        stem.tick();
+        assert_eq!(stem.cell_count(), 0);
+        assert_eq!(stem.lifecycle_stats().retired_by_age, 1);
+    }
+
+    /// Тестовая клетка - умножает вход на коэффициент
+    struct Gain {
+        gain: f64,
+        value: f64,
+    }
+
+    impl crate::Cell for Gain {
+        fn sense(&mut self, input: f64) {
+            self.value = input * self.gain;
+        }
+
+        fn align(&mut self) {}
+
+        fn flow(&self) -> f64 {
+            self.value
+        }
+    }
+
+    fn gain(gain: f64) -> impl Fn(&CellInfo) -> LiveCell + Send + Sync {
+        move |_| Box::new(Gain { gain, value: 0.0 })
+    }
+
+    #[test]
+    fn test_live_cells_route_signal_through_stages() {
+        let mut stem = StemProcessor::with_params(1.0, 0.5)
+            .with_cell_factory(CellRole::Sensor, gain(0.5))
+            .with_cell_factory(CellRole::Logic, gain(1.0))
+            .with_cell_factory(CellRole::Motor, gain(2.0));
+        let sensor = stem.spawn_cell(CellRole::Sensor);
+        let logic = stem.spawn_cell(CellRole::Logic);
+        stem.spawn_cell(CellRole::Motor);
+        assert_eq!(stem.live_cell_count(), 3);
+
+        stem.sense(0.8);
+        stem.tick();
+
+        // 0.8 -> Sensor 0.4 -> Logic 0.4 -> Motor 0.8
+        assert!((stem.output() - 0.8).abs() < 1e-9);
+        assert!((stem.cells.activity_of(sensor).unwrap() - 0.4).abs() < 1e-9);
+        let logic_name = stem.cells.name(logic).unwrap();
+        assert!((stem.live_cell(&logic_name).unwrap().flow() - 0.4).abs() < 1e-9);
+
+        stem.remove_cell_by_id(logic);
+        assert_eq!(stem.live_cell_count(), 2);
+    }
+
+    #[test]
+    fn test_logic_cell_seeing_raw_input_becomes_sensor() {
+        let mut stem = StemProcessor::with_params(1.0, 0.5)
+            .with_cell_factory(CellRole::Sensor, gain(1.0))
+            .with_cell_factory(CellRole::Logic, gain(1.0))
+            .with_cell_factory(CellRole::Motor, gain(1.0))
+            .with_transdifferentiation(
+                TransdifferentiationPolicy::new().with_window(3).with_mismatch_ratio(1.0),
+            );
+        let first = stem.spawn_cell(CellRole::Logic);
+        let second = stem.spawn_cell(CellRole::Logic);
+        stem.spawn_cell(CellRole::Motor);
+        stem.sense(0.5);
+        let events = stem.event_channel();
+
+        for _ in 0..2 {
+            stem.tick();
+        }
+        assert_eq!(stem.cells.role_of(first), Some(CellRole::Logic));
+
+        // Третий тик заполняет историю: сырой вход только у логики, меняет роль старшая
+        stem.tick();
+        assert_eq!(stem.cells.role_of(first), Some(CellRole::Sensor));
+        assert_eq!(stem.cells.role_of(second), Some(CellRole::Logic));
+        assert_eq!(stem.live_cell_count(), 3);
+        let changed: Vec<_> = events
+            .try_iter()
+            .filter(|e| matches!(e, StemEvent::Transdifferentiated { .. }))
+            .collect();
+        assert!(matches!(
+            changed.as_slice(),
+            [StemEvent::Transdifferentiated { from: CellRole::Logic, to: CellRole::Sensor, .. }]
+        ));
+
+        // Теперь логика получает поток сенсора и сохраняет роль
+        for _ in 0..5 {
+            stem.tick();
+        }
+        let distribution = stem.role_distribution();
+        assert_eq!(distribution[&CellRole::Sensor], 1);
+        assert_eq!(distribution[&CellRole::Logic], 1);
+        assert_eq!(distribution[&CellRole::Motor], 1);
+        assert!(events.try_iter().next().is_none());
+    }
+
+    #[test]
+    fn test_delivered_signal_joins_next_sense() {
+        let mut stem = StemProcessor::with_params(1.0, 0.5).with_cell_factory(CellRole::Sensor, gain(1.0));
+        let sensor = stem.spawn_cell(CellRole::Sensor);
+        let memory = stem.spawn_cell(CellRole::Memory);
+        let (sensor, memory) = (stem.cells.name(sensor).unwrap(), stem.cells.name(memory).unwrap());
+
+        assert!(stem.deliver_signal(&sensor, 0.25));
+        assert!(stem.deliver_signal(&memory, 0.5));
+        assert!(!stem.deliver_signal("missing", 1.0));
+        stem.sense(0.5);
+        stem.tick();
+
+        // Живая клетка получает сигнал вместе со входом, прочие - к активности
+        assert!((stem.cell_flow(&sensor).unwrap() - 0.75).abs() < 1e-9);
+        let decay = CellRole::Memory.activity_decay();
+        assert!((stem.cell_flow(&memory).unwrap() - 0.5 * decay).abs() < 1e-9);
+
+        // Сигнал расходуется за один тик
+        stem.tick();
+        assert!((stem.cell_flow(&sensor).unwrap() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_missing_stage_passes_input_through() {
+        let mut stem = StemProcessor::with_params(1.0, 0.5);
+        let meta = stem.spawn_cell(CellRole::Sensor);
+        stem.spawn_cell(CellRole::Motor);
+
+        // Фабрика, зарегистрированная позже, оживляет существующие клетки
+        stem.register_cell_factory(CellRole::Motor, gain(1.0));
+        assert_eq!(stem.live_cell_count(), 1);
+        assert!(stem.live_cell(&stem.cells.name(meta).unwrap()).is_none());
+
+        stem.sense(0.3);
+        stem.tick();
+        assert!((stem.output() - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_lineage_tracks_divisions_and_deaths() {
+        let mut stem = StemProcessor::with_params(0.3, 0.5);
+        stem.sense(0.8);
+        let first = stem.cells.keys().next().unwrap();
+        stem.cells.set_activity(&first, 0.9);
+
+        stem.sense(0.8);
+        stem.sense(0.8);
+        assert_eq!(stem.cell_count(), 3);
+
+        // Обе новые клетки - потомки самой активной
+        let lineage = stem.lineage();
+        assert_eq!(lineage.roots().len(), 1);
+        assert_eq!(lineage.descendants(&first).len(), 2);
+
+        stem.remove_cell(&first);
+        let node = stem.lineage().get(&first).unwrap();
+        assert!(!node.is_alive());
+        assert_eq!(stem.lineage().subtree_stats(&first).unwrap().alive, 2);
+    }
+
+    #[test]
+    fn test_restore_keeps_disabled_lineage_tracking() {
+        let mut stem = StemProcessor::with_params(0.3, 0.0);
+        stem.sense(0.9);
+        stem.sense(0.9);
+        assert_eq!(stem.lineage().len(), 2);
+
+        let mut restored = StemProcessor::new().with_lineage_tracking(false);
+        restored.restore(stem.snapshot());
+        restored.sense(0.9);
+        assert_eq!(restored.cell_count(), 3);
+        assert_eq!(restored.lineage().len(), 2);
+    }
+
+    #[test]
+    fn test_parallel_tick_matches_sequential() {
+        use crate::config::population::PARALLEL_MIN_CELLS;
+
+        let build = |threads: usize| {
+            let mut stem = StemProcessor::new()
+                .with_seed(11)
+                .with_parallel_tick(threads)
+                .with_lineage_tracking(false);
+            for i in 0..PARALLEL_MIN_CELLS + 100 {
+                let id = stem.spawn_cell(CellRole::ALL[i % CellRole::ALL.len()]);
+                stem.cells.set_activity_of(id, (i % 10) as f64 / 10.0);
+            }
+            stem.tick();
+            stem
+        };
+
+        let sequential = build(1);
+        let parallel = build(4);
+        assert_eq!(sequential.cells.activities(), parallel.cells.activities());
+        assert!(sequential.lineage().is_empty());
+    }
+
+    #[test]
+    fn test_cell_ids_are_stable_across_removals() {
+        let mut stem = organism(&[CellRole::Sensor, CellRole::Logic, CellRole::Motor]);
+        let names: Vec<String> = stem.cells.keys().collect();
+
+        stem.remove_cell(&names[0]);
+        assert!(stem.cells.get(&names[0]).is_none());
+        assert_eq!(stem.cells.get(&names[2]).unwrap().role, CellRole::Motor);
+        assert!(stem.report_toxicity(&names[1], 0.5));
+    }
+
+    #[test]
+    fn test_events_report_morphogenesis() {
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_lifecycle(LifecyclePolicy::new().with_stem_pool(0, 0));
+        let events = stem.event_channel();
+
+        stem.sense(0.8);
+        let id = stem.cells.keys().next().unwrap();
+        stem.report_toxicity(&id, 5.0);
+        stem.tick();
+
+        let events: Vec<StemEvent> = events.try_iter().collect();
+        assert!(matches!(
+            events[0],
+            StemEvent::ThresholdCrossed { rising: true, .. }
+        ));
+        assert!(matches!(
+            &events[1],
+            StemEvent::CellDivided { role: CellRole::Sensor, parent: None, .. }
+        ));
+        // После деления нагрузка 0.2 - существенное изменение от 0.0
+        assert!(matches!(events[2], StemEvent::LoadChanged { previous, .. } if previous == 0.0));
+        assert_eq!(
+            events[3],
+            StemEvent::CellRemoved {
+                id,
+                role: CellRole::Sensor,
+                cause: Some(ApoptosisCause::Toxicity),
+            }
+        );
+    }
+
+    #[test]
+    fn test_callback_subscription() {
+        use std::sync::{Arc, Mutex};
+
+        let mut stem = StemProcessor::with_params(0.3, 0.5);
+        let divisions = Arc::new(Mutex::new(0));
+        let counter = divisions.clone();
+        let sub = stem.subscribe(move |event| {
+            if let StemEvent::CellDivided { .. } = event {
+                *counter.lock().unwrap() += 1;
+            }
+        });
+
+        stem.sense(0.8);
+        stem.sense(0.8);
+        assert!(stem.unsubscribe(sub));
+        stem.sense(0.8);
+
+        assert_eq!(*divisions.lock().unwrap(), 2);
+        assert_eq!(stem.cell_count(), 3);
+    }
+
+    #[test]
+    fn test_vector_load_targets_pressured_role() {
+        let model = LoadModel::new(0.5, 0.0).with_dimension(LoadDimension::Output, 0.9, 0.0);
+        let mut stem = StemProcessor::new().with_load_model(model);
+
+        // Вход перегружен, выход ниже своего (более высокого) порога
+        stem.sense_vector(LoadVector::new(0.9, 0.2, 0.8));
+        stem.sense_vector(LoadVector::new(0.9, 0.2, 0.8));
+
+        let distribution = stem.role_distribution();
+        assert_eq!(distribution.get(&CellRole::Sensor), Some(&2));
+        assert_eq!(stem.cell_count(), 2);
+        assert_eq!(stem.role_demand()[&CellRole::Motor], 0.8);
+        assert_eq!(stem.load_vector().unwrap().input, 0.45);
+        assert_eq!(stem.load, 0.8);
+    }
+
+    #[test]
+    fn test_vector_load_events_and_snapshot() {
+        let mut stem = StemProcessor::with_params(0.5, 0.0);
+        let events = stem.event_channel();
+
+        stem.sense_vector(LoadVector::new(0.0, 0.0, 0.9));
+        let events: Vec<StemEvent> = events.try_iter().collect();
+        assert!(matches!(
+            events[0],
+            StemEvent::DimensionThresholdCrossed { dimension: LoadDimension::Output, rising: true, .. }
+        ));
+        assert!(matches!(
+            &events[1],
+            StemEvent::CellDivided { role: CellRole::Motor, .. }
+        ));
+
+        let restored = StemProcessor::from_snapshot(stem.snapshot());
+        assert_eq!(restored.load_model(), stem.load_model());
+    }
+
+    #[test]
+    fn test_division_refused_without_energy() {
+        let budget = EnergyBudget::new(1.0, 0.0).with_division_cost(1.0);
+        let mut stem = StemProcessor::with_params(0.3, 0.0).with_energy(budget);
+        let events = stem.event_channel();
+
+        stem.sense(0.9);
+        stem.sense(0.9);
+
+        assert_eq!(stem.cell_count(), 1);
+        assert_eq!(stem.energy().unwrap().reserve(), 0.0);
+        assert!(events
+            .try_iter()
+            .any(|e| matches!(e, StemEvent::DivisionRefused { cost, .. } if cost == 1.0)));
+    }
+
+    #[test]
+    fn test_starvation_retires_least_active_cells() {
+        let budget = EnergyBudget::new(1.0, 0.5)
+            .with_reserve(0.0)
+            .with_role_cost(CellRole::Logic, 0.25);
+        let mut stem = organism(&[CellRole::Logic; 4]).with_energy(budget);
+        let ids: Vec<CellId> = stem.cells.ids().to_vec();
+        for (i, id) in ids.iter().enumerate() {
+            stem.cells.set_activity_of(*id, 0.2 * (i + 1) as f64);
+        }
+
+        // Восполнения 0.5 хватает на содержание только двух клеток
+        stem.tick();
+
+        assert_eq!(stem.cell_count(), 2);
+        assert!(!stem.cells.contains(ids[0]) && !stem.cells.contains(ids[1]));
+        assert_eq!(stem.lifecycle_stats().retired_by_starvation, 2);
+        assert_eq!(stem.energy().unwrap().reserve(), 0.0);
+    }
+
+    #[test]
+    fn test_children_are_placed_near_parent() {
+        let field = MorphogenField::planar(20, 20, 1.0);
+        let mut stem = StemProcessor::with_params(0.3, 0.0)
+            .with_seed(3)
+            .with_morphogen_field(field);
+
+        stem.sense(0.9);
+        let root = stem.cells.ids()[0];
+        stem.cells.set_activity_of(root, 1.0);
+        stem.sense(0.9);
+
+        let child = stem.cells.ids()[1];
+        let parent_pos = stem.cells.position_of(root).unwrap();
+        let child_pos = stem.cells.position_of(child).unwrap();
+        assert!(parent_pos.distance(&stem.morphogen_field().unwrap().center()) <= 2f64.sqrt());
+        assert!(parent_pos.distance(&child_pos) <= 2f64.sqrt() * morphogen::CHILD_OFFSET);
+        assert_eq!(child_pos.z, 0.0);
+
+        let restored = StemProcessor::from_snapshot(stem.snapshot());
+        assert_eq!(restored.cells.position_of(child), Some(child_pos));
+        assert_eq!(restored.morphogen_field(), stem.morphogen_field());
+    }
+
+    #[test]
+    fn test_morphogens_steer_differentiation() {
+        // Одна ячейка - все клетки видят общую концентрацию
+        let field = MorphogenField::planar(1, 1, 1.0).with_diffusion(0.0).with_decay(0.0);
+        let mut stem = StemProcessor::with_params(0.3, 0.0)
+            .with_morphogen_field(field)
+            .with_role_selector(MorphogenSelector::new());
+
+        stem.sense(0.9);
+        stem.tick();
+        stem.sense(0.9);
+        stem.tick();
+        stem.sense(0.9);
+
+        // Латеральное ингибирование: каждая новая роль отсутствует в поле
+        let roles: Vec<CellRole> = stem.cells.values().map(|c| c.role).collect();
+        assert_eq!(stem.cell_count(), 3);
+        for role in [CellRole::Sensor, CellRole::Logic, CellRole::Motor] {
+            assert!(roles.contains(&role));
+        }
+        assert_eq!(stem.morphogen_field().unwrap().total(CellRole::Sensor), 2.0);
+    }
+
+    #[test]
+    fn test_tissue_stimulation_divides_within_tissue() {
+        let mut stem = organism(&[CellRole::Sensor, CellRole::Logic]);
+        stem.smoothing = 0.0;
+        let retina = stem.create_tissue("retina", 0.4);
+        let eye = stem.create_organ("eye", 0.5);
+        assert!(stem.attach_tissue(retina, eye));
+
+        let sensor = stem.cells.keys().find(|id| stem.cells.get(id).unwrap().role == CellRole::Sensor).unwrap();
+        assert!(stem.assign_cell(&sensor, Some(retina)));
+
+        for _ in 0..3 {
+            stem.stimulate_organ(eye, 0.9);
+        }
+
+        // Ткань растёт, процессор в целом не стимулировался
+        let metrics = stem.tissue_metrics(retina).unwrap();
+        assert_eq!(metrics.cells, 4);
+        assert_eq!(stem.tissue_cells(retina).len(), 4);
+        assert_eq!(stem.cell_count(), 5);
+        assert_eq!(stem.load, 0.0);
+        // Баланс внутри ткани: Sensor уже есть, дальше Logic и Motor
+        assert_eq!(metrics.role_mix[&CellRole::Logic], 1);
+        assert_eq!(metrics.role_mix[&CellRole::Motor], 1);
+
+        let restored = StemProcessor::from_snapshot(stem.snapshot());
+        assert_eq!(restored.tissue_metrics(retina), stem.tissue_metrics(retina));
+        assert_eq!(restored.organ_metrics(eye).unwrap().cells, 4);
+    }
+
+    #[test]
+    fn test_budding_moves_youngest_cells_to_child() {
+        let mut stem = StemProcessor::with_params(0.3, 0.0)
+            .with_seed(5)
+            .with_cell_factory(CellRole::Logic, gain(1.0))
+            .with_energy(EnergyBudget::new(100.0, 0.0).with_division_cost(0.0))
+            .with_budding(BuddingPolicy::new(4).with_share(0.5).with_max_children(1));
+        let events = stem.event_channel();
+
+        for _ in 0..5 {
+            stem.sense(0.9);
+        }
+
+        // Пятая клетка превысила предел: три младших клетки ушли в почку
+        assert_eq!(stem.children().len(), 1);
+        assert_eq!(stem.cell_count(), 2);
+        assert_eq!(stem.total_cell_count(), 5);
+        assert!(stem.cells.generations().iter().all(|&g| g <= 2));
+
+        let child = &stem.children()[0];
+        assert_eq!(child.id, format!("{}.1", stem.id));
+        assert_eq!(child.threshold, 0.3);
+        assert_eq!(child.energy().unwrap().reserve(), 50.0);
+        assert_eq!(stem.energy().unwrap().reserve(), 50.0);
+        assert_eq!((child.live_cell_count(), stem.live_cell_count()), (1, 1));
+        assert_eq!(child.lineage().roots().len(), 3);
+        let budded = events.try_iter().find_map(|e| match e {
+            StemEvent::Budded { cells, .. } => Some(cells),
+            _ => None,
+        });
+        assert_eq!(budded.unwrap().len(), 3);
+
+        // Почка получает тот же вход, её нагрузка смешивается со входом родителя
+        stem.sense(0.0);
+        assert_eq!(stem.children()[0].load, 0.0);
+        let restored = StemProcessor::from_snapshot(stem.snapshot());
+        assert_eq!(restored.total_cell_count(), stem.total_cell_count());
+        assert_eq!(restored.children()[0].id, stem.children()[0].id);
+    }
+
+    #[test]
+    fn test_bud_records_transfer_in_parent_lineage() {
+        let mut stem = StemProcessor::with_params(0.3, 0.0).with_seed(3);
+        for _ in 0..3 {
+            stem.sense(0.9);
+        }
+        let child = stem.bud().unwrap();
+        let moved: Vec<String> = child.cells().values().map(|cell| cell.id).collect();
+        assert!(!moved.is_empty());
+
+        let lineage = stem.lineage();
+        for id in &moved {
+            let node = lineage.get(id).unwrap();
+            assert!(!node.is_alive());
+            assert_eq!(node.death_cause, Some(ApoptosisCause::Budded));
+        }
+        assert_eq!(lineage.nodes().filter(|node| node.is_alive()).count(), stem.cell_count());
+        assert_eq!(stem.lifecycle_stats().total_retired(), 0);
+    }
+
+    #[test]
+    fn test_bud_cell_names_are_unique() {
+        fn all_names(stem: &StemProcessor) -> Vec<String> {
+            let mut names: Vec<String> = stem.cells().keys().collect();
+            stem.children().iter().for_each(|child| names.extend(all_names(child)));
+            names
+        }
+
+        let mut stem = StemProcessor::with_params(0.3, 0.0).with_seed(3);
+        stem.sense(0.9);
+        stem.sense(0.9);
+        assert!(stem.bud().is_some());
+
+        // Родитель и почка делятся с одинаковых поколений и слотов
+        for _ in 0..3 {
+            stem.sense(0.9);
+        }
+        let mut names = all_names(&stem);
+        assert_eq!(names.len(), 8);
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), 8);
+
+        let restored = StemProcessor::from_snapshot(stem.snapshot());
+        let mut restored_names = all_names(&restored);
+        restored_names.sort();
+        assert_eq!(restored_names, names);
+
+        // Векторная нагрузка тоже доходит до почки
+        stem.sense_vector(LoadVector::uniform(0.5));
+        assert!(stem.children()[0].load_vector().is_some());
+    }
+
+    #[test]
+    fn test_cell_info_age() {
+        use crate::clock::ManualClock;
+
+        let clock = ManualClock::new(1_000);
+        let cell = CellInfo::born_at("test".to_string(), CellRole::Sensor, 1, clock.now_millis());
+        assert_eq!(cell.age_at(1_000), 0);
+
+        clock.advance(10);
+        assert_eq!(cell.age_at(clock.now_millis()), 10);
+        assert_eq!(cell.age_at(500), 0);
     }
 }
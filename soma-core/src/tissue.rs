@@ -0,0 +1,285 @@
+//! Ткани и органы - иерархическая группировка клеток
+//!
+//! Клетки принадлежат тканям, ткани объединяются в органы. У каждого
+//! уровня свой порог и сводные метрики (нагрузка, активность, состав
+//! ролей). Ткань можно стимулировать целиком: её нагрузка сглаживается
+//! отдельно от нагрузки процессора, а деление при превышении порога
+//! происходит внутри ткани.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::population::CellPopulation;
+use crate::stem::CellRole;
+
+/// Идентификатор ткани
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TissueId(u32);
+
+impl TissueId {
+    /// Индекс ткани
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Идентификатор органа
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct OrganId(u32);
+
+impl OrganId {
+    /// Индекс органа
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Ткань - группа клеток с общей нагрузкой
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tissue {
+    /// Имя ткани
+    pub name: String,
+    /// Орган, в который входит ткань
+    pub organ: Option<OrganId>,
+    /// Порог нагрузки для деления внутри ткани
+    pub threshold: f64,
+    /// Коэффициент сглаживания нагрузки ткани
+    pub smoothing: f64,
+    /// Сглаженная нагрузка ткани
+    pub load: f64,
+}
+
+impl Tissue {
+    /// Учесть стимул и вернуть true, если нагрузка превысила порог
+    pub fn sense(&mut self, activity: f64) -> bool {
+        self.load = self.load * self.smoothing + activity * (1.0 - self.smoothing);
+        self.load > self.threshold
+    }
+}
+
+/// Орган - группа тканей
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Organ {
+    /// Имя органа
+    pub name: String,
+    /// Порог средней нагрузки тканей органа
+    pub threshold: f64,
+    /// Ткани органа
+    pub tissues: Vec<TissueId>,
+}
+
+/// Сводные метрики ткани или органа
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupMetrics {
+    /// Количество клеток
+    pub cells: usize,
+    /// Нагрузка (для органа - средняя нагрузка тканей)
+    pub load: f64,
+    /// Порог уровня
+    pub threshold: f64,
+    /// Средняя активность клеток
+    pub mean_activity: f64,
+    /// Количество клеток по ролям
+    pub role_mix: HashMap<CellRole, usize>,
+    /// Превышен ли порог
+    pub overloaded: bool,
+}
+
+/// Анатомия организма - реестр тканей и органов
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Anatomy {
+    tissues: Vec<Tissue>,
+    organs: Vec<Organ>,
+}
+
+impl Anatomy {
+    /// Создать пустую анатомию
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Проверить, нет ли тканей и органов
+    pub fn is_empty(&self) -> bool {
+        self.tissues.is_empty() && self.organs.is_empty()
+    }
+
+    /// Добавить ткань
+    pub fn add_tissue(&mut self, name: &str, threshold: f64, smoothing: f64) -> TissueId {
+        self.tissues.push(Tissue {
+            name: name.to_string(),
+            organ: None,
+            threshold: threshold.clamp(0.0, 1.0),
+            smoothing: smoothing.clamp(0.0, 1.0),
+            load: 0.0,
+        });
+        TissueId((self.tissues.len() - 1) as u32)
+    }
+
+    /// Добавить орган
+    pub fn add_organ(&mut self, name: &str, threshold: f64) -> OrganId {
+        self.organs.push(Organ {
+            name: name.to_string(),
+            threshold: threshold.clamp(0.0, 1.0),
+            tissues: Vec::new(),
+        });
+        OrganId((self.organs.len() - 1) as u32)
+    }
+
+    /// Включить ткань в орган (ткань покидает прежний орган)
+    ///
+    /// Возвращает false, если ткань или орган не найдены
+    pub fn attach(&mut self, tissue: TissueId, organ: OrganId) -> bool {
+        if organ.index() >= self.organs.len() {
+            return false;
+        }
+        let Some(entry) = self.tissues.get_mut(tissue.index()) else { return false };
+
+        if let Some(previous) = entry.organ.replace(organ) {
+            self.organs[previous.index()].tissues.retain(|t| *t != tissue);
+        }
+        self.organs[organ.index()].tissues.push(tissue);
+        true
+    }
+
+    /// Получить ткань
+    pub fn tissue(&self, id: TissueId) -> Option<&Tissue> {
+        self.tissues.get(id.index())
+    }
+
+    pub(crate) fn tissue_mut(&mut self, id: TissueId) -> Option<&mut Tissue> {
+        self.tissues.get_mut(id.index())
+    }
+
+    /// Получить орган
+    pub fn organ(&self, id: OrganId) -> Option<&Organ> {
+        self.organs.get(id.index())
+    }
+
+    /// Все ткани
+    pub fn tissues(&self) -> impl Iterator<Item = (TissueId, &Tissue)> {
+        self.tissues.iter().enumerate().map(|(i, t)| (TissueId(i as u32), t))
+    }
+
+    /// Все органы
+    pub fn organs(&self) -> impl Iterator<Item = (OrganId, &Organ)> {
+        self.organs.iter().enumerate().map(|(i, o)| (OrganId(i as u32), o))
+    }
+
+    /// Найти ткань по имени
+    pub fn find_tissue(&self, name: &str) -> Option<TissueId> {
+        self.tissues().find(|(_, t)| t.name == name).map(|(id, _)| id)
+    }
+
+    /// Найти орган по имени
+    pub fn find_organ(&self, name: &str) -> Option<OrganId> {
+        self.organs().find(|(_, o)| o.name == name).map(|(id, _)| id)
+    }
+
+    /// Метрики ткани
+    pub fn tissue_metrics(&self, id: TissueId, cells: &CellPopulation) -> Option<GroupMetrics> {
+        let tissue = self.tissue(id)?;
+        let mut metrics = member_metrics(cells, |member| member == Some(id));
+        metrics.load = tissue.load;
+        metrics.threshold = tissue.threshold;
+        metrics.overloaded = tissue.load > tissue.threshold;
+        Some(metrics)
+    }
+
+    /// Метрики органа
+    pub fn organ_metrics(&self, id: OrganId, cells: &CellPopulation) -> Option<GroupMetrics> {
+        let organ = self.organ(id)?;
+        let mut metrics = member_metrics(cells, |member| member.is_some_and(|t| organ.tissues.contains(&t)));
+
+        let loads: Vec<f64> = organ
+            .tissues
+            .iter()
+            .filter_map(|t| self.tissue(*t))
+            .map(|t| t.load)
+            .collect();
+        metrics.load = if loads.is_empty() { 0.0 } else { loads.iter().sum::<f64>() / loads.len() as f64 };
+        metrics.threshold = organ.threshold;
+        metrics.overloaded = metrics.load > organ.threshold;
+        Some(metrics)
+    }
+}
+
+/// Состав и активность клеток, отобранных по принадлежности к ткани
+fn member_metrics<F>(cells: &CellPopulation, is_member: F) -> GroupMetrics
+where
+    F: Fn(Option<TissueId>) -> bool,
+{
+    let mut count = 0;
+    let mut activity = 0.0;
+    let mut role_mix = HashMap::new();
+    for index in 0..cells.len() {
+        if is_member(cells.memberships[index]) {
+            count += 1;
+            activity += cells.activity[index];
+            *role_mix.entry(cells.roles[index]).or_insert(0) += 1;
+        }
+    }
+
+    GroupMetrics {
+        cells: count,
+        load: 0.0,
+        threshold: 0.0,
+        mean_activity: if count > 0 { activity / count as f64 } else { 0.0 },
+        role_mix,
+        overloaded: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attach_moves_tissue_between_organs() {
+        let mut anatomy = Anatomy::new();
+        let skin = anatomy.add_tissue("skin", 0.5, 0.5);
+        let eye = anatomy.add_organ("eye", 0.5);
+        let ear = anatomy.add_organ("ear", 0.5);
+
+        assert!(anatomy.attach(skin, eye));
+        assert!(anatomy.attach(skin, ear));
+        assert!(anatomy.organ(eye).unwrap().tissues.is_empty());
+        assert_eq!(anatomy.organ(ear).unwrap().tissues, vec![skin]);
+        assert_eq!(anatomy.tissue(skin).unwrap().organ, Some(ear));
+        assert_eq!(anatomy.find_tissue("skin"), Some(skin));
+    }
+
+    #[test]
+    fn test_metrics_aggregate_members() {
+        let mut anatomy = Anatomy::new();
+        let a = anatomy.add_tissue("a", 0.5, 0.0);
+        let b = anatomy.add_tissue("b", 0.5, 0.0);
+        let organ = anatomy.add_organ("organ", 0.5);
+        anatomy.attach(a, organ);
+        anatomy.attach(b, organ);
+
+        let mut cells = CellPopulation::new();
+        for (role, tissue, activity) in [
+            (CellRole::Sensor, a, 0.2),
+            (CellRole::Sensor, a, 0.4),
+            (CellRole::Motor, b, 1.0),
+        ] {
+            let id = cells.insert(role, 1, 0);
+            cells.set_tissue(id, Some(tissue));
+            cells.set_activity_of(id, activity);
+        }
+        cells.insert(CellRole::Logic, 1, 0);
+
+        anatomy.tissue_mut(a).unwrap().sense(0.8);
+        let metrics = anatomy.tissue_metrics(a, &cells).unwrap();
+        assert_eq!(metrics.cells, 2);
+        assert!((metrics.mean_activity - 0.3).abs() < 1e-9);
+        assert!(metrics.overloaded);
+
+        let metrics = anatomy.organ_metrics(organ, &cells).unwrap();
+        assert_eq!(metrics.cells, 3);
+        assert_eq!(metrics.role_mix[&CellRole::Motor], 1);
+        assert_eq!(metrics.load, 0.4);
+        assert!(!metrics.overloaded);
+    }
+}
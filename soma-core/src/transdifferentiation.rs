@@ -0,0 +1,190 @@
+//! Трансдифференциация - смена роли клетки по истории её входов
+//!
+//! Роль, выбранная при делении, может перестать подходить клетке после
+//! смены нагрузки: например, клетка Logic, до которой доходит только
+//! сырой внешний вход, фактически работает сенсором. Живые клетки
+//! запоминают источник входов за последние тики, и если история
+//! устойчиво указывает на другую роль, клетка переходит в неё.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::transdifferentiation;
+use crate::stem::CellRole;
+
+/// Источник входа клетки в конвейере Sensor → Logic → Motor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputSource {
+    /// Сырой внешний вход
+    Raw,
+    /// Поток сенсоров
+    Sensed,
+    /// Поток логики
+    Processed,
+}
+
+impl InputSource {
+    /// Роль, которой подходит вход этого источника
+    pub fn fitting_role(self) -> CellRole {
+        match self {
+            InputSource::Raw => CellRole::Sensor,
+            InputSource::Sensed => CellRole::Logic,
+            InputSource::Processed => CellRole::Motor,
+        }
+    }
+
+    /// Участвует ли роль в конвейере (только такие клетки меняют роль)
+    pub fn is_pipeline_role(role: CellRole) -> bool {
+        matches!(role, CellRole::Sensor | CellRole::Logic | CellRole::Motor)
+    }
+}
+
+/// Скользящая история источников входа клетки
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalHistory {
+    sources: VecDeque<InputSource>,
+    window: usize,
+}
+
+impl SignalHistory {
+    /// Создать пустую историю заданной длины
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            sources: VecDeque::with_capacity(window),
+            window,
+        }
+    }
+
+    /// Записать источник входа (старейшая запись вытесняется)
+    pub fn record(&mut self, source: InputSource) {
+        if self.sources.len() >= self.window {
+            self.sources.pop_front();
+        }
+        self.sources.push_back(source);
+    }
+
+    /// Заполнено ли окно истории
+    pub fn is_full(&self) -> bool {
+        self.sources.len() >= self.window
+    }
+
+    /// Количество записей
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Пуста ли история
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Самый частый источник и его доля в истории
+    ///
+    /// При равенстве побеждает источник, встреченный последним
+    pub fn dominant(&self) -> Option<(InputSource, f64)> {
+        let count = |source| self.sources.iter().filter(|s| **s == source).count();
+        let last = *self.sources.back()?;
+        let best = [InputSource::Raw, InputSource::Sensed, InputSource::Processed]
+            .into_iter()
+            .max_by_key(|&source| (count(source), source == last))?;
+        Some((best, count(best) as f64 / self.sources.len() as f64))
+    }
+}
+
+/// Политика трансдифференциации
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransdifferentiationPolicy {
+    /// Длина истории входов (тиков)
+    pub window: usize,
+    /// Доля истории, которая должна указывать на другую роль (0.0 - 1.0)
+    pub mismatch_ratio: f64,
+    /// Максимум смен роли за тик
+    pub max_per_tick: usize,
+}
+
+impl TransdifferentiationPolicy {
+    /// Создать политику по умолчанию
+    pub fn new() -> Self {
+        Self {
+            window: transdifferentiation::DEFAULT_WINDOW,
+            mismatch_ratio: transdifferentiation::DEFAULT_MISMATCH_RATIO,
+            max_per_tick: transdifferentiation::DEFAULT_MAX_PER_TICK,
+        }
+    }
+
+    /// Установить длину истории
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Установить долю несоответствия для смены роли
+    pub fn with_mismatch_ratio(mut self, ratio: f64) -> Self {
+        self.mismatch_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Установить максимум смен роли за тик
+    pub fn with_max_per_tick(mut self, max: usize) -> Self {
+        self.max_per_tick = max;
+        self
+    }
+
+    /// Роль, в которую должна перейти клетка (None - роль подходит)
+    ///
+    /// Решение принимается только по полному окну истории
+    pub fn target_role(&self, role: CellRole, history: &SignalHistory) -> Option<CellRole> {
+        if !InputSource::is_pipeline_role(role) || !history.is_full() {
+            return None;
+        }
+        let (source, share) = history.dominant()?;
+        let target = source.fitting_role();
+        (target != role && share >= self.mismatch_ratio).then_some(target)
+    }
+}
+
+impl Default for TransdifferentiationPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_window_and_dominant() {
+        let mut history = SignalHistory::new(3);
+        assert_eq!(history.dominant(), None);
+
+        history.record(InputSource::Sensed);
+        history.record(InputSource::Raw);
+        history.record(InputSource::Raw);
+        history.record(InputSource::Raw);
+
+        assert!(history.is_full());
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.dominant(), Some((InputSource::Raw, 1.0)));
+    }
+
+    #[test]
+    fn test_target_role_requires_full_consistent_history() {
+        let policy = TransdifferentiationPolicy::new().with_window(4).with_mismatch_ratio(0.75);
+        let mut history = SignalHistory::new(4);
+        for _ in 0..3 {
+            history.record(InputSource::Raw);
+        }
+        assert_eq!(policy.target_role(CellRole::Logic, &history), None);
+
+        history.record(InputSource::Sensed);
+        assert_eq!(policy.target_role(CellRole::Logic, &history), Some(CellRole::Sensor));
+        assert_eq!(policy.target_role(CellRole::Sensor, &history), None);
+        assert_eq!(policy.target_role(CellRole::Memory, &history), None);
+
+        history.record(InputSource::Sensed);
+        assert_eq!(policy.target_role(CellRole::Logic, &history), None);
+    }
+}
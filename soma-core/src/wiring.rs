@@ -0,0 +1,477 @@
+//! Граф связей между портами клеток
+//!
+//! Узлы графа - многопортовые клетки, рёбра соединяют выходной порт одной
+//! клетки с входным портом другой. Каждый тик клетки вычисляются в
+//! топологическом порядке, так что клетка видит выходы своих источников
+//! за этот же тик. Циклы запрещены.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::cell::Cell;
+use crate::port::{PortCell, PortType, PortValue, ScalarCellAdapter};
+
+/// Клетка, принадлежащая графу связей
+pub type WiredCell = Box<dyn PortCell + Send>;
+
+/// Идентификатор узла графа связей
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Индекс узла в графе
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// Связь выходного порта с входным
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wire {
+    /// Узел-источник
+    pub from: NodeId,
+    /// Выходной порт источника
+    pub from_port: String,
+    /// Узел-приёмник
+    pub to: NodeId,
+    /// Входной порт приёмника
+    pub to_port: String,
+}
+
+/// Ошибки построения графа связей
+#[derive(Debug, Clone, PartialEq)]
+pub enum WiringError {
+    /// Узел не найден
+    UnknownNode(NodeId),
+    /// Порт не найден у узла
+    UnknownPort { node: NodeId, port: String },
+    /// Типы соединяемых портов не совпадают
+    TypeMismatch { from: PortType, to: PortType },
+    /// Входной порт уже подключён к клетке или занят внешним входом
+    PortAlreadyConnected { node: NodeId, port: String },
+    /// Связь образует цикл
+    Cycle,
+}
+
+impl fmt::Display for WiringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WiringError::UnknownNode(node) => write!(f, "Unknown node: {}", node.0),
+            WiringError::UnknownPort { node, port } => {
+                write!(f, "Unknown port '{}' on node {}", port, node.0)
+            }
+            WiringError::TypeMismatch { from, to } => {
+                write!(f, "Port type mismatch: {} -> {}", from, to)
+            }
+            WiringError::PortAlreadyConnected { node, port } => {
+                write!(f, "Port '{}' on node {} is already connected", port, node.0)
+            }
+            WiringError::Cycle => write!(f, "Wiring contains a cycle"),
+        }
+    }
+}
+
+impl std::error::Error for WiringError {}
+
+/// Граф связей между портами клеток
+#[derive(Default)]
+pub struct WiringGraph {
+    nodes: Vec<WiredCell>,
+    wires: Vec<Wire>,
+    /// Входящие связи каждого узла (индексы в `wires` по порядку соединения)
+    incoming: Vec<Vec<usize>>,
+    /// Внешние значения входных портов (по узлу, затем по имени порта)
+    inputs: BTreeMap<(NodeId, String), PortValue>,
+    /// Топологический порядок вычисления узлов
+    order: Vec<NodeId>,
+}
+
+impl WiringGraph {
+    /// Создать пустой граф
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Добавить многопортовую клетку
+    pub fn add_cell<C: PortCell + Send + 'static>(&mut self, cell: C) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Box::new(cell));
+        self.incoming.push(Vec::new());
+        self.order.push(id);
+        id
+    }
+
+    /// Добавить однопортовую клетку через адаптер (порты `in` и `out`)
+    pub fn add_scalar_cell<C: Cell + Send + 'static>(&mut self, cell: C) -> NodeId {
+        self.add_cell(ScalarCellAdapter::new(cell))
+    }
+
+    /// Соединить выходной порт одной клетки с входным портом другой
+    pub fn connect(
+        &mut self,
+        from: NodeId,
+        from_port: &str,
+        to: NodeId,
+        to_port: &str,
+    ) -> Result<(), WiringError> {
+        let source = self.nodes.get(from.0).ok_or(WiringError::UnknownNode(from))?;
+        let target = self.nodes.get(to.0).ok_or(WiringError::UnknownNode(to))?;
+
+        let output = source.output(from_port).ok_or_else(|| WiringError::UnknownPort {
+            node: from,
+            port: from_port.to_string(),
+        })?;
+        let input = target.input(to_port).ok_or_else(|| WiringError::UnknownPort {
+            node: to,
+            port: to_port.to_string(),
+        })?;
+        if output.port_type != input.port_type {
+            return Err(WiringError::TypeMismatch {
+                from: output.port_type,
+                to: input.port_type,
+            });
+        }
+        if self.is_connected(to, to_port) || self.inputs.contains_key(&(to, to_port.to_string())) {
+            return Err(WiringError::PortAlreadyConnected {
+                node: to,
+                port: to_port.to_string(),
+            });
+        }
+
+        self.wires.push(Wire {
+            from,
+            from_port: from_port.to_string(),
+            to,
+            to_port: to_port.to_string(),
+        });
+        match self.topological_order() {
+            Some(order) => {
+                self.order = order;
+                self.incoming[to.0].push(self.wires.len() - 1);
+                Ok(())
+            }
+            None => {
+                self.wires.pop();
+                Err(WiringError::Cycle)
+            }
+        }
+    }
+
+    /// Подключён ли входной порт к другой клетке
+    pub fn is_connected(&self, node: NodeId, port: &str) -> bool {
+        self.incoming
+            .get(node.0)
+            .is_some_and(|wires| wires.iter().any(|&w| self.wires[w].to_port == port))
+    }
+
+    /// Задать внешнее значение входного порта (подаётся каждый тик)
+    pub fn set_input(&mut self, node: NodeId, port: &str, value: PortValue) -> Result<(), WiringError> {
+        let cell = self.nodes.get(node.0).ok_or(WiringError::UnknownNode(node))?;
+        let spec = cell.input(port).ok_or_else(|| WiringError::UnknownPort {
+            node,
+            port: port.to_string(),
+        })?;
+        if spec.port_type != value.port_type() {
+            return Err(WiringError::TypeMismatch {
+                from: value.port_type(),
+                to: spec.port_type,
+            });
+        }
+        if self.is_connected(node, port) {
+            return Err(WiringError::PortAlreadyConnected {
+                node,
+                port: port.to_string(),
+            });
+        }
+        self.inputs.insert((node, port.to_string()), value);
+        Ok(())
+    }
+
+    /// Снять внешний вход с порта, чтобы подключить его к другой клетке
+    pub fn clear_input(&mut self, node: NodeId, port: &str) -> Option<PortValue> {
+        self.inputs.remove(&(node, port.to_string()))
+    }
+
+    /// Топологический порядок узлов (алгоритм Кана)
+    ///
+    /// При равенстве первым идёт узел, добавленный раньше. None - есть цикл
+    fn topological_order(&self) -> Option<Vec<NodeId>> {
+        let mut in_degree = vec![0usize; self.nodes.len()];
+        let mut outgoing = vec![Vec::new(); self.nodes.len()];
+        for wire in &self.wires {
+            in_degree[wire.to.0] += 1;
+            outgoing[wire.from.0].push(wire.to.0);
+        }
+
+        let mut ready: BTreeSet<usize> = (0..self.nodes.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(index) = ready.pop_first() {
+            order.push(NodeId(index));
+            for &to in &outgoing[index] {
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    ready.insert(to);
+                }
+            }
+        }
+
+        (order.len() == self.nodes.len()).then_some(order)
+    }
+
+    /// Порядок вычисления узлов
+    pub fn order(&self) -> &[NodeId] {
+        &self.order
+    }
+
+    /// Тик графа - вычислить все клетки в топологическом порядке
+    ///
+    /// Клетка получает сначала значения связей в порядке соединения, затем
+    /// внешние входы по имени порта
+    pub fn tick(&mut self) {
+        for position in 0..self.order.len() {
+            let node = self.order[position];
+
+            let mut values: Vec<(String, PortValue)> = self.incoming[node.0]
+                .iter()
+                .filter_map(|&index| {
+                    let wire = &self.wires[index];
+                    let value = self.nodes[wire.from.0].flow_port(&wire.from_port)?;
+                    Some((wire.to_port.clone(), value))
+                })
+                .collect();
+            values.extend(
+                self.inputs
+                    .range((node, String::new())..)
+                    .take_while(|((target, _), _)| *target == node)
+                    .map(|((_, port), value)| (port.clone(), value.clone())),
+            );
+
+            let cell = &mut self.nodes[node.0];
+            for (port, value) in values {
+                cell.sense_port(&port, value);
+            }
+            cell.align();
+        }
+    }
+
+    /// Значение выходного порта клетки
+    pub fn output(&self, node: NodeId, port: &str) -> Option<PortValue> {
+        self.nodes.get(node.0)?.flow_port(port)
+    }
+
+    /// Получить клетку графа
+    pub fn cell(&self, node: NodeId) -> Option<&WiredCell> {
+        self.nodes.get(node.0)
+    }
+
+    /// Все связи графа
+    pub fn wires(&self) -> &[Wire] {
+        &self.wires
+    }
+
+    /// Количество клеток в графе
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Проверить, пуст ли граф
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl fmt::Debug for WiringGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WiringGraph")
+            .field("nodes", &self.nodes.len())
+            .field("wires", &self.wires)
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::port::{PortSpec, SCALAR_INPUT, SCALAR_OUTPUT};
+    use std::sync::{Arc, Mutex};
+
+    struct Scale(f64, f64);
+
+    impl Cell for Scale {
+        fn sense(&mut self, input: f64) {
+            self.0 = input;
+        }
+
+        fn align(&mut self) {
+            self.0 *= self.1;
+        }
+
+        fn flow(&self) -> f64 {
+            self.0
+        }
+    }
+
+    /// Сумматор двух входов с выходами суммы и разности
+    struct Mixer {
+        inputs: Vec<PortSpec>,
+        outputs: Vec<PortSpec>,
+        a: f64,
+        b: f64,
+    }
+
+    impl Mixer {
+        fn new() -> Self {
+            Self {
+                inputs: vec![PortSpec::scalar("a"), PortSpec::scalar("b")],
+                outputs: vec![PortSpec::scalar("sum"), PortSpec::scalar("diff")],
+                a: 0.0,
+                b: 0.0,
+            }
+        }
+    }
+
+    impl PortCell for Mixer {
+        fn inputs(&self) -> &[PortSpec] {
+            &self.inputs
+        }
+
+        fn outputs(&self) -> &[PortSpec] {
+            &self.outputs
+        }
+
+        fn sense_port(&mut self, port: &str, value: PortValue) {
+            match port {
+                "a" => self.a = value.as_scalar(),
+                "b" => self.b = value.as_scalar(),
+                _ => {}
+            }
+        }
+
+        fn align(&mut self) {}
+
+        fn flow_port(&self, port: &str) -> Option<PortValue> {
+            match port {
+                "sum" => Some(PortValue::Scalar(self.a + self.b)),
+                "diff" => Some(PortValue::Scalar(self.a - self.b)),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_tick_evaluates_in_topological_order() {
+        let mut graph = WiringGraph::new();
+        // Смеситель добавлен первым, но зависит от масштабирующих клеток
+        let mixer = graph.add_cell(Mixer::new());
+        let double = graph.add_scalar_cell(Scale(0.0, 2.0));
+        let half = graph.add_scalar_cell(Scale(0.0, 0.5));
+
+        graph.connect(double, SCALAR_OUTPUT, mixer, "a").unwrap();
+        graph.connect(half, SCALAR_OUTPUT, mixer, "b").unwrap();
+        graph.set_input(double, SCALAR_INPUT, PortValue::Scalar(0.25)).unwrap();
+        graph.set_input(half, SCALAR_INPUT, PortValue::Scalar(0.5)).unwrap();
+
+        assert_eq!(graph.order(), &[double, half, mixer]);
+        graph.tick();
+        assert_eq!(graph.output(mixer, "sum"), Some(PortValue::Scalar(0.75)));
+        assert_eq!(graph.output(mixer, "diff"), Some(PortValue::Scalar(0.25)));
+    }
+
+    /// Клетка, записывающая порядок получения входов
+    struct Recorder {
+        inputs: Vec<PortSpec>,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl PortCell for Recorder {
+        fn inputs(&self) -> &[PortSpec] {
+            &self.inputs
+        }
+
+        fn outputs(&self) -> &[PortSpec] {
+            &[]
+        }
+
+        fn sense_port(&mut self, port: &str, _value: PortValue) {
+            self.log.lock().unwrap().push(port.to_string());
+        }
+
+        fn align(&mut self) {}
+
+        fn flow_port(&self, _port: &str) -> Option<PortValue> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_tick_senses_ports_in_deterministic_order() {
+        let run = |order: &[&str]| {
+            let log = Arc::new(Mutex::new(Vec::new()));
+            let mut graph = WiringGraph::new();
+            let recorder = graph.add_cell(Recorder {
+                inputs: ["a", "b", "c", "d", "e"].map(PortSpec::scalar).to_vec(),
+                log: log.clone(),
+            });
+            let source = graph.add_scalar_cell(Scale(0.0, 1.0));
+            graph.connect(source, SCALAR_OUTPUT, recorder, "e").unwrap();
+            for &port in order {
+                graph.set_input(recorder, port, PortValue::Scalar(1.0)).unwrap();
+            }
+            graph.tick();
+            let log = log.lock().unwrap().clone();
+            log
+        };
+
+        // Сначала связи, затем внешние входы по имени порта
+        let expected = ["e", "a", "b", "c", "d"];
+        assert_eq!(run(&["d", "a", "c", "b"]), expected);
+        assert_eq!(run(&["b", "d", "a", "c"]), expected);
+    }
+
+    #[test]
+    fn test_connect_rejects_cycles_and_bad_ports() {
+        let mut graph = WiringGraph::new();
+        let a = graph.add_scalar_cell(Scale(0.0, 1.0));
+        let b = graph.add_scalar_cell(Scale(0.0, 1.0));
+
+        graph.connect(a, SCALAR_OUTPUT, b, SCALAR_INPUT).unwrap();
+        assert_eq!(graph.connect(b, SCALAR_OUTPUT, a, SCALAR_INPUT), Err(WiringError::Cycle));
+        assert_eq!(graph.wires().len(), 1);
+
+        assert!(matches!(
+            graph.connect(a, "missing", b, SCALAR_INPUT),
+            Err(WiringError::UnknownPort { .. })
+        ));
+        assert!(matches!(
+            graph.connect(a, SCALAR_OUTPUT, b, SCALAR_INPUT),
+            Err(WiringError::PortAlreadyConnected { .. })
+        ));
+
+        // Порт с внешним входом тоже занят
+        let c = graph.add_scalar_cell(Scale(0.0, 1.0));
+        graph.set_input(c, SCALAR_INPUT, PortValue::Scalar(1.0)).unwrap();
+        assert!(matches!(
+            graph.connect(b, SCALAR_OUTPUT, c, SCALAR_INPUT),
+            Err(WiringError::PortAlreadyConnected { .. })
+        ));
+        assert_eq!(graph.clear_input(c, SCALAR_INPUT), Some(PortValue::Scalar(1.0)));
+        assert!(graph.connect(b, SCALAR_OUTPUT, c, SCALAR_INPUT).is_ok());
+    }
+
+    #[test]
+    fn test_port_types_must_match() {
+        let mut graph = WiringGraph::new();
+        let a = graph.add_scalar_cell(Scale(0.0, 1.0));
+
+        let err = graph.set_input(a, SCALAR_INPUT, PortValue::Spike(true)).unwrap_err();
+        assert_eq!(
+            err,
+            WiringError::TypeMismatch {
+                from: PortType::Spike,
+                to: PortType::Scalar,
+            }
+        );
+        assert_eq!(err.to_string(), "Port type mismatch: spike -> scalar");
+    }
+}
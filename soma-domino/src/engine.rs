@@ -2,8 +2,9 @@
 //!
 //! Высокоуровневый интерфейс для оценки "удачи" и выбора лучших пиров.
 
-use crate::qstar_loop::{evaluate_candidates, CandidateScore};
+use crate::qstar_loop::{evaluate_candidates, evaluate_candidates_on, CandidateScore};
 use serde::{Deserialize, Serialize};
+use soma_core::{Clock, SystemClock};
 
 // Re-export PeerCandidate для удобства
 pub use crate::string_resonance::PeerCandidate;
@@ -135,13 +136,18 @@ impl DominoEngine {
     /// # Returns
     /// Решение с лучшими пирами и оценками
     pub fn evaluate(input: DominoInput) -> DominoDecision {
+        Self::evaluate_on(input, &SystemClock)
+    }
+
+    /// Оценить input, вычисляя фазу резонанса по заданным часам
+    pub fn evaluate_on(input: DominoInput, clock: &dyn Clock) -> DominoDecision {
         // Проверка на пустой список кандидатов
         if input.candidates.is_empty() {
             return DominoDecision::empty("no candidates provided");
         }
 
         // 1. Оценка всех кандидатов
-        let scored = evaluate_candidates(&input.candidates);
+        let scored = evaluate_candidates_on(&input.candidates, clock);
 
         // 2. Выбрать top-N (по умолчанию все, отсортированные)
         let best_peers: Vec<String> = scored.iter().map(|s| s.peer_id.clone()).collect();
@@ -5,7 +5,8 @@
 //! итоговый score и возвращаем отсортированный список.
 
 use crate::fuzzy_luck::{evaluate_fuzzy, FuzzyLuck};
-use crate::string_resonance::{compute_resonance, PeerCandidate};
+use crate::string_resonance::{compute_resonance_on, PeerCandidate, ResonanceWeights};
+use soma_core::{Clock, SystemClock};
 
 /// Результат оценки одного кандидата
 #[derive(Debug, Clone)]
@@ -46,11 +47,17 @@ impl CandidateScore {
 /// # Returns
 /// Отсортированный список результатов (лучшие первые)
 pub fn evaluate_candidates(candidates: &[PeerCandidate]) -> Vec<CandidateScore> {
+    evaluate_candidates_on(candidates, &SystemClock)
+}
+
+/// Оценить всех кандидатов, вычисляя фазу резонанса по заданным часам
+pub fn evaluate_candidates_on(candidates: &[PeerCandidate], clock: &dyn Clock) -> Vec<CandidateScore> {
+    let weights = ResonanceWeights::default();
     let mut scores: Vec<CandidateScore> = candidates
         .iter()
         .map(|candidate| {
             // 1. Вычислить резонанс
-            let resonance = compute_resonance(candidate);
+            let resonance = compute_resonance_on(candidate, &weights, clock);
 
             // 2. Fuzzy-оценка
             let fuzzy = evaluate_fuzzy(resonance);
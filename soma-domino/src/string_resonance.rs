@@ -4,7 +4,7 @@
 //! Модель: взвешенная комбинация health, quality, intent_match с добавлением
 //! фазового коэффициента (зависит от времени).
 
-use std::time::{SystemTime, UNIX_EPOCH};
+use soma_core::{Clock, SystemClock};
 
 /// Представляет кандидата-пира для оценки
 #[derive(Debug, Clone)]
@@ -52,25 +52,31 @@ pub fn compute_resonance(candidate: &PeerCandidate) -> f32 {
 pub fn compute_resonance_with_weights(
     candidate: &PeerCandidate,
     weights: &ResonanceWeights,
+) -> f32 {
+    compute_resonance_on(candidate, weights, &SystemClock)
+}
+
+/// Вычислить резонанс с кастомными весами и фазой по заданным часам
+pub fn compute_resonance_on(
+    candidate: &PeerCandidate,
+    weights: &ResonanceWeights,
+    clock: &dyn Clock,
 ) -> f32 {
     let base_resonance = candidate.health * weights.health_weight
         + candidate.quality * weights.quality_weight
         + candidate.intent_match * weights.intent_weight;
 
-    let phase_coeff = compute_phase_coefficient();
+    let phase_coeff = compute_phase_coefficient(clock);
 
     (base_resonance * phase_coeff).min(1.0)
 }
 
-/// Вычислить фазовый коэффициент на основе текущего времени
+/// Вычислить фазовый коэффициент на основе времени часов
 ///
 /// Фаза зависит от времени суток и колеблется между 0.8 и 1.0
 /// Это имитирует "ритмы сети" — в разное время удача может быть выше/ниже
-fn compute_phase_coefficient() -> f32 {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
+pub fn compute_phase_coefficient(clock: &dyn Clock) -> f32 {
+    let now = clock.now_millis() / 1000;
 
     // Простая синусоида: колебание с периодом ~24 часа
     // Амплитуда: 0.8 - 1.0
@@ -141,12 +147,24 @@ mod tests {
 
     #[test]
     fn test_phase_coefficient_range() {
-        let phase = compute_phase_coefficient();
+        let phase = compute_phase_coefficient(&SystemClock);
 
         // Фаза должна быть в диапазоне 0.8 - 1.0
         assert!(phase >= 0.8 && phase <= 1.0);
     }
 
+    #[test]
+    fn test_phase_coefficient_follows_clock() {
+        use soma_core::ManualClock;
+
+        // Четверть суток - пик синусоиды, три четверти - минимум
+        let clock = ManualClock::new(21_600_000);
+        assert!((compute_phase_coefficient(&clock) - 1.0).abs() < 1e-6);
+
+        clock.set(64_800_000);
+        assert!((compute_phase_coefficient(&clock) - 0.8).abs() < 1e-6);
+    }
+
     #[test]
     fn test_custom_weights() {
         let candidate = PeerCandidate {
@@ -5,8 +5,14 @@
 //! - Влияние health на luck_score
 //! - Порядок best_peers (отсортирован по score)
 
+use soma_core::ManualClock;
 use soma_domino::{DominoEngine, DominoInput, DominoIntentKind, PeerCandidate};
 
+/// Часы в полночь UTC - нейтральная фаза (коэффициент 0.9), тесты не зависят от времени суток
+fn neutral_phase_clock() -> ManualClock {
+    ManualClock::new(0)
+}
+
 #[test]
 fn test_selects_superior_peer() {
     // Создаём кандидатов: один явно превосходный, остальные посредственные
@@ -37,7 +43,7 @@ fn test_selects_superior_peer() {
         vec![],
     );
 
-    let decision = DominoEngine::evaluate(input);
+    let decision = DominoEngine::evaluate_on(input, &neutral_phase_clock());
 
     // Лучший пир должен быть "superior"
     assert_eq!(decision.best_peers[0], "superior");
@@ -81,7 +87,7 @@ fn test_high_health_increases_luck_score() {
         vec![],
     );
 
-    let decision_healthy = DominoEngine::evaluate(input_healthy);
+    let decision_healthy = DominoEngine::evaluate_on(input_healthy, &neutral_phase_clock());
 
     // Сценарий 2: Все кандидаты нездоровы
     let unhealthy_candidates = vec![
@@ -105,7 +111,7 @@ fn test_high_health_increases_luck_score() {
         vec![],
     );
 
-    let decision_unhealthy = DominoEngine::evaluate(input_unhealthy);
+    let decision_unhealthy = DominoEngine::evaluate_on(input_unhealthy, &neutral_phase_clock());
 
     // Здоровые кандидаты должны иметь значительно более высокий luck_score
     assert!(
@@ -0,0 +1,466 @@
+//! Эволюция организмов - автоматический подбор параметров
+//!
+//! [`EvolutionEngine`] прогоняет популяцию организмов без интерфейса.
+//! Каждый кандидат - [`Genome`]: параметры StemProcessor (порог,
+//! сглаживание, стратегия выбора роли) и параметры нейронов, в которые
+//! дифференцируются логические клетки. Кандидаты оцениваются функцией
+//! приспособленности пользователя, лучшие выживают, остальные
+//! замещаются потомками (кроссовер и мутация). Все случайные решения
+//! берутся из одного генератора с зерном, поэтому прогон воспроизводим.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use soma_core::config::stem;
+use soma_core::{
+    BalancedSelector, CellRole, ManualClock, MorphogenSelector, PressureGradientSelector,
+    RoleSelector, SharedClock, StemCell, StemProcessor,
+};
+use soma_vnp::config::neuron;
+use soma_vnp::Neuron;
+
+/// Стратегия выбора роли, закодированная в геноме
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RoleStrategy {
+    /// [`BalancedSelector`]
+    Balanced,
+    /// [`PressureGradientSelector`]
+    PressureGradient,
+    /// [`MorphogenSelector`]
+    Morphogen,
+}
+
+impl RoleStrategy {
+    /// Все стратегии
+    pub const ALL: [RoleStrategy; 3] = [Self::Balanced, Self::PressureGradient, Self::Morphogen];
+
+    /// Создать стратегию выбора роли
+    pub fn selector(&self) -> Box<dyn RoleSelector> {
+        match self {
+            RoleStrategy::Balanced => Box::new(BalancedSelector::new()),
+            RoleStrategy::PressureGradient => Box::new(PressureGradientSelector::new()),
+            RoleStrategy::Morphogen => Box::new(MorphogenSelector::new()),
+        }
+    }
+}
+
+/// Геном организма - параметры процессора и нейронов
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Genome {
+    /// Порог нагрузки для деления
+    pub threshold: f64,
+    /// Коэффициент сглаживания нагрузки
+    pub smoothing: f64,
+    /// Стратегия выбора роли
+    pub strategy: RoleStrategy,
+    /// Порог активации нейронов
+    pub neuron_threshold: f64,
+    /// Затухание потенциала нейронов
+    pub neuron_decay: f64,
+    /// Вес входа нейронов
+    pub neuron_weight: f64,
+}
+
+impl Genome {
+    /// Случайный геном
+    pub fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            threshold: rng.gen_range(0.0..=1.0),
+            smoothing: rng.gen_range(0.0..=1.0),
+            strategy: RoleStrategy::ALL[rng.gen_range(0..RoleStrategy::ALL.len())],
+            neuron_threshold: rng.gen_range(0.0..=1.0),
+            neuron_decay: rng.gen_range(0.0..=1.0),
+            neuron_weight: rng.gen_range(0.0..=2.0),
+        }
+    }
+
+    /// Нейрон с параметрами генома
+    pub fn neuron(&self, clock: SharedClock) -> Neuron {
+        Neuron::with_params(self.neuron_threshold, self.neuron_decay, self.neuron_weight)
+            .with_clock(clock)
+    }
+
+    /// Вырастить организм по геному
+    ///
+    /// Организм работает на собственных ручных часах и с заданным зерном,
+    /// логические клетки дифференцируются в нейроны генома
+    pub fn organism(&self, seed: u64) -> StemProcessor {
+        let clock = ManualClock::new(0).shared();
+        let genome = self.clone();
+        let neuron_clock = clock.clone();
+
+        let mut stem = StemProcessor::with_params(self.threshold, self.smoothing)
+            .with_clock(clock)
+            .with_seed(seed)
+            .with_cell_factory(CellRole::Logic, move |_| {
+                Box::new(StemCell::new().differentiate(genome.neuron(neuron_clock.clone())))
+            });
+        stem.set_role_selector(self.strategy.selector());
+        stem
+    }
+
+    /// Мутировать геном
+    ///
+    /// Каждый ген меняется с вероятностью `rate`, непрерывные гены
+    /// сдвигаются не более чем на `strength` (доля допустимого диапазона)
+    pub fn mutate(&mut self, rng: &mut impl Rng, rate: f64, strength: f64) {
+        let (rate, strength) = (unit(rate), unit(strength));
+        let mut gene = |value: &mut f64, max: f64| {
+            if rng.gen_bool(rate) {
+                let delta = rng.gen_range(-strength..=strength) * max;
+                *value = (*value + delta).clamp(0.0, max);
+            }
+        };
+        gene(&mut self.threshold, 1.0);
+        gene(&mut self.smoothing, 1.0);
+        gene(&mut self.neuron_threshold, 1.0);
+        gene(&mut self.neuron_decay, 1.0);
+        gene(&mut self.neuron_weight, 2.0);
+
+        if rng.gen_bool(rate) {
+            self.strategy = RoleStrategy::ALL[rng.gen_range(0..RoleStrategy::ALL.len())];
+        }
+    }
+
+    /// Равномерный кроссовер - каждый ген берётся от случайного родителя
+    pub fn crossover(&self, other: &Genome, rng: &mut impl Rng) -> Genome {
+        let mut pick = |a: f64, b: f64| if rng.gen_bool(0.5) { a } else { b };
+        Genome {
+            threshold: pick(self.threshold, other.threshold),
+            smoothing: pick(self.smoothing, other.smoothing),
+            neuron_threshold: pick(self.neuron_threshold, other.neuron_threshold),
+            neuron_decay: pick(self.neuron_decay, other.neuron_decay),
+            neuron_weight: pick(self.neuron_weight, other.neuron_weight),
+            strategy: if rng.gen_bool(0.5) { self.strategy } else { other.strategy },
+        }
+    }
+}
+
+impl Default for Genome {
+    /// Геном с параметрами по умолчанию (ручная настройка до эволюции)
+    fn default() -> Self {
+        Self {
+            threshold: stem::DEFAULT_THRESHOLD,
+            smoothing: stem::DEFAULT_SMOOTHING,
+            strategy: RoleStrategy::Balanced,
+            neuron_threshold: neuron::DEFAULT_THRESHOLD,
+            neuron_decay: neuron::DEFAULT_DECAY,
+            neuron_weight: neuron::DEFAULT_WEIGHT,
+        }
+    }
+}
+
+/// Параметры эволюции
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvolutionConfig {
+    /// Размер популяции
+    pub population_size: usize,
+    /// Количество лучших кандидатов, переходящих в следующее поколение без изменений
+    pub elite: usize,
+    /// Размер турнира при выборе родителей
+    pub tournament_size: usize,
+    /// Вероятность кроссовера (иначе потомок - копия родителя)
+    pub crossover_rate: f64,
+    /// Вероятность мутации гена
+    pub mutation_rate: f64,
+    /// Сила мутации (доля диапазона гена)
+    pub mutation_strength: f64,
+    /// Зерно генератора
+    pub seed: u64,
+}
+
+impl EvolutionConfig {
+    /// Параметры по умолчанию с заданным зерном
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed, ..Self::default() }
+    }
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        Self {
+            population_size: 20,
+            elite: 2,
+            tournament_size: 3,
+            crossover_rate: 0.7,
+            mutation_rate: 0.2,
+            mutation_strength: 0.1,
+            seed: 0,
+        }
+    }
+}
+
+/// Отчёт об одном поколении
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationReport {
+    /// Номер поколения (с 0)
+    pub generation: u32,
+    /// Лучшая приспособленность
+    pub best_fitness: f64,
+    /// Средняя приспособленность
+    pub mean_fitness: f64,
+    /// Худшая приспособленность
+    pub worst_fitness: f64,
+    /// Лучший геном поколения
+    pub best: Genome,
+}
+
+/// Кандидат с оценкой приспособленности
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scored {
+    /// Геном кандидата
+    pub genome: Genome,
+    /// Приспособленность (больше - лучше)
+    pub fitness: f64,
+}
+
+/// Движок эволюции организмов
+#[derive(Debug)]
+pub struct EvolutionEngine {
+    config: EvolutionConfig,
+    rng: StdRng,
+    population: Vec<Genome>,
+    generation: u32,
+    best: Option<Scored>,
+    history: Vec<GenerationReport>,
+}
+
+impl EvolutionEngine {
+    /// Создать движок со случайной начальной популяцией
+    ///
+    /// Первый кандидат - геном по умолчанию, чтобы эволюция не
+    /// уступала ручной настройке
+    pub fn new(config: EvolutionConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let size = config.population_size.max(1);
+        let mut population = vec![Genome::default()];
+        population.extend((1..size).map(|_| Genome::random(&mut rng)));
+        Self::with_population(config, population, rng)
+    }
+
+    /// Создать движок с заданной начальной популяцией
+    pub fn from_population(config: EvolutionConfig, population: Vec<Genome>) -> Self {
+        let rng = StdRng::seed_from_u64(config.seed);
+        Self::with_population(config, population, rng)
+    }
+
+    fn with_population(mut config: EvolutionConfig, population: Vec<Genome>, rng: StdRng) -> Self {
+        let population = if population.is_empty() { vec![Genome::default()] } else { population };
+        config.population_size = population.len();
+        config.elite = config.elite.min(population.len());
+        config.tournament_size = config.tournament_size.max(1);
+        config.crossover_rate = unit(config.crossover_rate);
+        config.mutation_rate = unit(config.mutation_rate);
+        config.mutation_strength = unit(config.mutation_strength);
+        Self {
+            config,
+            rng,
+            population,
+            generation: 0,
+            best: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// Параметры эволюции
+    pub fn config(&self) -> &EvolutionConfig {
+        &self.config
+    }
+
+    /// Текущая популяция
+    pub fn population(&self) -> &[Genome] {
+        &self.population
+    }
+
+    /// Номер следующего поколения
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Лучший кандидат за всё время
+    pub fn best(&self) -> Option<&Scored> {
+        self.best.as_ref()
+    }
+
+    /// Отчёты по пройденным поколениям
+    pub fn history(&self) -> &[GenerationReport] {
+        &self.history
+    }
+
+    /// Зерно организма кандидата (воспроизводимо для поколения и позиции)
+    fn organism_seed(&self, index: usize) -> u64 {
+        self.config
+            .seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(((self.generation as u64) << 32) | index as u64)
+    }
+
+    /// Прогнать одно поколение
+    ///
+    /// Функция приспособленности получает геном и выращенный по нему
+    /// организм (чем больше значение - тем лучше, NaN - худший результат)
+    pub fn step<F>(&mut self, mut fitness: F) -> GenerationReport
+    where
+        F: FnMut(&Genome, &mut StemProcessor) -> f64,
+    {
+        let mut scored: Vec<Scored> = self
+            .population
+            .iter()
+            .enumerate()
+            .map(|(index, genome)| {
+                let mut organism = genome.organism(self.organism_seed(index));
+                let value = fitness(genome, &mut organism);
+                Scored {
+                    genome: genome.clone(),
+                    fitness: if value.is_nan() { f64::NEG_INFINITY } else { value },
+                }
+            })
+            .collect();
+        // Стабильная сортировка - при равенстве сохраняется порядок популяции
+        scored.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+
+        let mean = scored.iter().map(|s| s.fitness).sum::<f64>() / scored.len() as f64;
+        let report = GenerationReport {
+            generation: self.generation,
+            best_fitness: scored[0].fitness,
+            mean_fitness: mean,
+            worst_fitness: scored[scored.len() - 1].fitness,
+            best: scored[0].genome.clone(),
+        };
+        if self.best.as_ref().is_none_or(|best| scored[0].fitness > best.fitness) {
+            self.best = Some(scored[0].clone());
+        }
+
+        self.population = self.breed(&scored);
+        self.generation += 1;
+        self.history.push(report.clone());
+        report
+    }
+
+    /// Прогнать несколько поколений и вернуть лучшего кандидата
+    pub fn run<F>(&mut self, generations: u32, mut fitness: F) -> Option<&Scored>
+    where
+        F: FnMut(&Genome, &mut StemProcessor) -> f64,
+    {
+        for _ in 0..generations {
+            self.step(&mut fitness);
+        }
+        self.best()
+    }
+
+    /// Следующее поколение: элита плюс потомки турнирных победителей
+    fn breed(&mut self, scored: &[Scored]) -> Vec<Genome> {
+        let size = self.config.population_size;
+        let mut next: Vec<Genome> = scored.iter().take(self.config.elite).map(|s| s.genome.clone()).collect();
+
+        while next.len() < size {
+            let first = self.tournament(scored);
+            let mut child = if self.rng.gen_bool(self.config.crossover_rate) {
+                let second = self.tournament(scored);
+                first.crossover(second, &mut self.rng)
+            } else {
+                first.clone()
+            };
+            child.mutate(&mut self.rng, self.config.mutation_rate, self.config.mutation_strength);
+            next.push(child);
+        }
+        next
+    }
+
+    /// Турнирный отбор - лучший из случайной выборки
+    ///
+    /// `scored` отсортирован по убыванию, поэтому побеждает наименьший индекс
+    fn tournament<'a>(&mut self, scored: &'a [Scored]) -> &'a Genome {
+        let winner = (0..self.config.tournament_size)
+            .map(|_| self.rng.gen_range(0..scored.len()))
+            .min()
+            .unwrap_or(0);
+        &scored[winner].genome
+    }
+}
+
+/// Ограничить вероятность или долю диапазоном [0, 1] (NaN считается нулём)
+fn unit(value: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Приспособленность - близость порога к 0.3
+    fn target_threshold(genome: &Genome, _: &mut StemProcessor) -> f64 {
+        -(genome.threshold - 0.3).abs()
+    }
+
+    #[test]
+    fn test_evolution_improves_fitness() {
+        let mut engine = EvolutionEngine::new(EvolutionConfig::with_seed(7));
+        engine.run(15, target_threshold);
+
+        let history = engine.history();
+        assert_eq!(history.len(), 15);
+        assert!(history[14].best_fitness >= history[0].best_fitness);
+        assert!(engine.best().unwrap().fitness > -0.02);
+        assert_eq!(engine.population().len(), 20);
+    }
+
+    #[test]
+    fn test_evolution_is_reproducible() {
+        // Приспособленность зависит от поведения организма
+        let grow = |_: &Genome, stem: &mut StemProcessor| {
+            for _ in 0..20 {
+                stem.sense(0.9);
+                stem.tick();
+            }
+            stem.cell_count() as f64 + stem.output()
+        };
+
+        let mut a = EvolutionEngine::new(EvolutionConfig::with_seed(11));
+        let mut b = EvolutionEngine::new(EvolutionConfig::with_seed(11));
+        a.run(3, grow);
+        b.run(3, grow);
+
+        assert_eq!(a.history(), b.history());
+        assert_eq!(a.population(), b.population());
+    }
+
+    #[test]
+    fn test_mutation_and_crossover_stay_in_range() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut genome = Genome::default();
+        for _ in 0..100 {
+            genome.mutate(&mut rng, 1.0, 0.5);
+        }
+        assert!((0.0..=1.0).contains(&genome.threshold));
+        assert!((0.0..=2.0).contains(&genome.neuron_weight));
+
+        let other = Genome::random(&mut rng);
+        let child = genome.crossover(&other, &mut rng);
+        assert!(child.smoothing == genome.smoothing || child.smoothing == other.smoothing);
+    }
+
+    #[test]
+    fn test_invalid_rates_are_sanitised() {
+        for strength in [-0.5, f64::NAN, 3.0] {
+            let config = EvolutionConfig {
+                mutation_rate: f64::NAN,
+                mutation_strength: strength,
+                population_size: 4,
+                ..EvolutionConfig::with_seed(3)
+            };
+            let mut engine = EvolutionEngine::new(config);
+            assert!((0.0..=1.0).contains(&engine.config().mutation_strength));
+            engine.run(2, target_threshold);
+        }
+
+        let mut genome = Genome::default();
+        genome.mutate(&mut StdRng::seed_from_u64(1), 1.0, -1.0);
+        assert_eq!(genome.neuron_threshold, soma_vnp::config::neuron::DEFAULT_THRESHOLD);
+    }
+}
@@ -17,6 +17,11 @@
 //! - **Creative**: Доминирует творчество (Морфей)
 //! - **Structured**: Доминирует планирование (Архитектор)
 //!
+//! ## Эволюция
+//!
+//! **EvolutionEngine** подбирает параметры StemProcessor и нейронов
+//! мутацией, кроссовером и отбором по функции приспособленности.
+//!
 //! ## Примеры
 //!
 //! ```
@@ -36,10 +41,14 @@
 //! ```
 
 pub mod council;
+pub mod evolution;
 
 pub use council::{
     Architect, CouncilMode, Decision, InnerCouncil, Morpheus, Opinion, Pythia,
 };
+pub use evolution::{
+    EvolutionConfig, EvolutionEngine, GenerationReport, Genome, RoleStrategy, Scored,
+};
 
 /// Конфигурация модуля разума
 #[derive(Debug, Clone)]
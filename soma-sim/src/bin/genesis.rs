@@ -1,4 +1,5 @@
-use soma_core::{CellRole, StemProcessor};
+use soma_core::{system_clock, CellRole, StemProcessor};
+use soma_vnp::neuron_factory;
 use std::io::Write;
 use std::thread::sleep;
 use std::time::Duration;
@@ -10,7 +11,9 @@ fn main() {
     println!("\nStem processor observing the system...\n");
     println!("Press Ctrl+C to stop.\n");
 
-    let mut stem = StemProcessor::with_params(0.5, 0.85);
+    // Логические клетки дифференцируются в нейроны
+    let mut stem = StemProcessor::with_params(0.5, 0.85)
+        .with_cell_factory(CellRole::Logic, neuron_factory(system_clock()));
     let mut cycle = 0;
 
     loop {
@@ -58,7 +61,8 @@ fn render_genesis(stem: &StemProcessor, activity: f64, cycle: u64) {
     print!("\r[{:04}] ", cycle);
     print!("Load: {}{}{}\x1b[0m {:.2} | ", load_color, load_vis, load_empty, stem.load);
     print!("Activity: \x1b[96m{}{}\x1b[0m {:.2} | ", activity_vis, activity_empty, activity);
-    print!("Cells: \x1b[95m{:3}\x1b[0m Gen: {}", stem.cell_count(), stem.generation);
+    print!("Cells: \x1b[95m{:3}\x1b[0m Gen: {} | ", stem.cell_count(), stem.generation);
+    print!("Output: {:.2}", stem.output());
 
     std::io::stdout().flush().unwrap();
 }
@@ -79,6 +83,14 @@ fn print_statistics(stem: &StemProcessor) {
     println!("║ 🔵 Sensor cells: {:3}            ║", sensor_count);
     println!("║ 🟢 Logic cells:  {:3}            ║", logic_count);
     println!("║ 🟡 Motor cells:  {:3}            ║", motor_count);
+
+    // Специализированные роли показываем только если они есть
+    for role in [CellRole::Memory, CellRole::Hub, CellRole::Inhibitor, CellRole::Explorer] {
+        let count = distribution.get(&role).copied().unwrap_or(0);
+        if count > 0 {
+            println!("║ {} {:<10} {:3}            ║", role_icon(role), format!("{:?}:", role), count);
+        }
+    }
     println!("╚═══════════════════════════════════╝\n");
 
     // Показываем последние созданные клетки
@@ -89,17 +101,11 @@ fn print_statistics(stem: &StemProcessor) {
         cells.reverse();
 
         for (i, cell) in cells.iter().take(5).enumerate() {
-            let age_sec = cell.age_millis() / 1000;
-            let role_icon = match cell.role {
-                CellRole::Sensor => "🔵",
-                CellRole::Logic => "🟢",
-                CellRole::Motor => "🟡",
-            };
-
+            let age_sec = cell.age_at(stem.clock().now_millis()) / 1000;
             println!(
                 "  {}. {} {} (gen {}, age {}s)",
                 i + 1,
-                role_icon,
+                role_icon(cell.role),
                 cell.id,
                 cell.generation,
                 age_sec
@@ -108,3 +114,16 @@ fn print_statistics(stem: &StemProcessor) {
         println!();
     }
 }
+
+/// Иконка роли клетки
+fn role_icon(role: CellRole) -> &'static str {
+    match role {
+        CellRole::Sensor => "🔵",
+        CellRole::Logic => "🟢",
+        CellRole::Motor => "🟡",
+        CellRole::Memory => "🟣",
+        CellRole::Hub => "🟠",
+        CellRole::Inhibitor => "🔴",
+        CellRole::Explorer => "⚪",
+    }
+}
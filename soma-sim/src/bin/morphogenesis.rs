@@ -0,0 +1,91 @@
+use soma_core::{CellRole, MorphogenField, MorphogenSelector, StemProcessor};
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Размер плоского поля морфогенов
+const FIELD_SIZE: usize = 24;
+
+/// Количество циклов роста
+const CYCLES: u64 = 300;
+
+fn main() {
+    println!("\n╔═══════════════════════════════════════╗");
+    println!("║  🧫 SOMA Morphogenesis Simulation   ║");
+    println!("╚═══════════════════════════════════════╝");
+    println!("\nCells emit role morphogens; new cells differentiate");
+    println!("into the role least present around their birth site.\n");
+
+    // Латеральное ингибирование складывает ткань в чередующийся узор
+    let field = MorphogenField::planar(FIELD_SIZE, FIELD_SIZE, 1.0)
+        .with_diffusion(0.3)
+        .with_decay(0.1);
+    let mut stem = StemProcessor::with_params(0.4, 0.7)
+        .with_seed(42)
+        .with_morphogen_field(field)
+        .with_role_selector(MorphogenSelector::new());
+
+    for cycle in 0..CYCLES {
+        // Пульсирующая нагрузка стимулирует деление волнами
+        let activity = ((cycle as f64 * 0.2).sin().abs() * 0.5) + 0.4;
+
+        // Локальный стимул: клетка со всплеском активности делится следующей
+        let count = stem.cell_count();
+        if count > 0 {
+            let index = (cycle as usize * 7919) % count;
+            let target = stem.population().keys().nth(index);
+            if let Some(id) = target {
+                stem.deliver_signal(&id, 1.0);
+            }
+        }
+
+        stem.sense(activity);
+        stem.tick();
+
+        if cycle % 50 == 49 {
+            render_tissue(&stem, cycle + 1);
+            sleep(Duration::from_millis(300));
+        }
+    }
+}
+
+/// Отрисовка ткани: каждая ячейка поля показывает роль клетки в ней
+fn render_tissue(stem: &StemProcessor, cycle: u64) {
+    let mut grid = vec![vec!['·'; FIELD_SIZE]; FIELD_SIZE];
+    let mut placed = 0;
+
+    for cell in stem.cells().values() {
+        let Some(position) = cell.position else { continue };
+        let (x, y) = (position.x as usize, position.y as usize);
+        if x < FIELD_SIZE && y < FIELD_SIZE {
+            grid[y][x] = role_glyph(cell.role);
+            placed += 1;
+        }
+    }
+
+    println!("Cycle {:4} | Cells: {:4} | Gen: {:4}", cycle, placed, stem.generation);
+    for row in grid {
+        let line: String = row.iter().flat_map(|c| [*c, ' ']).collect();
+        println!("  {}", line);
+    }
+
+    let distribution = stem.role_distribution();
+    println!(
+        "  S: {}  L: {}  M: {}\n",
+        distribution.get(&CellRole::Sensor).unwrap_or(&0),
+        distribution.get(&CellRole::Logic).unwrap_or(&0),
+        distribution.get(&CellRole::Motor).unwrap_or(&0),
+    );
+}
+
+/// Символ роли клетки
+fn role_glyph(role: CellRole) -> char {
+    match role {
+        CellRole::Sensor => 'S',
+        CellRole::Logic => 'L',
+        CellRole::Motor => 'M',
+        CellRole::Memory => 'm',
+        CellRole::Hub => 'H',
+        CellRole::Inhibitor => 'I',
+        CellRole::Explorer => 'E',
+    }
+}
@@ -0,0 +1,60 @@
+use soma_vnp::{NetworkBuilder, NeuronId};
+
+/// Количество нейронов в каждой сети
+const SIZE: usize = 64;
+
+/// Количество шагов симуляции
+const STEPS: u64 = 200;
+
+/// Зерно генерации случайных топологий
+const SEED: u64 = 42;
+
+fn main() {
+    println!("\n╔═══════════════════════════════════════╗");
+    println!("║  🕸️  SOMA Topology Comparison        ║");
+    println!("╚═══════════════════════════════════════╝");
+    println!("\nSame neurons, same stimulus - different wiring.\n");
+
+    let base = NetworkBuilder::new(SIZE)
+        .with_seed(SEED)
+        .with_weight(0.4)
+        .with_delay(2)
+        .with_neuron(0.7, 0.2);
+
+    let builders = [
+        ("all-to-all", base.clone().all_to_all().with_weight(0.02)),
+        ("ring", base.clone().ring(2)),
+        ("grid 8x8", base.clone().grid(8, 8)),
+        ("small-world", base.clone().small_world(2, 0.1)),
+        ("scale-free", base.clone().scale_free(2)),
+    ];
+
+    println!("  {:<12} {:>8} {:>8} {:>8} {:>10}", "topology", "synapses", "spikes", "active", "last spike");
+    for (name, builder) in builders {
+        let mut network = builder.build();
+        let seed_neuron: NeuronId = network.neuron_ids().next().expect("network is not empty");
+
+        let mut spikes = 0;
+        let mut last = 0;
+        for step in 0..STEPS {
+            // Короткий импульс в первый нейрон, дальше сеть живёт сама
+            let input = if step < 5 { vec![(seed_neuron, 1.0)] } else { Vec::new() };
+            let fired = network.step(&input).len();
+            spikes += fired;
+            if fired > 0 {
+                last = network.time();
+            }
+        }
+
+        let active = network.neuron_ids().filter(|id| network.last_spike(*id).is_some()).count();
+        println!(
+            "  {:<12} {:>8} {:>8} {:>8} {:>10}",
+            name,
+            network.synapse_count(),
+            spikes,
+            active,
+            last
+        );
+    }
+    println!();
+}
@@ -0,0 +1,364 @@
+//! Построение нейронных сетей стандартных топологий
+//!
+//! [`NetworkBuilder`] генерирует [`NeuralNetwork`] заданной топологии:
+//! полносвязную, кольцо, двумерную решётку, «малый мир» Уоттса-Строгаца
+//! и безмасштабную сеть Барабаши-Альберт. Генерация воспроизводима -
+//! случайные топологии используют генератор с заданным зерном. Сила
+//! связи может убывать с расстоянием между нейронами.
+
+use std::collections::BTreeSet;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::config::neuron;
+use crate::network::{NeuralNetwork, NeuronId};
+use crate::neuron::Neuron;
+use crate::plasticity::StdpRule;
+
+/// Топология сети
+///
+/// Связи всех топологий двунаправленные: ребро между нейронами даёт по
+/// синапсу в каждую сторону
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Topology {
+    /// Каждый нейрон связан с каждым
+    AllToAll,
+    /// Кольцо: нейрон связан с `neighbours` ближайшими с каждой стороны
+    Ring { neighbours: usize },
+    /// Решётка `width × height`, связи с соседями по горизонтали и вертикали
+    Grid { width: usize, height: usize },
+    /// Кольцо, рёбра которого перебрасываются с вероятностью `rewire`
+    SmallWorld { neighbours: usize, rewire: f64 },
+    /// Предпочтительное присоединение: новый нейрон получает `attachments` рёбер
+    ScaleFree { attachments: usize },
+}
+
+/// Построитель нейронной сети
+///
+/// Расстояние между нейронами решётки - евклидово между узлами, для
+/// остальных топологий - число шагов по кольцу нейронов
+#[derive(Debug, Clone)]
+pub struct NetworkBuilder {
+    size: usize,
+    topology: Topology,
+    seed: u64,
+    weight: f64,
+    delay: u32,
+    /// Масштаб убывания силы связи с расстоянием (None - сила постоянна)
+    distance_scale: Option<f64>,
+    threshold: f64,
+    decay: f64,
+    plasticity: Option<StdpRule>,
+}
+
+impl NetworkBuilder {
+    /// Сеть из `size` нейронов (полносвязная по умолчанию)
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            topology: Topology::AllToAll,
+            seed: 0,
+            weight: 1.0,
+            delay: 1,
+            distance_scale: None,
+            threshold: neuron::DEFAULT_THRESHOLD,
+            decay: neuron::DEFAULT_DECAY,
+            plasticity: None,
+        }
+    }
+
+    /// Установить топологию
+    ///
+    /// Вероятность переброски рёбер «малого мира» ограничивается [0, 1]
+    /// (NaN считается нулём)
+    pub fn with_topology(mut self, mut topology: Topology) -> Self {
+        match &mut topology {
+            Topology::Grid { width, height } => self.size = *width * *height,
+            Topology::SmallWorld { rewire, .. } => {
+                *rewire = if rewire.is_nan() { 0.0 } else { rewire.clamp(0.0, 1.0) };
+            }
+            _ => {}
+        }
+        self.topology = topology;
+        self
+    }
+
+    /// Полносвязная сеть
+    pub fn all_to_all(self) -> Self {
+        self.with_topology(Topology::AllToAll)
+    }
+
+    /// Кольцо
+    pub fn ring(self, neighbours: usize) -> Self {
+        self.with_topology(Topology::Ring { neighbours })
+    }
+
+    /// Решётка (размер сети становится `width × height`)
+    pub fn grid(self, width: usize, height: usize) -> Self {
+        self.with_topology(Topology::Grid { width, height })
+    }
+
+    /// «Малый мир» Уоттса-Строгаца
+    pub fn small_world(self, neighbours: usize, rewire: f64) -> Self {
+        self.with_topology(Topology::SmallWorld { neighbours, rewire })
+    }
+
+    /// Безмасштабная сеть Барабаши-Альберт
+    pub fn scale_free(self, attachments: usize) -> Self {
+        self.with_topology(Topology::ScaleFree { attachments })
+    }
+
+    /// Зерно генератора случайных чисел
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Базовый вес синапсов
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Задержка синапсов в шагах
+    pub fn with_delay(mut self, delay: u32) -> Self {
+        self.delay = delay.max(1);
+        self
+    }
+
+    /// Сила связи убывает с расстоянием: `weight * exp(-distance / scale)`
+    pub fn with_distance_decay(mut self, scale: f64) -> Self {
+        self.distance_scale = (scale > 0.0).then_some(scale);
+        self
+    }
+
+    /// Параметры нейронов сети
+    pub fn with_neuron(mut self, threshold: f64, decay: f64) -> Self {
+        self.threshold = threshold;
+        self.decay = decay;
+        self
+    }
+
+    /// Включить пластичность синапсов построенной сети
+    pub fn with_plasticity(mut self, rule: StdpRule) -> Self {
+        self.plasticity = Some(rule);
+        self
+    }
+
+    /// Топология
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Количество нейронов
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Рёбра топологии (пары `i < j`) в детерминированном порядке
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let edges = match self.topology {
+            Topology::AllToAll => (0..self.size)
+                .flat_map(|i| (i + 1..self.size).map(move |j| (i, j)))
+                .collect(),
+            Topology::Ring { neighbours } => ring_edges(self.size, neighbours),
+            Topology::Grid { width, height } => grid_edges(width, height),
+            Topology::SmallWorld { neighbours, rewire } => {
+                small_world_edges(self.size, neighbours, rewire, &mut rng)
+            }
+            Topology::ScaleFree { attachments } => scale_free_edges(self.size, attachments, &mut rng),
+        };
+        edges.into_iter().collect::<BTreeSet<_>>().into_iter().collect()
+    }
+
+    /// Расстояние между нейронами (бесконечность для номеров вне сети)
+    pub fn distance(&self, a: usize, b: usize) -> f64 {
+        if a >= self.size || b >= self.size {
+            return f64::INFINITY;
+        }
+        match self.topology {
+            Topology::Grid { width, .. } if width > 0 => {
+                let (ax, ay) = ((a % width) as f64, (a / width) as f64);
+                let (bx, by) = ((b % width) as f64, (b / width) as f64);
+                ((ax - bx).powi(2) + (ay - by).powi(2)).sqrt()
+            }
+            _ => {
+                let steps = a.abs_diff(b);
+                steps.min(self.size - steps) as f64
+            }
+        }
+    }
+
+    /// Вес синапса между нейронами с учётом расстояния
+    pub fn weight_between(&self, a: usize, b: usize) -> f64 {
+        match self.distance_scale {
+            Some(scale) => self.weight * (-self.distance(a, b) / scale).exp(),
+            None => self.weight,
+        }
+    }
+
+    /// Построить сеть
+    ///
+    /// Нейрон с номером `i` получает `NeuronId` с индексом `i`
+    pub fn build(&self) -> NeuralNetwork {
+        let mut network = NeuralNetwork::new();
+        network.set_plasticity(self.plasticity);
+        let ids: Vec<NeuronId> = (0..self.size)
+            .map(|_| network.add_neuron(Neuron::with_params(self.threshold, self.decay, 1.0)))
+            .collect();
+
+        for (a, b) in self.edges() {
+            let weight = self.weight_between(a, b);
+            for (pre, post) in [(a, b), (b, a)] {
+                network
+                    .connect(ids[pre], ids[post], weight, self.delay)
+                    .expect("neurons were just added");
+            }
+        }
+        network
+    }
+}
+
+/// Рёбра кольца: каждый узел связан с `k` следующими
+fn ring_edges(n: usize, k: usize) -> Vec<(usize, usize)> {
+    let k = k.min(n / 2);
+    (0..n)
+        .flat_map(|i| (1..=k).map(move |d| ordered(i, (i + d) % n)))
+        .collect()
+}
+
+/// Рёбра решётки: соседи справа и снизу
+fn grid_edges(width: usize, height: usize) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            if x + 1 < width {
+                edges.push((i, i + 1));
+            }
+            if y + 1 < height {
+                edges.push((i, i + width));
+            }
+        }
+    }
+    edges
+}
+
+/// Модель Уоттса-Строгаца: дальний конец каждого ребра кольца с
+/// вероятностью `p` перебрасывается на случайный узел (без петель и дублей)
+fn small_world_edges(n: usize, k: usize, p: f64, rng: &mut StdRng) -> Vec<(usize, usize)> {
+    let mut edges: BTreeSet<(usize, usize)> = ring_edges(n, k).into_iter().collect();
+    let k = k.min(n / 2);
+
+    for i in 0..n {
+        for d in 1..=k {
+            let edge = ordered(i, (i + d) % n);
+            if !rng.gen_bool(p) || !edges.contains(&edge) {
+                continue;
+            }
+            let free: Vec<usize> = (0..n)
+                .filter(|&j| j != i && !edges.contains(&ordered(i, j)))
+                .collect();
+            if free.is_empty() {
+                continue;
+            }
+            edges.remove(&edge);
+            edges.insert(ordered(i, free[rng.gen_range(0..free.len())]));
+        }
+    }
+    edges.into_iter().collect()
+}
+
+/// Модель Барабаши-Альберт: затравка из `m + 1` связанных узлов, каждый
+/// следующий узел присоединяется к `m` узлам с вероятностью по степени
+fn scale_free_edges(n: usize, m: usize, rng: &mut StdRng) -> Vec<(usize, usize)> {
+    let m = m.max(1);
+    let seed = (m + 1).min(n);
+    let mut edges: Vec<(usize, usize)> = (0..seed)
+        .flat_map(|i| (i + 1..seed).map(move |j| (i, j)))
+        .collect();
+    // Каждый узел встречается здесь столько раз, какова его степень
+    let mut endpoints: Vec<usize> = edges.iter().flat_map(|&(a, b)| [a, b]).collect();
+
+    for node in seed..n {
+        let mut targets = BTreeSet::new();
+        while targets.len() < m.min(node) {
+            targets.insert(endpoints[rng.gen_range(0..endpoints.len())]);
+        }
+        for target in targets {
+            edges.push((target, node));
+            endpoints.extend([target, node]);
+        }
+    }
+    edges
+}
+
+fn ordered(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn degrees(builder: &NetworkBuilder) -> Vec<usize> {
+        let mut degrees = vec![0; builder.size()];
+        for (a, b) in builder.edges() {
+            degrees[a] += 1;
+            degrees[b] += 1;
+        }
+        degrees
+    }
+
+    #[test]
+    fn test_regular_topologies() {
+        let all = NetworkBuilder::new(5).build();
+        assert_eq!(all.synapse_count(), 20);
+
+        let ring = NetworkBuilder::new(6).ring(2);
+        assert!(degrees(&ring).iter().all(|&d| d == 4));
+        assert_eq!(ring.distance(0, 5), 1.0);
+
+        let grid = NetworkBuilder::new(0).grid(3, 2);
+        assert_eq!(grid.size(), 6);
+        assert_eq!(grid.edges().len(), 7);
+        assert_eq!(grid.build().synapse_count(), 14);
+        assert_eq!(grid.distance(0, 4), 2f64.sqrt());
+        assert_eq!(grid.distance(0, 6), f64::INFINITY);
+        assert_eq!(ring.distance(7, 0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_random_topologies_are_reproducible() {
+        let small = NetworkBuilder::new(20).small_world(2, 0.3).with_seed(7);
+        assert_eq!(small.edges(), small.clone().edges());
+        assert_eq!(small.edges().len(), 40);
+        assert_ne!(small.edges(), NetworkBuilder::new(20).ring(2).edges());
+        assert_ne!(small.edges(), small.clone().with_seed(8).edges());
+
+        for rewire in [f64::NAN, -1.0, 2.0] {
+            let raw = NetworkBuilder::new(10).with_topology(Topology::SmallWorld { neighbours: 2, rewire });
+            assert_eq!(raw.edges().len(), 20);
+        }
+
+        let free = NetworkBuilder::new(50).scale_free(2).with_seed(3);
+        assert_eq!(free.edges(), free.clone().edges());
+        assert_eq!(free.edges().len(), 3 + 47 * 2);
+        let degrees = degrees(&free);
+        assert!(degrees.iter().all(|&d| d >= 2));
+        assert!(*degrees.iter().max().unwrap() > 8);
+    }
+
+    #[test]
+    fn test_distance_dependent_weights() {
+        let builder = NetworkBuilder::new(8).with_weight(1.0).with_distance_decay(2.0);
+        let network = builder.build();
+
+        let (_, near) = network.synapses().find(|(_, s)| s.pre.index() == 0 && s.post.index() == 1).unwrap();
+        let (_, far) = network.synapses().find(|(_, s)| s.pre.index() == 0 && s.post.index() == 4).unwrap();
+        assert!((near.weight - (-0.5f64).exp()).abs() < 1e-12);
+        assert!((far.weight - (-2.0f64).exp()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,70 @@
+//! Конфигурационные константы для SOMA VNP
+
+/// Параметры пластичности синапсов нейронных сетей (STDP)
+pub mod stdp {
+    /// Прирост веса при срабатывании пост- после пресинаптического нейрона
+    pub const DEFAULT_POTENTIATION: f64 = 0.01;
+
+    /// Снижение веса при срабатывании пре- после постсинаптического нейрона
+    pub const DEFAULT_DEPRESSION: f64 = 0.012;
+
+    /// Постоянная времени окна потенциации (шагов)
+    pub const DEFAULT_TAU_POTENTIATION: f64 = 20.0;
+
+    /// Постоянная времени окна депрессии (шагов)
+    pub const DEFAULT_TAU_DEPRESSION: f64 = 20.0;
+
+    /// Минимальный вес пластичного синапса
+    pub const DEFAULT_MIN_WEIGHT: f64 = 0.0;
+
+    /// Максимальный вес пластичного синапса
+    pub const DEFAULT_MAX_WEIGHT: f64 = 1.0;
+}
+
+/// Параметры мышечных юнитов VNP
+pub mod muscle {
+    /// Прирост возбуждения мышцы от одного спайка
+    pub const DEFAULT_TWITCH: f64 = 0.2;
+
+    /// Доля возбуждения, затухающая за шаг
+    pub const DEFAULT_DRIVE_DECAY: f64 = 0.2;
+
+    /// Скорость нарастания активации (доля разницы за шаг)
+    pub const DEFAULT_RISE: f64 = 0.3;
+
+    /// Скорость спада активации (доля разницы за шаг)
+    pub const DEFAULT_FALL: f64 = 0.1;
+
+    /// Расход ресурса мышцы за шаг при полной активации
+    pub const DEFAULT_FATIGUE_RATE: f64 = 0.01;
+
+    /// Восстановление ресурса мышцы (доля недостающего за шаг)
+    pub const DEFAULT_RECOVERY_RATE: f64 = 0.005;
+}
+
+/// Параметры событийного симулятора спайковых сетей (время в микросекундах)
+pub mod simulation {
+    /// Порог срабатывания нейрона
+    pub const DEFAULT_THRESHOLD: f64 = 0.7;
+
+    /// Постоянная времени затухания потенциала
+    pub const DEFAULT_TAU_US: f64 = 10_000.0;
+
+    /// Рефрактерный период после спайка
+    pub const DEFAULT_REFRACTORY_US: u64 = 2_000;
+
+    /// Длительность шага сети при переносе задержек синапсов
+    pub const DEFAULT_STEP_US: u64 = 1_000;
+}
+
+/// Параметры нейронов по умолчанию
+pub mod neuron {
+    /// Порог активации
+    pub const DEFAULT_THRESHOLD: f64 = 0.7;
+
+    /// Доля потенциала, теряемая за шаг
+    pub const DEFAULT_DECAY: f64 = 0.1;
+
+    /// Вес (усиление входа)
+    pub const DEFAULT_WEIGHT: f64 = 1.0;
+}
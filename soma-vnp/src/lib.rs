@@ -6,7 +6,16 @@
 //! ## Компоненты
 //!
 //! - **Neuron**: Виртуальный нейрон с порогом активации
-//! - **NeuronLayer**: Слой связанных нейронов
+//! - **NeuronModel**: Модели нейронов - LIF, Ижикевич, AdEx
+//! - **NeuronLayer**: Слой связанных нейронов (модели можно смешивать)
+//! - **NeuralNetwork**: Сеть нейронов с синапсами и задержками спайков
+//! - **MuscleUnit**: Мышца - сила из потока спайков с утомлением
+//! - **HybridProcessor**: Нейронная сеть, управляющая мышцами
+//! - **EventSimulator**: Событийная симуляция сети в модельном времени
+//! - **VnpProcessor**: Процессор из ProcessorConfig с обучением по ошибке
+//! - **NetworkBuilder**: Генерация сетей стандартных топологий
+//! - **StdpRule**: Обучение синапсов по времени спайков (STDP)
+//! - **neuron_factory**: Дифференциация клеток StemProcessor в нейроны
 //!
 //! ## Примеры
 //!
@@ -20,9 +29,24 @@
 //! let output = neuron.flow();
 //! ```
 
+pub mod builder;
+pub mod config;
+pub mod model;
+pub mod muscle;
+pub mod network;
 pub mod neuron;
+pub mod plasticity;
+pub mod processor;
+pub mod simulator;
 
-pub use neuron::{Neuron, NeuronLayer};
+pub use builder::{NetworkBuilder, Topology};
+pub use model::{AdExNeuron, IzhikevichNeuron, LifNeuron, NeuronModel};
+pub use muscle::{muscle_factory, HybridProcessor, MuscleUnit};
+pub use network::{NetworkError, NeuralNetwork, NeuronId, Synapse, SynapseId};
+pub use neuron::{neuron_factory, Neuron, NeuronLayer};
+pub use plasticity::{SpikeTrace, StdpRule};
+pub use processor::VnpProcessor;
+pub use simulator::{EventNeuron, EventSimulator, SpikeEvent};
 
 /// Тип процессора VNP
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
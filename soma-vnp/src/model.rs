@@ -0,0 +1,460 @@
+//! Модели нейронов
+//!
+//! [`NeuronModel`] - общий интерфейс спайковых моделей: шаг модели
+//! получает входной ток и сообщает о спайке. Один шаг соответствует
+//! одной миллисекунде модельного времени. Слои и сети хранят нейроны
+//! как `Box<dyn NeuronModel>` и могут смешивать модели:
+//!
+//! - [`Neuron`](crate::Neuron) - исходный нейрон SOMA со сбросом в ноль
+//! - [`LifNeuron`] - интегрировать-и-сработать с утечкой и рефрактерностью
+//! - [`IzhikevichNeuron`] - модель Ижикевича (пачки, «болтовня», быстрые спайки)
+//! - [`AdExNeuron`] - адаптивная экспоненциальная модель (адаптация, пачки)
+
+use crate::config::neuron;
+
+/// Спайковая модель нейрона
+pub trait NeuronModel: Send {
+    /// Выполнить шаг модели с входным током, вернуть true при спайке
+    fn step(&mut self, current: f64) -> bool;
+
+    /// Мембранный потенциал в единицах модели
+    fn potential(&self) -> f64;
+
+    /// Активен ли нейрон (для спайковых моделей - был ли спайк на последнем шаге)
+    fn is_activated(&self) -> bool;
+
+    /// Вернуть модель в состояние покоя
+    fn reset(&mut self);
+
+    /// Название модели
+    fn name(&self) -> &'static str;
+
+    /// Обработать вход в режиме слоя и вернуть выходной сигнал
+    ///
+    /// По умолчанию - шаг модели, выход 1.0 при спайке
+    fn process(&mut self, input: f64) -> f64 {
+        if self.step(input) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Нейрон «интегрировать-и-сработать» с утечкой и рефрактерным периодом
+///
+/// Потенциал стремится к потенциалу покоя с долей `leak` за шаг и
+/// суммирует входной ток. После спайка потенциал сбрасывается, и
+/// `refractory` шагов нейрон не воспринимает вход
+#[derive(Debug, Clone, PartialEq)]
+pub struct LifNeuron {
+    potential: f64,
+    /// Потенциал покоя
+    pub rest: f64,
+    /// Потенциал после спайка
+    pub reset: f64,
+    /// Порог срабатывания
+    pub threshold: f64,
+    /// Доля отклонения от покоя, теряемая за шаг (0.0 - 1.0)
+    pub leak: f64,
+    /// Рефрактерный период в шагах
+    pub refractory: u32,
+    refractory_left: u32,
+    spiked: bool,
+}
+
+impl LifNeuron {
+    /// Создать нейрон с порогом и утечкой нейрона SOMA и рефрактерностью 2 шага
+    pub fn new() -> Self {
+        Self {
+            potential: 0.0,
+            rest: 0.0,
+            reset: 0.0,
+            threshold: neuron::DEFAULT_THRESHOLD,
+            leak: neuron::DEFAULT_DECAY,
+            refractory: 2,
+            refractory_left: 0,
+            spiked: false,
+        }
+    }
+
+    /// Установить порог срабатывания
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Установить долю утечки за шаг
+    pub fn with_leak(mut self, leak: f64) -> Self {
+        self.leak = leak.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Установить рефрактерный период
+    pub fn with_refractory(mut self, steps: u32) -> Self {
+        self.refractory = steps;
+        self
+    }
+
+    /// Установить потенциалы покоя и сброса
+    pub fn with_potentials(mut self, rest: f64, reset: f64) -> Self {
+        self.rest = rest;
+        self.reset = reset;
+        self.potential = rest;
+        self
+    }
+
+    /// Находится ли нейрон в рефрактерном периоде
+    pub fn is_refractory(&self) -> bool {
+        self.refractory_left > 0
+    }
+}
+
+impl Default for LifNeuron {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NeuronModel for LifNeuron {
+    fn step(&mut self, current: f64) -> bool {
+        if self.refractory_left > 0 {
+            self.refractory_left -= 1;
+            self.spiked = false;
+            return false;
+        }
+        self.potential += (self.rest - self.potential) * self.leak + current;
+        self.spiked = self.potential >= self.threshold;
+        if self.spiked {
+            self.potential = self.reset;
+            self.refractory_left = self.refractory;
+        }
+        self.spiked
+    }
+
+    fn potential(&self) -> f64 {
+        self.potential
+    }
+
+    fn is_activated(&self) -> bool {
+        self.spiked
+    }
+
+    fn reset(&mut self) {
+        self.potential = self.rest;
+        self.refractory_left = 0;
+        self.spiked = false;
+    }
+
+    fn name(&self) -> &'static str {
+        "lif"
+    }
+}
+
+/// Модель Ижикевича
+///
+/// `v' = 0.04v² + 5v + 140 - u + I`, `u' = a(bv - u)`; при `v ≥ 30` мВ
+/// `v ← c`, `u ← u + d`. Ток умножается на `gain`, типичный ток
+/// модели - около 10
+#[derive(Debug, Clone, PartialEq)]
+pub struct IzhikevichNeuron {
+    v: f64,
+    u: f64,
+    /// Скорость восстановления
+    pub a: f64,
+    /// Чувствительность восстановления к потенциалу
+    pub b: f64,
+    /// Потенциал после спайка, мВ
+    pub c: f64,
+    /// Прирост восстановления после спайка
+    pub d: f64,
+    /// Множитель входного тока
+    pub gain: f64,
+    spiked: bool,
+}
+
+impl IzhikevichNeuron {
+    /// Порог регистрации спайка, мВ
+    const PEAK: f64 = 30.0;
+
+    /// Создать нейрон с заданными параметрами
+    pub fn new(a: f64, b: f64, c: f64, d: f64) -> Self {
+        Self {
+            v: c.min(-65.0),
+            u: b * c.min(-65.0),
+            a,
+            b,
+            c,
+            d,
+            gain: 1.0,
+            spiked: false,
+        }
+    }
+
+    /// Регулярные спайки (типичный корковый возбуждающий нейрон)
+    pub fn regular_spiking() -> Self {
+        Self::new(0.02, 0.2, -65.0, 8.0)
+    }
+
+    /// Пачка в начале стимула, затем регулярные спайки
+    pub fn intrinsically_bursting() -> Self {
+        Self::new(0.02, 0.2, -55.0, 4.0)
+    }
+
+    /// Повторяющиеся пачки («болтовня»)
+    pub fn chattering() -> Self {
+        Self::new(0.02, 0.2, -50.0, 2.0)
+    }
+
+    /// Быстрые спайки без адаптации (тормозный интернейрон)
+    pub fn fast_spiking() -> Self {
+        Self::new(0.1, 0.2, -65.0, 2.0)
+    }
+
+    /// Установить множитель входного тока
+    pub fn with_gain(mut self, gain: f64) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Переменная восстановления
+    pub fn recovery(&self) -> f64 {
+        self.u
+    }
+}
+
+impl NeuronModel for IzhikevichNeuron {
+    fn step(&mut self, current: f64) -> bool {
+        let current = current * self.gain;
+        // Два полушага по 0.5 мс для устойчивости, как в исходной модели
+        for _ in 0..2 {
+            if self.v < Self::PEAK {
+                self.v += 0.5 * (0.04 * self.v * self.v + 5.0 * self.v + 140.0 - self.u + current);
+            }
+        }
+        self.u += self.a * (self.b * self.v - self.u);
+
+        self.spiked = self.v >= Self::PEAK;
+        if self.spiked {
+            self.v = self.c;
+            self.u += self.d;
+        }
+        self.spiked
+    }
+
+    fn potential(&self) -> f64 {
+        self.v
+    }
+
+    fn is_activated(&self) -> bool {
+        self.spiked
+    }
+
+    fn reset(&mut self) {
+        self.v = self.c.min(-65.0);
+        self.u = self.b * self.v;
+        self.spiked = false;
+    }
+
+    fn name(&self) -> &'static str {
+        "izhikevich"
+    }
+}
+
+/// Адаптивная экспоненциальная модель (AdEx, Бретт-Герстнер)
+///
+/// `C V' = -gL(V - EL) + gL ΔT exp((V - VT)/ΔT) - w + I`,
+/// `τw w' = a(V - EL) - w`; при `V ≥ peak` `V ← Vr`, `w ← w + b`.
+/// Единицы: мВ, мс, нС, пФ, пА. Ток умножается на `gain` (пА на единицу входа)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdExNeuron {
+    v: f64,
+    w: f64,
+    /// Ёмкость мембраны, пФ
+    pub capacitance: f64,
+    /// Проводимость утечки, нС
+    pub leak_conductance: f64,
+    /// Потенциал покоя, мВ
+    pub rest: f64,
+    /// Порог экспоненциального роста, мВ
+    pub threshold: f64,
+    /// Крутизна экспоненциального роста, мВ
+    pub slope: f64,
+    /// Постоянная времени адаптации, мс
+    pub tau_adaptation: f64,
+    /// Подпороговая адаптация, нС
+    pub a: f64,
+    /// Приращение адаптации после спайка, пА
+    pub b: f64,
+    /// Потенциал после спайка, мВ
+    pub reset: f64,
+    /// Потенциал регистрации спайка, мВ
+    pub peak: f64,
+    /// Множитель входного тока, пА
+    pub gain: f64,
+    spiked: bool,
+}
+
+impl AdExNeuron {
+    /// Подшагов интегрирования на шаг модели (0.1 мс)
+    const SUBSTEPS: u32 = 10;
+
+    /// Тонические спайки без адаптации
+    pub fn tonic() -> Self {
+        Self::with_params(200.0, 10.0, -70.0, -50.0, 2.0, 30.0, 2.0, 0.0, -58.0)
+    }
+
+    /// Спайки с адаптацией - интервалы растут при постоянном токе
+    pub fn adapting() -> Self {
+        Self::with_params(200.0, 12.0, -70.0, -50.0, 2.0, 300.0, 2.0, 60.0, -58.0)
+    }
+
+    /// Регулярные пачки
+    pub fn bursting() -> Self {
+        Self::with_params(200.0, 10.0, -58.0, -50.0, 2.0, 120.0, 2.0, 100.0, -46.0)
+    }
+
+    /// Создать нейрон с заданными параметрами (C, gL, EL, VT, ΔT, τw, a, b, Vr)
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_params(
+        capacitance: f64,
+        leak_conductance: f64,
+        rest: f64,
+        threshold: f64,
+        slope: f64,
+        tau_adaptation: f64,
+        a: f64,
+        b: f64,
+        reset: f64,
+    ) -> Self {
+        Self {
+            v: rest,
+            w: 0.0,
+            capacitance,
+            leak_conductance,
+            rest,
+            threshold,
+            slope: slope.max(f64::EPSILON),
+            tau_adaptation: tau_adaptation.max(f64::EPSILON),
+            a,
+            b,
+            reset,
+            peak: 0.0,
+            gain: 1000.0,
+            spiked: false,
+        }
+    }
+
+    /// Установить множитель входного тока
+    pub fn with_gain(mut self, gain: f64) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Ток адаптации, пА
+    pub fn adaptation(&self) -> f64 {
+        self.w
+    }
+}
+
+impl NeuronModel for AdExNeuron {
+    fn step(&mut self, current: f64) -> bool {
+        let current = current * self.gain;
+        let dt = 1.0 / Self::SUBSTEPS as f64;
+        self.spiked = false;
+
+        for _ in 0..Self::SUBSTEPS {
+            let exponent = ((self.v - self.threshold) / self.slope).min(20.0);
+            let dv = (-self.leak_conductance * (self.v - self.rest)
+                + self.leak_conductance * self.slope * exponent.exp()
+                - self.w
+                + current)
+                / self.capacitance;
+            let dw = (self.a * (self.v - self.rest) - self.w) / self.tau_adaptation;
+            self.v += dt * dv;
+            self.w += dt * dw;
+
+            if self.v >= self.peak {
+                self.v = self.reset;
+                self.w += self.b;
+                self.spiked = true;
+            }
+        }
+        self.spiked
+    }
+
+    fn potential(&self) -> f64 {
+        self.v
+    }
+
+    fn is_activated(&self) -> bool {
+        self.spiked
+    }
+
+    fn reset(&mut self) {
+        self.v = self.rest;
+        self.w = 0.0;
+        self.spiked = false;
+    }
+
+    fn name(&self) -> &'static str {
+        "adex"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Шаги, на которых нейрон сработал при постоянном токе
+    fn spike_times(model: &mut dyn NeuronModel, current: f64, steps: usize) -> Vec<usize> {
+        (0..steps).filter(|_| model.step(current)).collect()
+    }
+
+    fn intervals(times: &[usize]) -> Vec<usize> {
+        times.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    #[test]
+    fn test_lif_refractory_period() {
+        let mut lif = LifNeuron::new().with_leak(0.0).with_refractory(3);
+        assert!(lif.step(1.0));
+        assert!(lif.is_refractory());
+        for _ in 0..3 {
+            assert!(!lif.step(1.0));
+        }
+        assert!(lif.step(1.0));
+        assert_eq!(intervals(&spike_times(&mut lif, 1.0, 20)), vec![4; 4]);
+    }
+
+    #[test]
+    fn test_izhikevich_bursting_and_tonic() {
+        let regular = intervals(&spike_times(&mut IzhikevichNeuron::regular_spiking(), 10.0, 400));
+        let chattering = intervals(&spike_times(&mut IzhikevichNeuron::chattering(), 10.0, 400));
+
+        // Регулярный нейрон: интервалы почти равны
+        assert!(regular.len() > 3);
+        assert!(regular[1..].iter().all(|&i| i.abs_diff(regular[1]) <= 3));
+
+        // Пачки: короткие интервалы внутри пачки, длинные между пачками
+        let short = chattering.iter().filter(|&&i| i <= 5).count();
+        let long = chattering.iter().filter(|&&i| i > 20).count();
+        assert!(short > 2 * long && long >= 3);
+    }
+
+    #[test]
+    fn test_adex_adaptation() {
+        let mut adapting = AdExNeuron::adapting();
+        let isi = intervals(&spike_times(&mut adapting, 0.5, 400));
+        assert!(isi.len() >= 4);
+        assert!(isi.windows(2).take(4).all(|w| w[1] > w[0]));
+        assert!(adapting.adaptation() > 0.0);
+
+        let tonic = intervals(&spike_times(&mut AdExNeuron::tonic(), 0.5, 400));
+        assert!(tonic.iter().all(|&i| i.abs_diff(tonic[0]) <= 1));
+
+        adapting.reset();
+        assert_eq!(adapting.adaptation(), 0.0);
+        assert_eq!(adapting.potential(), adapting.rest);
+    }
+}
@@ -0,0 +1,322 @@
+//! Мышечные процессоры - выходная стадия VNP
+//!
+//! [`MuscleUnit`] превращает поток спайков в непрерывную силу:
+//! спайки накапливают возбуждение, активация следует за возбуждением
+//! (нарастает быстрее, чем спадает), а сила ограничена ресурсом мышцы,
+//! который расходуется при работе и восстанавливается в покое.
+//! [`HybridProcessor`] соединяет нейронную сеть с мышцами через
+//! моторные нейроны.
+
+use soma_core::{Cell, CellInfo, LiveCell, StemCell};
+
+use crate::config::muscle;
+use crate::network::{NetworkError, NeuralNetwork, NeuronId};
+
+/// Мышечный юнит
+#[derive(Debug, Clone, PartialEq)]
+pub struct MuscleUnit {
+    /// Возбуждение от входящих спайков (0.0 - 1.0)
+    drive: f64,
+    /// Активация сократительного аппарата (0.0 - 1.0)
+    activation: f64,
+    /// Доступный ресурс мышцы (1.0 - полностью отдохнувшая)
+    capacity: f64,
+    /// Вход, накопленный в sense до следующего align
+    pending: f64,
+    force: f64,
+    max_force: f64,
+    twitch: f64,
+    drive_decay: f64,
+    rise: f64,
+    fall: f64,
+    fatigue_rate: f64,
+    recovery_rate: f64,
+}
+
+impl MuscleUnit {
+    /// Создать отдохнувшую мышцу с параметрами по умолчанию
+    pub fn new() -> Self {
+        Self {
+            drive: 0.0,
+            activation: 0.0,
+            capacity: 1.0,
+            pending: 0.0,
+            force: 0.0,
+            max_force: 1.0,
+            twitch: muscle::DEFAULT_TWITCH,
+            drive_decay: muscle::DEFAULT_DRIVE_DECAY,
+            rise: muscle::DEFAULT_RISE,
+            fall: muscle::DEFAULT_FALL,
+            fatigue_rate: muscle::DEFAULT_FATIGUE_RATE,
+            recovery_rate: muscle::DEFAULT_RECOVERY_RATE,
+        }
+    }
+
+    /// Установить максимальную силу
+    pub fn with_max_force(mut self, max_force: f64) -> Self {
+        self.max_force = max_force.max(0.0);
+        self
+    }
+
+    /// Установить возбуждение от спайка и его затухание за шаг
+    pub fn with_twitch(mut self, twitch: f64, decay: f64) -> Self {
+        self.twitch = twitch.max(0.0);
+        self.drive_decay = decay.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Установить скорости нарастания и спада активации
+    pub fn with_activation(mut self, rise: f64, fall: f64) -> Self {
+        self.rise = rise.clamp(0.0, 1.0);
+        self.fall = fall.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Установить скорости утомления и восстановления
+    pub fn with_fatigue(mut self, fatigue_rate: f64, recovery_rate: f64) -> Self {
+        self.fatigue_rate = fatigue_rate.clamp(0.0, 1.0);
+        self.recovery_rate = recovery_rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Выполнить шаг с заданным числом входящих спайков, вернуть силу
+    pub fn step(&mut self, spikes: f64) -> f64 {
+        self.drive = (self.drive * (1.0 - self.drive_decay) + spikes.max(0.0) * self.twitch).min(1.0);
+
+        let rate = if self.drive > self.activation { self.rise } else { self.fall };
+        self.activation += (self.drive - self.activation) * rate;
+
+        // Расход пропорционален активации, восстановление - недостающему ресурсу
+        let used = self.fatigue_rate * self.activation * self.capacity;
+        let restored = self.recovery_rate * (1.0 - self.capacity);
+        self.capacity = (self.capacity - used + restored).clamp(0.0, 1.0);
+
+        self.force = self.max_force * self.activation * self.capacity;
+        self.force
+    }
+
+    /// Текущая сила
+    pub fn force(&self) -> f64 {
+        self.force
+    }
+
+    /// Максимальная сила
+    pub fn max_force(&self) -> f64 {
+        self.max_force
+    }
+
+    /// Возбуждение
+    pub fn drive(&self) -> f64 {
+        self.drive
+    }
+
+    /// Активация
+    pub fn activation(&self) -> f64 {
+        self.activation
+    }
+
+    /// Утомление (0.0 - отдохнувшая, 1.0 - ресурс исчерпан)
+    pub fn fatigue(&self) -> f64 {
+        1.0 - self.capacity
+    }
+
+    /// Вернуть мышцу в состояние покоя
+    pub fn reset(&mut self) {
+        self.drive = 0.0;
+        self.activation = 0.0;
+        self.capacity = 1.0;
+        self.pending = 0.0;
+        self.force = 0.0;
+    }
+}
+
+impl Default for MuscleUnit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cell for MuscleUnit {
+    /// Накопить вход (число или силу спайков) до следующего шага
+    fn sense(&mut self, input: f64) {
+        self.pending += input;
+    }
+
+    /// Выполнить шаг с накопленным входом
+    fn align(&mut self) {
+        let input = std::mem::take(&mut self.pending);
+        self.step(input);
+    }
+
+    /// Текущая сила
+    fn flow(&self) -> f64 {
+        self.force
+    }
+}
+
+/// Фабрика мышц для StemProcessor
+///
+/// Обычно регистрируется для роли `Motor`: выход организма становится
+/// средней силой его мышц
+pub fn muscle_factory(unit: MuscleUnit) -> impl Fn(&CellInfo) -> LiveCell + Send + Sync + 'static {
+    move |_| Box::new(StemCell::new().differentiate(unit.clone()))
+}
+
+/// Гибридный процессор: нейронная сеть, управляющая мышцами
+///
+/// Каждую мышцу иннервируют один или несколько моторных нейронов сети,
+/// спайки моторных нейронов за шаг суммируются на входе мышцы
+pub struct HybridProcessor {
+    network: NeuralNetwork,
+    muscles: Vec<MuscleUnit>,
+    /// Моторные нейроны каждой мышцы
+    innervation: Vec<Vec<NeuronId>>,
+}
+
+impl HybridProcessor {
+    /// Создать процессор над нейронной сетью
+    pub fn new(network: NeuralNetwork) -> Self {
+        Self {
+            network,
+            muscles: Vec::new(),
+            innervation: Vec::new(),
+        }
+    }
+
+    /// Добавить мышцу, иннервируемую заданными моторными нейронами
+    ///
+    /// Возвращает индекс мышцы
+    pub fn add_muscle(&mut self, unit: MuscleUnit, motor_neurons: &[NeuronId]) -> Result<usize, NetworkError> {
+        if let Some(&id) = motor_neurons.iter().find(|id| self.network.neuron(**id).is_none()) {
+            return Err(NetworkError::UnknownNeuron(id));
+        }
+        self.muscles.push(unit);
+        self.innervation.push(motor_neurons.to_vec());
+        Ok(self.muscles.len() - 1)
+    }
+
+    /// Нейронная сеть
+    pub fn network(&self) -> &NeuralNetwork {
+        &self.network
+    }
+
+    /// Мутабельный доступ к нейронной сети
+    pub fn network_mut(&mut self) -> &mut NeuralNetwork {
+        &mut self.network
+    }
+
+    /// Получить мышцу
+    pub fn muscle(&self, index: usize) -> Option<&MuscleUnit> {
+        self.muscles.get(index)
+    }
+
+    /// Моторные нейроны мышцы
+    pub fn motor_neurons(&self, index: usize) -> &[NeuronId] {
+        self.innervation.get(index).map_or(&[], Vec::as_slice)
+    }
+
+    /// Количество мышц
+    pub fn muscle_count(&self) -> usize {
+        self.muscles.len()
+    }
+
+    /// Текущие силы мышц
+    pub fn forces(&self) -> Vec<f64> {
+        self.muscles.iter().map(MuscleUnit::force).collect()
+    }
+
+    /// Выполнить шаг сети и мышц, вернуть силы мышц
+    pub fn step(&mut self, inputs: &[(NeuronId, f64)]) -> Vec<f64> {
+        let fired = self.network.step(inputs);
+        for (muscle, neurons) in self.muscles.iter_mut().zip(&self.innervation) {
+            let spikes = neurons.iter().filter(|id| fired.contains(id)).count();
+            muscle.step(spikes as f64);
+        }
+        self.forces()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::LifNeuron;
+    use soma_core::{CellRole, FixedRatioSelector, StemProcessor};
+
+    /// Сила после `steps` шагов со спайком каждые `period` шагов
+    fn force_at_rate(period: usize, steps: usize) -> MuscleUnit {
+        let mut unit = MuscleUnit::new().with_fatigue(0.0, 0.0);
+        for t in 0..steps {
+            unit.step(if t % period == 0 { 1.0 } else { 0.0 });
+        }
+        unit
+    }
+
+    #[test]
+    fn test_force_grows_with_spike_rate() {
+        let slow = force_at_rate(10, 100);
+        let fast = force_at_rate(2, 100);
+        assert!(fast.force() > 2.0 * slow.force());
+        assert!(fast.force() <= fast.max_force());
+
+        // Без спайков сила спадает медленнее, чем нарастала
+        let mut relaxing = fast.clone();
+        relaxing.step(0.0);
+        assert!(relaxing.force() > 0.0 && relaxing.force() < fast.force());
+    }
+
+    #[test]
+    fn test_fatigue_and_recovery() {
+        let mut unit = MuscleUnit::new();
+        let peak = (0..20).map(|_| unit.step(5.0)).fold(0.0, f64::max);
+        for _ in 0..300 {
+            unit.step(5.0);
+        }
+        let tired = unit.force();
+        assert!(tired < 0.6 * peak);
+        assert!(unit.fatigue() > 0.4);
+
+        for _ in 0..1000 {
+            unit.step(0.0);
+        }
+        assert!(unit.force() < 1e-9);
+        assert!(unit.fatigue() < 0.01);
+    }
+
+    #[test]
+    fn test_hybrid_processor_drives_muscle() {
+        let mut network = NeuralNetwork::new();
+        let sensor = network.add_neuron(LifNeuron::new().with_refractory(0));
+        let motor = network.add_neuron(LifNeuron::new().with_refractory(1));
+        let removed = network.add_neuron(LifNeuron::new());
+        network.remove_neuron(removed);
+        network.connect(sensor, motor, 1.0, 1).unwrap();
+
+        let mut processor = HybridProcessor::new(network);
+        let arm = processor.add_muscle(MuscleUnit::new(), &[motor]).unwrap();
+        assert_eq!(
+            processor.add_muscle(MuscleUnit::new(), &[motor, removed]),
+            Err(NetworkError::UnknownNeuron(removed))
+        );
+        assert_eq!(processor.muscle_count(), 1);
+
+        let forces: Vec<f64> = (0..30).map(|_| processor.step(&[(sensor, 1.0)])[arm]).collect();
+        assert_eq!(forces[0], 0.0);
+        assert!(forces[29] > forces[5]);
+        assert_eq!(processor.motor_neurons(arm), &[motor]);
+    }
+
+    #[test]
+    fn test_muscle_factory_drives_organism_output() {
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_role_selector(FixedRatioSelector::new(&[(CellRole::Motor, 1.0)]))
+            .with_cell_factory(CellRole::Motor, muscle_factory(MuscleUnit::new()));
+
+        stem.sense(0.8);
+        assert_eq!(stem.live_cell_count(), 1);
+        for _ in 0..10 {
+            stem.sense(1.0);
+            stem.tick();
+        }
+        assert!(stem.output() > 0.0 && stem.output() <= 1.0);
+    }
+}
@@ -0,0 +1,507 @@
+//! Нейронная сеть с явными синапсами
+//!
+//! [`NeuronLayer`](crate::NeuronLayer) - плоский набор нейронов без связей.
+//! [`NeuralNetwork`] хранит нейроны и направленные синапсы (пре, пост,
+//! вес, задержка) и распространяет спайки сработавших нейронов к их
+//! целям с учётом задержки. Нейроны и синапсы можно добавлять и удалять
+//! во время работы сети, модели нейронов ([`NeuronModel`]) можно
+//! смешивать. С включённой пластичностью ([`StdpRule`]) веса
+//! синапсов обучаются по времени спайков прямо во время шагов сети.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::{self, Write};
+
+use crate::model::NeuronModel;
+use crate::plasticity::{SpikeTrace, StdpRule};
+
+/// Идентификатор нейрона в сети
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NeuronId(pub(crate) u32);
+
+impl NeuronId {
+    /// Порядковый номер нейрона (не переиспользуется после удаления)
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Идентификатор синапса в сети
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SynapseId(u32);
+
+impl SynapseId {
+    /// Порядковый номер синапса (не переиспользуется после удаления)
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Направленный синапс между двумя нейронами
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Synapse {
+    /// Пресинаптический нейрон
+    pub pre: NeuronId,
+    /// Постсинаптический нейрон
+    pub post: NeuronId,
+    /// Вес (отрицательный - тормозный синапс)
+    pub weight: f64,
+    /// Задержка доставки спайка в шагах (не меньше 1)
+    pub delay: u32,
+}
+
+/// Ошибки изменения сети
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkError {
+    /// Нейрон не найден
+    UnknownNeuron(NeuronId),
+    /// Синапс не найден
+    UnknownSynapse(SynapseId),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::UnknownNeuron(id) => write!(f, "Unknown neuron: {}", id.0),
+            NetworkError::UnknownSynapse(id) => write!(f, "Unknown synapse: {}", id.0),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+/// Сеть нейронов, связанных синапсами
+///
+/// Сеть работает дискретными шагами. На каждом шаге модель каждого
+/// нейрона получает сумму внешнего входа и спайков, пришедших по
+/// синапсам. Спайк сработавшего нейрона приходит к цели через `delay`
+/// шагов с силой, равной весу синапса.
+#[derive(Default)]
+pub struct NeuralNetwork {
+    neurons: BTreeMap<NeuronId, Box<dyn NeuronModel>>,
+    synapses: BTreeMap<SynapseId, Synapse>,
+    /// Исходящие синапсы нейронов
+    outgoing: BTreeMap<NeuronId, Vec<SynapseId>>,
+    /// Входящие синапсы нейронов
+    incoming: BTreeMap<NeuronId, Vec<SynapseId>>,
+    /// Спайки в пути: элемент `i` доставляется через `i + 1` шагов
+    pending: VecDeque<Vec<(NeuronId, f64)>>,
+    /// Шаг последнего срабатывания нейронов
+    last_spikes: BTreeMap<NeuronId, u64>,
+    /// Нейроны, сработавшие на последнем шаге
+    fired: Vec<NeuronId>,
+    /// Правило пластичности синапсов (None - веса не обучаются)
+    plasticity: Option<StdpRule>,
+    /// Следы спайков нейронов для пластичности
+    traces: BTreeMap<NeuronId, SpikeTrace>,
+    next_neuron: u32,
+    next_synapse: u32,
+    time: u64,
+}
+
+impl NeuralNetwork {
+    /// Создать пустую сеть
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Включить пластичность синапсов
+    pub fn with_plasticity(mut self, rule: StdpRule) -> Self {
+        self.set_plasticity(Some(rule));
+        self
+    }
+
+    /// Установить правило пластичности (None - выключить)
+    pub fn set_plasticity(&mut self, rule: Option<StdpRule>) {
+        if rule.is_none() {
+            self.traces.clear();
+        }
+        self.plasticity = rule;
+    }
+
+    /// Правило пластичности
+    pub fn plasticity(&self) -> Option<&StdpRule> {
+        self.plasticity.as_ref()
+    }
+
+    /// Следы спайков нейрона (None - нейрон ещё не срабатывал)
+    pub fn trace(&self, id: NeuronId) -> Option<SpikeTrace> {
+        self.traces.get(&id).copied()
+    }
+
+    /// Добавить нейрон
+    pub fn add_neuron(&mut self, neuron: impl NeuronModel + 'static) -> NeuronId {
+        self.add_boxed_neuron(Box::new(neuron))
+    }
+
+    /// Добавить нейрон, модель которого известна только во время работы
+    pub fn add_boxed_neuron(&mut self, neuron: Box<dyn NeuronModel>) -> NeuronId {
+        let id = NeuronId(self.next_neuron);
+        self.next_neuron += 1;
+        self.neurons.insert(id, neuron);
+        id
+    }
+
+    /// Удалить нейрон вместе с его синапсами и спайками, идущими к нему
+    pub fn remove_neuron(&mut self, id: NeuronId) -> Option<Box<dyn NeuronModel>> {
+        let neuron = self.neurons.remove(&id)?;
+
+        let outgoing = self.outgoing.remove(&id).unwrap_or_default();
+        let incoming = self.incoming.remove(&id).unwrap_or_default();
+        for synapse in outgoing.into_iter().chain(incoming) {
+            self.disconnect(synapse);
+        }
+        self.last_spikes.remove(&id);
+        self.traces.remove(&id);
+        self.fired.retain(|n| *n != id);
+        for slot in &mut self.pending {
+            slot.retain(|(post, _)| *post != id);
+        }
+        Some(neuron)
+    }
+
+    /// Соединить два нейрона синапсом
+    ///
+    /// Задержка меньше одного шага округляется до одного шага
+    pub fn connect(&mut self, pre: NeuronId, post: NeuronId, weight: f64, delay: u32) -> Result<SynapseId, NetworkError> {
+        for id in [pre, post] {
+            if !self.neurons.contains_key(&id) {
+                return Err(NetworkError::UnknownNeuron(id));
+            }
+        }
+
+        let id = SynapseId(self.next_synapse);
+        self.next_synapse += 1;
+        self.synapses.insert(id, Synapse { pre, post, weight, delay: delay.max(1) });
+        self.outgoing.entry(pre).or_default().push(id);
+        self.incoming.entry(post).or_default().push(id);
+        Ok(id)
+    }
+
+    /// Удалить синапс (спайки, уже отправленные по нему, будут доставлены)
+    pub fn disconnect(&mut self, id: SynapseId) -> Option<Synapse> {
+        let synapse = self.synapses.remove(&id)?;
+        if let Some(outgoing) = self.outgoing.get_mut(&synapse.pre) {
+            outgoing.retain(|s| *s != id);
+        }
+        if let Some(incoming) = self.incoming.get_mut(&synapse.post) {
+            incoming.retain(|s| *s != id);
+        }
+        Some(synapse)
+    }
+
+    /// Изменить вес синапса
+    pub fn set_weight(&mut self, id: SynapseId, weight: f64) -> Result<(), NetworkError> {
+        let synapse = self.synapses.get_mut(&id).ok_or(NetworkError::UnknownSynapse(id))?;
+        synapse.weight = weight;
+        Ok(())
+    }
+
+    /// Получить нейрон
+    pub fn neuron(&self, id: NeuronId) -> Option<&dyn NeuronModel> {
+        self.neurons.get(&id).map(|n| n.as_ref())
+    }
+
+    /// Получить мутабельный доступ к нейрону
+    pub fn neuron_mut(&mut self, id: NeuronId) -> Option<&mut dyn NeuronModel> {
+        self.neurons.get_mut(&id).map(|n| n.as_mut() as &mut dyn NeuronModel)
+    }
+
+    /// Получить синапс
+    pub fn synapse(&self, id: SynapseId) -> Option<&Synapse> {
+        self.synapses.get(&id)
+    }
+
+    /// Идентификаторы нейронов по возрастанию
+    pub fn neuron_ids(&self) -> impl Iterator<Item = NeuronId> + '_ {
+        self.neurons.keys().copied()
+    }
+
+    /// Все синапсы по возрастанию идентификатора
+    pub fn synapses(&self) -> impl Iterator<Item = (SynapseId, &Synapse)> {
+        self.synapses.iter().map(|(id, s)| (*id, s))
+    }
+
+    /// Исходящие синапсы нейрона
+    pub fn outgoing(&self, id: NeuronId) -> &[SynapseId] {
+        self.outgoing.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Входящие синапсы нейрона
+    pub fn incoming(&self, id: NeuronId) -> &[SynapseId] {
+        self.incoming.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Количество нейронов
+    pub fn neuron_count(&self) -> usize {
+        self.neurons.len()
+    }
+
+    /// Количество синапсов
+    pub fn synapse_count(&self) -> usize {
+        self.synapses.len()
+    }
+
+    /// Количество выполненных шагов
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+
+    /// Нейроны, сработавшие на последнем шаге
+    pub fn fired(&self) -> &[NeuronId] {
+        &self.fired
+    }
+
+    /// Шаг последнего срабатывания нейрона
+    pub fn last_spike(&self, id: NeuronId) -> Option<u64> {
+        self.last_spikes.get(&id).copied()
+    }
+
+    /// Количество спайков в пути
+    pub fn pending_spikes(&self) -> usize {
+        self.pending.iter().map(Vec::len).sum()
+    }
+
+    /// Экспорт сети в формат GraphViz DOT
+    ///
+    /// Рёбра подписаны весом и задержкой, тормозные синапсы - красные
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n    node [shape=circle];\n");
+        for id in self.neurons.keys() {
+            let _ = writeln!(dot, "    n{};", id.0);
+        }
+        for synapse in self.synapses.values() {
+            let color = if synapse.weight < 0.0 { ", color=red" } else { "" };
+            let _ = writeln!(
+                dot,
+                "    n{} -> n{} [label=\"{:.2}/{}\"{}];",
+                synapse.pre.0, synapse.post.0, synapse.weight, synapse.delay, color
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Выполнить шаг сети
+    ///
+    /// `inputs` - внешний ток по нейронам (неизвестные нейроны игнорируются).
+    /// При включённой пластичности после срабатывания обновляются веса
+    /// синапсов. Возвращает нейроны, сработавшие на этом шаге
+    pub fn step(&mut self, inputs: &[(NeuronId, f64)]) -> &[NeuronId] {
+        self.time += 1;
+
+        let mut currents: BTreeMap<NeuronId, f64> = BTreeMap::new();
+        let arrivals = self.pending.pop_front().unwrap_or_default();
+        for &(id, value) in arrivals.iter().chain(inputs) {
+            *currents.entry(id).or_insert(0.0) += value;
+        }
+
+        self.fired.clear();
+        for (&id, neuron) in &mut self.neurons {
+            let current = currents.get(&id).copied().unwrap_or(0.0);
+            if neuron.step(current) {
+                self.fired.push(id);
+            }
+        }
+
+        for index in 0..self.fired.len() {
+            let pre = self.fired[index];
+            self.last_spikes.insert(pre, self.time);
+            for synapse in self.outgoing.get(&pre).into_iter().flatten() {
+                let synapse = self.synapses[synapse];
+                let slot = synapse.delay as usize - 1;
+                if self.pending.len() <= slot {
+                    self.pending.resize_with(slot + 1, Vec::new);
+                }
+                self.pending[slot].push((synapse.post, synapse.weight));
+            }
+        }
+
+        if let Some(rule) = self.plasticity {
+            self.apply_plasticity(&rule);
+        }
+
+        &self.fired
+    }
+
+    /// Обновить веса возбуждающих синапсов по следам спайков
+    ///
+    /// Тормозные синапсы (с отрицательным весом) не обучаются. Спайки
+    /// пре- и постсинаптического нейрона на одном шаге вес не меняют.
+    /// Обновляются только синапсы сработавших нейронов
+    fn apply_plasticity(&mut self, rule: &StdpRule) {
+        for trace in self.traces.values_mut() {
+            trace.decay(rule);
+        }
+        if self.fired.is_empty() {
+            return;
+        }
+
+        // Сработавшие нейроны идут по возрастанию идентификатора
+        let fired = |id: &NeuronId| self.fired.binary_search(id).is_ok();
+        let mut touched: Vec<SynapseId> = self
+            .fired
+            .iter()
+            .flat_map(|id| self.outgoing(*id).iter().chain(self.incoming(*id)))
+            .copied()
+            .collect();
+        touched.sort_unstable();
+        touched.dedup();
+
+        for id in touched {
+            let Some(synapse) = self.synapses.get_mut(&id) else { continue };
+            if synapse.weight < 0.0 {
+                continue;
+            }
+            let mut delta = 0.0;
+            if fired(&synapse.post) {
+                delta += rule.potentiation * self.traces.get(&synapse.pre).map_or(0.0, |t| t.pre);
+            }
+            if fired(&synapse.pre) {
+                delta -= rule.depression * self.traces.get(&synapse.post).map_or(0.0, |t| t.post);
+            }
+            if delta != 0.0 {
+                synapse.weight = rule.clamp(synapse.weight + delta);
+            }
+        }
+
+        for id in &self.fired {
+            self.traces.entry(*id).or_default().spike();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{IzhikevichNeuron, LifNeuron};
+    use crate::neuron::Neuron;
+
+    fn neuron() -> Neuron {
+        Neuron::with_params(0.5, 0.0, 1.0)
+    }
+
+    #[test]
+    fn test_spike_propagates_with_delay() {
+        let mut network = NeuralNetwork::new();
+        let a = network.add_neuron(neuron());
+        let b = network.add_neuron(neuron());
+        let c = network.add_neuron(neuron());
+        network.connect(a, b, 1.0, 2).unwrap();
+        network.connect(b, c, 1.0, 0).unwrap();
+
+        assert_eq!(network.step(&[(a, 1.0)]), &[a]);
+        assert_eq!(network.pending_spikes(), 1);
+        assert!(network.step(&[]).is_empty());
+        assert_eq!(network.step(&[]), &[b]);
+        assert_eq!(network.step(&[]), &[c]);
+        assert_eq!(network.last_spike(b), Some(3));
+        assert!(network.step(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_inhibitory_synapse_cancels_input() {
+        let mut network = NeuralNetwork::new();
+        let excite = network.add_neuron(neuron());
+        let inhibit = network.add_neuron(neuron());
+        let target = network.add_neuron(neuron());
+        network.connect(excite, target, 0.6, 1).unwrap();
+        let synapse = network.connect(inhibit, target, -0.6, 1).unwrap();
+
+        network.step(&[(excite, 1.0), (inhibit, 1.0)]);
+        assert!(network.step(&[]).is_empty());
+
+        network.set_weight(synapse, 0.0).unwrap();
+        network.step(&[(excite, 1.0), (inhibit, 1.0)]);
+        assert_eq!(network.step(&[]), &[target]);
+    }
+
+    #[test]
+    fn test_runtime_removal() {
+        let mut network = NeuralNetwork::new();
+        let a = network.add_neuron(neuron());
+        let b = network.add_neuron(neuron());
+        let ab = network.connect(a, b, 1.0, 3).unwrap();
+        network.connect(b, a, 1.0, 1).unwrap();
+
+        network.step(&[(a, 1.0)]);
+        assert_eq!(network.incoming(b), vec![ab]);
+        assert!(network.remove_neuron(b).is_some());
+        assert_eq!(network.synapse_count(), 0);
+        assert!(network.outgoing(a).is_empty());
+        assert_eq!(network.pending_spikes(), 0);
+
+        assert_eq!(network.connect(a, b, 1.0, 1), Err(NetworkError::UnknownNeuron(b)));
+        assert_eq!(network.set_weight(ab, 1.0), Err(NetworkError::UnknownSynapse(ab)));
+        let c = network.add_neuron(neuron());
+        assert!(c > b);
+    }
+
+    #[test]
+    fn test_stdp_follows_spike_order() {
+        let rule = StdpRule::new().with_potentiation(0.1, 10.0).with_depression(0.1, 10.0);
+        let mut network = NeuralNetwork::new().with_plasticity(rule);
+        let a = network.add_neuron(neuron());
+        let b = network.add_neuron(neuron());
+        let ab = network.connect(a, b, 0.5, 5).unwrap();
+        let ba = network.connect(b, a, 0.5, 5).unwrap();
+
+        // a срабатывает на шаге 1, b - на шаге 3
+        network.step(&[(a, 1.0)]);
+        network.step(&[]);
+        network.step(&[(b, 1.0)]);
+
+        let expected = 0.1 * (-0.2f64).exp();
+        assert!((network.synapse(ab).unwrap().weight - (0.5 + expected)).abs() < 1e-12);
+        assert!((network.synapse(ba).unwrap().weight - (0.5 - expected)).abs() < 1e-12);
+        assert!(network.trace(b).unwrap().pre > network.trace(a).unwrap().pre);
+    }
+
+    #[test]
+    fn test_stdp_respects_bounds_and_inhibition() {
+        let rule = StdpRule::new().with_potentiation(0.5, 10.0).with_bounds(0.0, 0.8);
+        let mut network = NeuralNetwork::new().with_plasticity(rule);
+        let pre = network.add_neuron(neuron());
+        let post = network.add_neuron(neuron());
+        let excite = network.connect(pre, post, 0.6, 10).unwrap();
+        let inhibit = network.connect(pre, post, -0.6, 10).unwrap();
+
+        for _ in 0..3 {
+            network.step(&[(pre, 1.0)]);
+            network.step(&[(post, 1.0)]);
+        }
+        assert_eq!(network.synapse(excite).unwrap().weight, 0.8);
+        assert_eq!(network.synapse(inhibit).unwrap().weight, -0.6);
+
+        network.set_plasticity(None);
+        assert!(network.trace(pre).is_none());
+    }
+
+    #[test]
+    fn test_network_mixes_models() {
+        let mut network = NeuralNetwork::new();
+        let driver = network.add_neuron(LifNeuron::new().with_refractory(0));
+        let burster = network.add_neuron(IzhikevichNeuron::chattering().with_gain(10.0));
+        network.connect(driver, burster, 1.0, 1).unwrap();
+
+        let mut spikes = 0;
+        for _ in 0..200 {
+            spikes += network.step(&[(driver, 1.0)]).iter().filter(|id| **id == burster).count();
+        }
+        assert!(spikes > 10);
+        assert_eq!(network.neuron(burster).unwrap().name(), "izhikevich");
+        assert_eq!(network.remove_neuron(driver).unwrap().name(), "lif");
+    }
+
+    #[test]
+    fn test_to_dot_lists_neurons_and_synapses() {
+        let mut network = NeuralNetwork::new();
+        let a = network.add_neuron(neuron());
+        let b = network.add_neuron(neuron());
+        network.connect(a, b, -0.25, 2).unwrap();
+
+        let dot = network.to_dot();
+        assert!(dot.starts_with("digraph network {"));
+        assert!(dot.contains("    n1;"));
+        assert!(dot.contains("n0 -> n1 [label=\"-0.25/2\", color=red];"));
+    }
+}
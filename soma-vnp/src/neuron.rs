@@ -1,5 +1,7 @@
-use soma_core::Cell;
-use std::time::Instant;
+use soma_core::{system_clock, Cell, CellInfo, LiveCell, SharedClock, StemCell};
+
+use crate::config::neuron;
+use crate::model::NeuronModel;
 
 /// Виртуальный нейрон - базовая вычислительная единица SOMA
 ///
@@ -14,19 +16,23 @@ pub struct Neuron {
     decay: f64,
     /// Накопленная память/вес
     weight: f64,
-    /// Время последнего обновления (для временного затухания)
-    last_update: Instant,
+    /// Источник времени для временного затухания
+    clock: SharedClock,
+    /// Время последнего обновления в миллисекундах (для временного затухания)
+    last_update: u64,
 }
 
 impl Neuron {
     /// Создать новый нейрон с параметрами по умолчанию
     pub fn new() -> Self {
+        let clock = system_clock();
         Self {
             potential: 0.0,
-            threshold: 0.7,
-            decay: 0.1,
-            weight: 1.0,
-            last_update: Instant::now(),
+            threshold: neuron::DEFAULT_THRESHOLD,
+            decay: neuron::DEFAULT_DECAY,
+            weight: neuron::DEFAULT_WEIGHT,
+            last_update: clock.now_millis(),
+            clock,
         }
     }
 
@@ -37,10 +43,17 @@ impl Neuron {
             threshold: threshold.clamp(0.0, 1.0),
             decay: decay.clamp(0.0, 1.0),
             weight: weight.clamp(0.0, 10.0),
-            last_update: Instant::now(),
+            ..Self::new()
         }
     }
 
+    /// Использовать заданный источник времени для временного затухания
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.last_update = clock.now_millis();
+        self.clock = clock;
+        self
+    }
+
     /// Проверить, активирован ли нейрон
     pub fn is_activated(&self) -> bool {
         self.potential >= self.threshold
@@ -80,13 +93,19 @@ impl Neuron {
         }
     }
 
+    /// Применить затухание за один шаг (для pulse-режима)
+    pub fn leak(&mut self) {
+        self.potential *= 1.0 - self.decay;
+    }
+
     /// Применить временное затухание (для pulse-режима)
     ///
-    /// Учитывает реальное время, прошедшее с последнего обновления
+    /// Учитывает время, прошедшее по часам нейрона с последнего обновления
     pub fn time_based_decay(&mut self) {
-        let elapsed = self.last_update.elapsed().as_secs_f64();
+        let now = self.clock.now_millis();
+        let elapsed = now.saturating_sub(self.last_update) as f64 / 1000.0;
         self.potential *= (1.0 - self.decay * elapsed).max(0.0);
-        self.last_update = Instant::now();
+        self.last_update = now;
     }
 
     /// Получить нормализованное состояние (0.0 - 1.0)
@@ -125,17 +144,66 @@ impl Cell for Neuron {
     }
 }
 
+impl NeuronModel for Neuron {
+    /// Затухание, затем стимуляция ненулевым током
+    fn step(&mut self, current: f64) -> bool {
+        self.leak();
+        current != 0.0 && self.stimulate(current)
+    }
+
+    fn potential(&self) -> f64 {
+        self.potential
+    }
+
+    fn is_activated(&self) -> bool {
+        Neuron::is_activated(self)
+    }
+
+    fn reset(&mut self) {
+        self.potential = 0.0;
+    }
+
+    fn name(&self) -> &'static str {
+        "soma"
+    }
+
+    /// Цикл Sense-Align-Flow
+    fn process(&mut self, input: f64) -> f64 {
+        self.sense(input);
+        self.align();
+        self.flow()
+    }
+}
+
+/// Фабрика нейронов для StemProcessor
+///
+/// Стволовая клетка дифференцируется в нейрон, использующий заданные часы.
+/// Обычно регистрируется для роли `Logic`.
+pub fn neuron_factory(clock: SharedClock) -> impl Fn(&CellInfo) -> LiveCell + Send + Sync + 'static {
+    move |_| Box::new(StemCell::new().differentiate(Neuron::new().with_clock(clock.clone())))
+}
+
 /// Слой нейронов - коллекция связанных нейронов
+///
+/// Нейроны слоя могут быть разных моделей
 pub struct NeuronLayer {
-    neurons: Vec<Neuron>,
+    neurons: Vec<Box<dyn NeuronModel>>,
 }
 
 impl NeuronLayer {
     /// Создать слой с заданным количеством нейронов
     pub fn new(count: usize) -> Self {
-        Self {
-            neurons: (0..count).map(|_| Neuron::new()).collect(),
-        }
+        Self::from_models((0..count).map(|_| Box::new(Neuron::new()) as Box<dyn NeuronModel>).collect())
+    }
+
+    /// Создать слой из нейронов произвольных моделей
+    pub fn from_models(neurons: Vec<Box<dyn NeuronModel>>) -> Self {
+        Self { neurons }
+    }
+
+    /// Добавить нейрон в слой
+    pub fn push(&mut self, neuron: impl NeuronModel + 'static) {
+        self.neurons.push(Box::new(neuron));
     }
 
     /// Количество нейронов в слое
@@ -153,30 +221,35 @@ impl NeuronLayer {
         self.neurons
             .iter_mut()
             .enumerate()
-            .map(|(i, neuron)| {
-                if i < inputs.len() {
-                    neuron.sense(inputs[i]);
-                }
-                neuron.align();
-                neuron.flow()
-            })
+            .map(|(i, neuron)| neuron.process(inputs.get(i).copied().unwrap_or(0.0)))
+            .collect()
+    }
+
+    /// Выполнить шаг спайковых моделей, вернуть признаки спайков
+    pub fn step(&mut self, inputs: &[f64]) -> Vec<bool> {
+        self.neurons
+            .iter_mut()
+            .enumerate()
+            .map(|(i, neuron)| neuron.step(inputs.get(i).copied().unwrap_or(0.0)))
             .collect()
     }
 
     /// Получить доступ к нейрону по индексу
-    pub fn neuron(&self, index: usize) -> Option<&Neuron> {
-        self.neurons.get(index)
+    pub fn neuron(&self, index: usize) -> Option<&dyn NeuronModel> {
+        self.neurons.get(index).map(|n| n.as_ref())
     }
 
     /// Получить мутабельный доступ к нейрону
-    pub fn neuron_mut(&mut self, index: usize) -> Option<&mut Neuron> {
-        self.neurons.get_mut(index)
+    pub fn neuron_mut(&mut self, index: usize) -> Option<&mut dyn NeuronModel> {
+        self.neurons.get_mut(index).map(|n| n.as_mut() as &mut dyn NeuronModel)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::{IzhikevichNeuron, LifNeuron};
+    use soma_core::{CellRole, FixedRatioSelector, ManualClock, StemProcessor};
 
     #[test]
     fn test_neuron_activation() {
@@ -211,6 +284,22 @@ mod tests {
         assert!(potential_after < potential_before);
     }
 
+    #[test]
+    fn test_time_based_decay_with_manual_clock() {
+        let clock = ManualClock::new(0);
+        let mut neuron = Neuron::with_params(0.9, 0.5, 1.0).with_clock(clock.shared());
+        neuron.stimulate(0.8);
+
+        // Без течения времени затухания нет
+        neuron.time_based_decay();
+        assert_eq!(neuron.potential(), 0.8);
+
+        // Через секунду: 0.8 * (1 - 0.5 * 1.0)
+        clock.advance(1_000);
+        neuron.time_based_decay();
+        assert!((neuron.potential() - 0.4).abs() < 1e-9);
+    }
+
     #[test]
     fn test_neuron_layer() {
         let mut layer = NeuronLayer::new(3);
@@ -221,4 +310,34 @@ mod tests {
         assert_eq!(outputs.len(), 3);
         assert!(outputs[0] > 0.0); // Должен активироваться
     }
+
+    #[test]
+    fn test_layer_mixes_models() {
+        let mut layer = NeuronLayer::new(1);
+        layer.push(LifNeuron::new().with_threshold(0.5));
+        layer.push(IzhikevichNeuron::regular_spiking().with_gain(20.0));
+
+        let names: Vec<_> = (0..layer.len()).map(|i| layer.neuron(i).unwrap().name()).collect();
+        assert_eq!(names, vec!["soma", "lif", "izhikevich"]);
+
+        assert_eq!(layer.step(&[0.8, 0.6]), vec![true, true, false]);
+        let fired: Vec<bool> = (0..30).map(|_| layer.step(&[0.0, 0.0, 1.0])[2]).collect();
+        assert!(fired.contains(&true));
+    }
+
+    #[test]
+    fn test_neuron_factory_differentiates_logic_cells() {
+        let clock = ManualClock::new(0);
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_role_selector(FixedRatioSelector::new(&[(CellRole::Logic, 1.0)]))
+            .with_cell_factory(CellRole::Logic, neuron_factory(clock.shared()));
+
+        stem.sense(0.8);
+        assert_eq!(stem.live_cell_count(), 1);
+
+        stem.sense(0.9);
+        stem.tick();
+        assert!(stem.output() > 0.0);
+        assert!(stem.cells().values().all(|c| c.activity > 0.0));
+    }
 }
@@ -0,0 +1,137 @@
+//! Пластичность синапсов, зависящая от времени спайков (STDP)
+//!
+//! Каждый нейрон сети хранит два следа: пресинаптический и
+//! постсинаптический. След скачком растёт при спайке нейрона и
+//! экспоненциально затухает. Когда срабатывает постсинаптический нейрон,
+//! вес входящего синапса растёт пропорционально следу пресинаптического
+//! (пре раньше пост - потенциация); когда срабатывает пресинаптический,
+//! вес исходящего синапса падает пропорционально следу
+//! постсинаптического (пост раньше пре - депрессия).
+
+use crate::config::stdp;
+
+/// Правило STDP с асимметричными временными окнами
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StdpRule {
+    /// Амплитуда потенциации
+    pub potentiation: f64,
+    /// Амплитуда депрессии
+    pub depression: f64,
+    /// Постоянная времени окна потенциации (шагов)
+    pub tau_potentiation: f64,
+    /// Постоянная времени окна депрессии (шагов)
+    pub tau_depression: f64,
+    /// Минимальный вес
+    pub min_weight: f64,
+    /// Максимальный вес
+    pub max_weight: f64,
+}
+
+impl StdpRule {
+    /// Создать правило с параметрами по умолчанию
+    pub fn new() -> Self {
+        Self {
+            potentiation: stdp::DEFAULT_POTENTIATION,
+            depression: stdp::DEFAULT_DEPRESSION,
+            tau_potentiation: stdp::DEFAULT_TAU_POTENTIATION,
+            tau_depression: stdp::DEFAULT_TAU_DEPRESSION,
+            min_weight: stdp::DEFAULT_MIN_WEIGHT,
+            max_weight: stdp::DEFAULT_MAX_WEIGHT,
+        }
+    }
+
+    /// Установить окно потенциации (амплитуда и постоянная времени)
+    pub fn with_potentiation(mut self, amplitude: f64, tau: f64) -> Self {
+        self.potentiation = amplitude.max(0.0);
+        self.tau_potentiation = tau.max(f64::EPSILON);
+        self
+    }
+
+    /// Установить окно депрессии (амплитуда и постоянная времени)
+    pub fn with_depression(mut self, amplitude: f64, tau: f64) -> Self {
+        self.depression = amplitude.max(0.0);
+        self.tau_depression = tau.max(f64::EPSILON);
+        self
+    }
+
+    /// Установить границы веса
+    pub fn with_bounds(mut self, min: f64, max: f64) -> Self {
+        self.min_weight = min.min(max);
+        self.max_weight = max.max(min);
+        self
+    }
+
+    /// Изменение веса для пары спайков, разнесённых на `dt` шагов
+    ///
+    /// `dt = t_post - t_pre`: положительное - потенциация, иначе депрессия
+    pub fn window(&self, dt: i64) -> f64 {
+        if dt > 0 {
+            self.potentiation * (-(dt as f64) / self.tau_potentiation).exp()
+        } else if dt < 0 {
+            -self.depression * (dt as f64 / self.tau_depression).exp()
+        } else {
+            0.0
+        }
+    }
+
+    /// Ограничить вес границами правила
+    pub fn clamp(&self, weight: f64) -> f64 {
+        weight.clamp(self.min_weight, self.max_weight)
+    }
+}
+
+impl Default for StdpRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Следы спайков нейрона
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SpikeTrace {
+    /// След нейрона как пресинаптического (окно потенциации)
+    pub pre: f64,
+    /// След нейрона как постсинаптического (окно депрессии)
+    pub post: f64,
+}
+
+impl SpikeTrace {
+    /// Затухание следов за один шаг
+    pub fn decay(&mut self, rule: &StdpRule) {
+        self.pre *= (-1.0 / rule.tau_potentiation).exp();
+        self.post *= (-1.0 / rule.tau_depression).exp();
+    }
+
+    /// Учесть спайк нейрона
+    pub fn spike(&mut self) {
+        self.pre += 1.0;
+        self.post += 1.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_is_asymmetric() {
+        let rule = StdpRule::new().with_potentiation(0.1, 10.0).with_depression(0.2, 5.0);
+
+        assert!((rule.window(10) - 0.1 * (-1.0f64).exp()).abs() < 1e-12);
+        assert!((rule.window(-5) + 0.2 * (-1.0f64).exp()).abs() < 1e-12);
+        assert_eq!(rule.window(0), 0.0);
+        assert!(rule.window(1) > rule.window(5));
+    }
+
+    #[test]
+    fn test_trace_matches_window() {
+        let rule = StdpRule::new().with_potentiation(0.1, 4.0);
+        let mut trace = SpikeTrace::default();
+        trace.spike();
+        for _ in 0..3 {
+            trace.decay(&rule);
+        }
+        assert!((rule.potentiation * trace.pre - rule.window(3)).abs() < 1e-12);
+        assert_eq!(rule.clamp(1.5), 1.0);
+    }
+}
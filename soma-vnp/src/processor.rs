@@ -0,0 +1,217 @@
+//! VNP-процессор - настраиваемый блок обработки сигналов
+//!
+//! [`VnpProcessor`] создаёт юниты по [`ProcessorConfig`]: нейроны,
+//! мышцы или нейромышечные пары, и работает как обычная клетка SOMA.
+//! Веса нейронов обучаются онлайн по ошибке выхода с шагом
+//! `learning_rate` (дельта-правило).
+
+use soma_core::Cell;
+
+use crate::muscle::MuscleUnit;
+use crate::neuron::Neuron;
+use crate::{ProcessorConfig, ProcessorType};
+
+/// VNP-процессор
+///
+/// - `Neural` - `unit_count` нейронов, выход - средний поток нейронов
+/// - `Muscle` - `unit_count` мышц, выход - средняя сила
+/// - `Hybrid` - `unit_count` пар: поток нейрона `i` возбуждает мышцу `i`
+pub struct VnpProcessor {
+    config: ProcessorConfig,
+    neurons: Vec<Neuron>,
+    muscles: Vec<MuscleUnit>,
+    /// Входы юнитов, накопленные с прошлого align
+    inputs: Vec<f64>,
+    /// Входы юнитов на последнем align (для обучения)
+    last_inputs: Vec<f64>,
+    /// Выходы юнитов на последнем align
+    outputs: Vec<f64>,
+}
+
+impl VnpProcessor {
+    /// Создать процессор по конфигурации
+    pub fn new(config: ProcessorConfig) -> Self {
+        let count = config.unit_count;
+        let neurons = match config.processor_type {
+            ProcessorType::Neural | ProcessorType::Hybrid => (0..count).map(|_| Neuron::new()).collect(),
+            ProcessorType::Muscle => Vec::new(),
+        };
+        let muscles = match config.processor_type {
+            ProcessorType::Muscle | ProcessorType::Hybrid => (0..count).map(|_| MuscleUnit::new()).collect(),
+            ProcessorType::Neural => Vec::new(),
+        };
+        Self {
+            config,
+            neurons,
+            muscles,
+            inputs: vec![0.0; count],
+            last_inputs: vec![0.0; count],
+            outputs: vec![0.0; count],
+        }
+    }
+
+    /// Конфигурация процессора
+    pub fn config(&self) -> &ProcessorConfig {
+        &self.config
+    }
+
+    /// Тип процессора
+    pub fn processor_type(&self) -> ProcessorType {
+        self.config.processor_type
+    }
+
+    /// Количество юнитов
+    pub fn unit_count(&self) -> usize {
+        self.config.unit_count
+    }
+
+    /// Получить нейрон
+    pub fn neuron(&self, index: usize) -> Option<&Neuron> {
+        self.neurons.get(index)
+    }
+
+    /// Получить мышцу
+    pub fn muscle(&self, index: usize) -> Option<&MuscleUnit> {
+        self.muscles.get(index)
+    }
+
+    /// Выходы юнитов на последнем шаге
+    pub fn outputs(&self) -> &[f64] {
+        &self.outputs
+    }
+
+    /// Подать на юниты отдельные входы (лишние входы игнорируются)
+    pub fn sense_vector(&mut self, inputs: &[f64]) {
+        for (slot, input) in self.inputs.iter_mut().zip(inputs) {
+            *slot += input;
+        }
+    }
+
+    /// Выполнить шаг с отдельными входами юнитов, вернуть выход
+    pub fn process(&mut self, inputs: &[f64]) -> f64 {
+        self.sense_vector(inputs);
+        self.align();
+        self.flow()
+    }
+
+    /// Обучить веса нейронов по ошибке выхода
+    ///
+    /// Вес нейрона меняется на `learning_rate * (target - flow) * input`,
+    /// где `input` - вход нейрона на последнем шаге. Мышцы не обучаются.
+    /// Возвращает ошибку до обучения
+    pub fn train(&mut self, target: f64) -> f64 {
+        let error = target - self.flow();
+        let rate = self.config.learning_rate;
+        for (neuron, input) in self.neurons.iter_mut().zip(&self.last_inputs) {
+            neuron.train(rate * error * input);
+        }
+        error
+    }
+}
+
+impl Default for VnpProcessor {
+    fn default() -> Self {
+        Self::new(ProcessorConfig::default())
+    }
+}
+
+impl Cell for VnpProcessor {
+    /// Подать одинаковый вход на все юниты
+    fn sense(&mut self, input: f64) {
+        for slot in &mut self.inputs {
+            *slot += input;
+        }
+    }
+
+    /// Прогнать накопленные входы через юниты
+    fn align(&mut self) {
+        self.last_inputs = std::mem::replace(&mut self.inputs, vec![0.0; self.config.unit_count]);
+        for (index, &input) in self.last_inputs.iter().enumerate() {
+            let drive = match self.neurons.get_mut(index) {
+                Some(neuron) => {
+                    neuron.sense(input);
+                    neuron.align();
+                    neuron.flow()
+                }
+                None => input,
+            };
+            self.outputs[index] = match self.muscles.get_mut(index) {
+                Some(muscle) => muscle.step(drive),
+                None => drive,
+            };
+        }
+    }
+
+    /// Средний выход юнитов
+    fn flow(&self) -> f64 {
+        if self.outputs.is_empty() {
+            return 0.0;
+        }
+        self.outputs.iter().sum::<f64>() / self.outputs.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soma_core::{CellRole, FixedRatioSelector, StemProcessor};
+
+    fn config(processor_type: ProcessorType, unit_count: usize) -> ProcessorConfig {
+        ProcessorConfig {
+            processor_type,
+            unit_count,
+            learning_rate: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_units_follow_processor_type() {
+        let neural = VnpProcessor::new(config(ProcessorType::Neural, 3));
+        assert!(neural.neuron(2).is_some() && neural.muscle(0).is_none());
+
+        let mut hybrid = VnpProcessor::new(config(ProcessorType::Hybrid, 2));
+        assert!(hybrid.neuron(1).is_some() && hybrid.muscle(1).is_some());
+
+        // Нейрон 0 активируется и возбуждает свою мышцу, нейрон 1 молчит
+        let output = hybrid.process(&[0.9, 0.1]);
+        assert!(hybrid.outputs()[0] > 0.0);
+        assert_eq!(hybrid.outputs()[1], 0.0);
+        assert_eq!(output, hybrid.outputs()[0] / 2.0);
+
+        let mut muscle = VnpProcessor::new(config(ProcessorType::Muscle, 1));
+        muscle.sense(1.0);
+        muscle.align();
+        assert!(muscle.flow() > 0.0);
+        assert_eq!(muscle.train(0.0), -muscle.flow());
+    }
+
+    #[test]
+    fn test_online_training_reduces_error() {
+        let mut processor = VnpProcessor::new(config(ProcessorType::Neural, 2));
+        let target = 0.8;
+
+        processor.process(&[0.1, 0.1]);
+        let initial = processor.train(target).abs();
+        let mut error = initial;
+        for _ in 0..200 {
+            processor.process(&[0.1, 0.1]);
+            error = processor.train(target);
+        }
+        assert!(error.abs() < 0.02 && error.abs() < initial);
+        assert!(processor.neuron(0).unwrap().weight() < 1.0);
+    }
+
+    #[test]
+    fn test_plugs_into_stem_processor() {
+        let processor_config = config(ProcessorType::Neural, 4);
+        let mut stem = StemProcessor::with_params(0.3, 0.5)
+            .with_role_selector(FixedRatioSelector::new(&[(CellRole::Logic, 1.0)]))
+            .with_cell_factory(CellRole::Logic, move |_| Box::new(VnpProcessor::new(processor_config.clone())));
+
+        stem.sense(0.8);
+        assert_eq!(stem.live_cell_count(), 1);
+        stem.sense(0.9);
+        stem.tick();
+        assert!(stem.output() > 0.0);
+    }
+}
@@ -0,0 +1,340 @@
+//! Событийный симулятор спайковых сетей
+//!
+//! В отличие от пошагового [`NeuralNetwork`], [`EventSimulator`] не
+//! тратит работу на шаги без событий: спайки лежат в очереди с
+//! приоритетом по времени доставки, а затухание потенциала между
+//! событиями вычисляется аналитически. Модельное время (в микросекундах)
+//! не связано с реальным - симуляция идёт так быстро, как позволяет
+//! процессор, и воспроизводима: события с равным временем
+//! обрабатываются в порядке постановки в очередь.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap};
+
+use crate::config::simulation;
+use crate::network::{NetworkError, NeuralNetwork, NeuronId};
+
+/// Нейрон событийного симулятора
+///
+/// Потенциал экспоненциально затухает к нулю с постоянной времени `tau`,
+/// после спайка сбрасывается в ноль и `refractory` микросекунд не
+/// воспринимает вход
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventNeuron {
+    /// Порог срабатывания
+    pub threshold: f64,
+    /// Постоянная времени затухания, мкс
+    pub tau: f64,
+    /// Рефрактерный период, мкс
+    pub refractory: u64,
+    potential: f64,
+    last_update: u64,
+    refractory_until: u64,
+}
+
+impl EventNeuron {
+    /// Создать нейрон с параметрами по умолчанию
+    pub fn new() -> Self {
+        Self {
+            threshold: simulation::DEFAULT_THRESHOLD,
+            tau: simulation::DEFAULT_TAU_US,
+            refractory: simulation::DEFAULT_REFRACTORY_US,
+            potential: 0.0,
+            last_update: 0,
+            refractory_until: 0,
+        }
+    }
+
+    /// Установить порог срабатывания
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Установить постоянную времени затухания
+    pub fn with_tau(mut self, tau: f64) -> Self {
+        self.tau = tau.max(f64::EPSILON);
+        self
+    }
+
+    /// Установить рефрактерный период
+    pub fn with_refractory(mut self, refractory: u64) -> Self {
+        self.refractory = refractory;
+        self
+    }
+
+    /// Потенциал в момент `time` (не раньше последнего обновления)
+    pub fn potential_at(&self, time: u64) -> f64 {
+        let elapsed = time.saturating_sub(self.last_update) as f64;
+        self.potential * (-elapsed / self.tau).exp()
+    }
+
+    /// Принять вход в момент `time`, вернуть true при спайке
+    fn receive(&mut self, time: u64, weight: f64) -> bool {
+        self.potential = self.potential_at(time);
+        self.last_update = time;
+        if time < self.refractory_until {
+            return false;
+        }
+
+        self.potential += weight;
+        if self.potential < self.threshold {
+            return false;
+        }
+        self.potential = 0.0;
+        self.refractory_until = time + self.refractory;
+        true
+    }
+}
+
+impl Default for EventNeuron {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Спайк в очереди симулятора
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpikeEvent {
+    /// Время доставки, мкс
+    pub time: u64,
+    /// Нейрон-получатель
+    pub target: NeuronId,
+    /// Сила входа
+    pub weight: f64,
+    /// Порядковый номер постановки в очередь
+    seq: u64,
+}
+
+impl Eq for SpikeEvent {}
+
+impl Ord for SpikeEvent {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.time, self.seq).cmp(&(other.time, other.seq))
+    }
+}
+
+impl PartialOrd for SpikeEvent {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Исходящая связь нейрона
+#[derive(Debug, Clone, Copy)]
+struct Connection {
+    post: NeuronId,
+    weight: f64,
+    delay: u64,
+}
+
+/// Событийный симулятор спайковой сети
+#[derive(Default)]
+pub struct EventSimulator {
+    neurons: BTreeMap<NeuronId, EventNeuron>,
+    outgoing: BTreeMap<NeuronId, Vec<Connection>>,
+    queue: BinaryHeap<Reverse<SpikeEvent>>,
+    /// Журнал спайков: (время, нейрон)
+    spikes: Vec<(u64, NeuronId)>,
+    next_neuron: u32,
+    next_seq: u64,
+    processed: u64,
+    now: u64,
+}
+
+impl EventSimulator {
+    /// Создать пустой симулятор
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Перенести топологию пошаговой сети
+    ///
+    /// Нейроны сохраняют идентификаторы и получают параметры `neuron`,
+    /// задержка синапса в шагах умножается на `step` микросекунд
+    pub fn from_network(network: &NeuralNetwork, neuron: EventNeuron, step: u64) -> Self {
+        let mut simulator = Self::new();
+        for id in network.neuron_ids() {
+            simulator.neurons.insert(id, neuron.clone());
+            simulator.next_neuron = simulator.next_neuron.max(id.0 + 1);
+        }
+        for (_, synapse) in network.synapses() {
+            simulator
+                .connect(synapse.pre, synapse.post, synapse.weight, synapse.delay as u64 * step)
+                .expect("synapse ends are network neurons");
+        }
+        simulator
+    }
+
+    /// Добавить нейрон
+    pub fn add_neuron(&mut self, neuron: EventNeuron) -> NeuronId {
+        let id = NeuronId(self.next_neuron);
+        self.next_neuron += 1;
+        self.neurons.insert(id, neuron);
+        id
+    }
+
+    /// Соединить нейроны (задержка не меньше 1 мкс)
+    pub fn connect(&mut self, pre: NeuronId, post: NeuronId, weight: f64, delay: u64) -> Result<(), NetworkError> {
+        for id in [pre, post] {
+            if !self.neurons.contains_key(&id) {
+                return Err(NetworkError::UnknownNeuron(id));
+            }
+        }
+        self.outgoing.entry(pre).or_default().push(Connection {
+            post,
+            weight,
+            delay: delay.max(1),
+        });
+        Ok(())
+    }
+
+    /// Запланировать внешний вход нейрону
+    ///
+    /// Вход в прошлом доставляется в текущий момент
+    pub fn inject(&mut self, target: NeuronId, time: u64, weight: f64) -> Result<(), NetworkError> {
+        if !self.neurons.contains_key(&target) {
+            return Err(NetworkError::UnknownNeuron(target));
+        }
+        self.schedule(time.max(self.now), target, weight);
+        Ok(())
+    }
+
+    /// Текущее модельное время, мкс
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Количество событий в очереди
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Количество обработанных событий
+    pub fn processed(&self) -> u64 {
+        self.processed
+    }
+
+    /// Журнал спайков в порядке возникновения
+    pub fn spikes(&self) -> &[(u64, NeuronId)] {
+        &self.spikes
+    }
+
+    /// Потенциал нейрона в текущий момент
+    pub fn potential(&self, id: NeuronId) -> Option<f64> {
+        self.neurons.get(&id).map(|n| n.potential_at(self.now))
+    }
+
+    /// Время ближайшего события
+    pub fn next_event_time(&self) -> Option<u64> {
+        self.queue.peek().map(|Reverse(event)| event.time)
+    }
+
+    /// Обработать ближайшее событие, вернуть нейрон, если он сработал
+    pub fn process_next(&mut self) -> Option<(SpikeEvent, bool)> {
+        let Reverse(event) = self.queue.pop()?;
+        self.now = event.time;
+        self.processed += 1;
+
+        let Some(neuron) = self.neurons.get_mut(&event.target) else {
+            return Some((event, false));
+        };
+        let fired = neuron.receive(event.time, event.weight);
+        if fired {
+            self.spikes.push((event.time, event.target));
+            let count = self.outgoing.get(&event.target).map_or(0, Vec::len);
+            for index in 0..count {
+                let connection = self.outgoing[&event.target][index];
+                self.schedule(event.time + connection.delay, connection.post, connection.weight);
+            }
+        }
+        Some((event, fired))
+    }
+
+    /// Обработать все события до момента `until` включительно
+    ///
+    /// Модельное время становится равным `until`. Возвращает число спайков
+    pub fn run_until(&mut self, until: u64) -> usize {
+        let before = self.spikes.len();
+        while self.next_event_time().is_some_and(|time| time <= until) {
+            self.process_next();
+        }
+        self.now = self.now.max(until);
+        self.spikes.len() - before
+    }
+
+    /// Обработать не больше `max_events` событий, вернуть число спайков
+    pub fn run_events(&mut self, max_events: usize) -> usize {
+        let before = self.spikes.len();
+        for _ in 0..max_events {
+            if self.process_next().is_none() {
+                break;
+            }
+        }
+        self.spikes.len() - before
+    }
+
+    fn schedule(&mut self, time: u64, target: NeuronId, weight: f64) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.queue.push(Reverse(SpikeEvent { time, target, weight, seq }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::NetworkBuilder;
+
+    #[test]
+    fn test_analytic_decay_between_events() {
+        let mut simulator = EventSimulator::new();
+        let id = simulator.add_neuron(EventNeuron::new().with_tau(1_000.0));
+
+        simulator.inject(id, 0, 0.5).unwrap();
+        simulator.inject(id, 1_000, 0.5).unwrap();
+        simulator.run_until(500);
+        assert!((simulator.potential(id).unwrap() - 0.5 * (-0.5f64).exp()).abs() < 1e-12);
+
+        // 0.5 * e^-1 + 0.5 ниже порога 0.7
+        assert_eq!(simulator.run_until(1_000), 0);
+        assert!((simulator.potential(id).unwrap() - (0.5 * (-1.0f64).exp() + 0.5)).abs() < 1e-12);
+        assert_eq!(simulator.now(), 1_000);
+    }
+
+    #[test]
+    fn test_equal_timestamps_keep_schedule_order() {
+        let mut simulator = EventSimulator::new();
+        let id = simulator.add_neuron(EventNeuron::new().with_threshold(1.0).with_refractory(10));
+
+        // Спайк на первом входе, второй попадает в рефрактерный период
+        simulator.inject(id, 5, 1.0).unwrap();
+        simulator.inject(id, 5, -1.0).unwrap();
+        let (first, fired) = simulator.process_next().unwrap();
+        assert_eq!((first.weight, fired), (1.0, true));
+        let (second, fired) = simulator.process_next().unwrap();
+        assert_eq!((second.weight, fired), (-1.0, false));
+        assert_eq!(simulator.potential(id), Some(0.0));
+    }
+
+    #[test]
+    fn test_network_runs_reproducibly() {
+        let network = NetworkBuilder::new(30).small_world(2, 0.2).with_seed(5).with_weight(0.4).with_delay(2).build();
+
+        let run = || {
+            let step = simulation::DEFAULT_STEP_US;
+            let mut simulator = EventSimulator::from_network(&network, EventNeuron::new(), step);
+            let first = network.neuron_ids().next().unwrap();
+            for t in 0..5 {
+                simulator.inject(first, t * step, 1.0).unwrap();
+            }
+            simulator.run_until(100_000);
+            simulator.spikes().to_vec()
+        };
+
+        let spikes = run();
+        assert!(spikes.len() > 5);
+        assert!(spikes.windows(2).all(|w| w[0].0 <= w[1].0));
+        assert_eq!(spikes, run());
+    }
+}